@@ -60,7 +60,7 @@ fn kekw(c: &mut Criterion) {
     );
     c.bench_function("song_length", |c| {
         c.iter(|| {
-            black_box(entries.durations.get(&lth).unwrap());
+            black_box(entries.durations().get(&lth).unwrap());
         })
     });
 
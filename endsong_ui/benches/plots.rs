@@ -32,24 +32,24 @@ fn traces(c: &mut Criterion) {
 
     c.bench_function("absolute", |c| {
         c.iter(|| {
-            black_box(trace::absolute(&entries, &powerwolf));
+            black_box(trace::absolute(&entries, &powerwolf, None));
         })
     });
     c.bench_function("relative", |c| {
         c.iter(|| {
-            black_box(trace::relative::to_all(&entries, &powerwolf));
+            black_box(trace::relative::to_all(&entries, &powerwolf, None, None));
         })
     });
 
     c.bench_function("relative_to_artist", |c| {
         c.iter(|| {
-            black_box(trace::relative::to_artist(&entries, &coat));
+            black_box(trace::relative::to_artist(&entries, &coat, None, None));
         })
     });
 
     c.bench_function("relative_to_album", |c| {
         c.iter(|| {
-            black_box(trace::relative::to_album(&entries, &spart));
+            black_box(trace::relative::to_album(&entries, &spart, None, None));
         })
     });
 }
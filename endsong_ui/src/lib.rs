@@ -20,6 +20,7 @@
 
 pub mod plot;
 pub mod print;
+pub mod summarize;
 pub mod trace;
 pub mod ui;
 
@@ -43,6 +44,7 @@ pub const fn spaces(num: usize) -> &'static str {
 pub mod prelude {
     pub use crate::plot;
     pub use crate::print;
+    pub use crate::summarize;
     pub use crate::trace;
     pub use crate::ui;
 
@@ -24,7 +24,7 @@ use tracing_subscriber::filter::{EnvFilter, LevelFilter};
 
 /// Intializes the data,
 /// tests some functions using [`test()`] and
-/// starts the shell instance
+/// starts the shell instance (or executes a single command with `--exec`)
 fn main() {
     let env = EnvFilter::builder()
         .with_default_directive(LevelFilter::ERROR.into())
@@ -51,29 +51,47 @@ fn main() {
     // test_two(&entries);
     // test_plot(&entries);
 
-    ui::start(&entries);
+    // e.g. `endsong_ui --exec "print top artists"`
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--exec") {
+        let Some(command) = args.get(pos + 1) else {
+            eprintln!("--exec requires a command!");
+            std::process::exit(1);
+        };
+        if !ui::exec(&entries, command) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    ui::start(&entries, &paths);
 }
 
 /// tests various [`print`][crate::print] and [`endsong::gather`] functions
 #[allow(dead_code)]
 fn test(entries: &SongEntries) {
-    print::top(entries, Aspect::Songs, 10, false);
-    print::top(entries, Aspect::Albums, 10, false);
-    print::top(entries, Aspect::Artists, 10, false);
+    let settings = print::Settings::default();
+    print::top(&settings, entries, Aspect::Songs, 10, false);
+    print::top(&settings, entries, Aspect::Albums, 10, false);
+    print::top(&settings, entries, Aspect::Artists, 10, false);
 
     let powerwolf = Artist::new("Powerwolf");
-    print::top_from_artist(entries, Mode::Songs, &powerwolf, 10);
-    print::top_from_artist(entries, Mode::Albums, &powerwolf, 10);
+    print::top_from_artist(&settings, entries, Mode::Songs, &powerwolf, 10);
+    print::top_from_artist(&settings, entries, Mode::Albums, &powerwolf, 10);
 
     let coat = Album::new("Coat of Arms", "Sabaton");
-    print::top_from_album(entries, &coat, 50);
+    print::top_from_album(&settings, entries, &coat, 50);
 
     let final_solution = Song::new("The Final Solution", "Coat of Arms", "Sabaton");
-    print::aspect(entries, &AspectFull::Artist(&Artist::new("Sabaton")));
+    print::aspect(
+        &settings,
+        entries,
+        &AspectFull::Artist(&Artist::new("Sabaton")),
+    );
     println!();
-    print::aspect(entries, &AspectFull::Album(&coat));
+    print::aspect(&settings, entries, &AspectFull::Album(&coat));
     println!();
-    print::aspect(entries, &AspectFull::Song(&final_solution));
+    print::aspect(&settings, entries, &AspectFull::Song(&final_solution));
 
     dbg!(entries.find().artist("Sabaton").unwrap());
     dbg!(entries.find().album("COAT OF ARMS", "sabaton").unwrap());
@@ -101,13 +119,21 @@ fn test(entries: &SongEntries) {
     let end_date = parse_date("2022-07-01").unwrap();
 
     print::aspect_date(
+        &settings,
         entries,
         &AspectFull::Artist(&powerwolf),
         &start_date,
         &end_date,
     );
-    print::aspect_date(entries, &AspectFull::Album(&coat), &start_date, &end_date);
     print::aspect_date(
+        &settings,
+        entries,
+        &AspectFull::Album(&coat),
+        &start_date,
+        &end_date,
+    );
+    print::aspect_date(
+        &settings,
         entries,
         &AspectFull::Song(&final_solution),
         &start_date,
@@ -127,6 +153,7 @@ fn test(entries: &SongEntries) {
     dbg!(gather::listening_time(entries.between(&start, &end)).num_minutes());
 
     print::aspect(
+        &settings,
         entries,
         &AspectFull::Album(&Album::new("Built To Last", "HammerFall")),
     );
@@ -140,7 +167,7 @@ fn test_two(entries: &SongEntries) {
         .find()
         .song_from_album("STYX HELIX", "eYe's", "MYTH & ROID")
         .is_some());
-    let a = entries.durations.get(&s).unwrap();
+    let a = entries.durations().get(&s).unwrap();
     dbg!(a.num_minutes(), a.num_seconds() - a.num_minutes() * 60);
     dbg!(a.display());
 
@@ -151,9 +178,9 @@ fn test_two(entries: &SongEntries) {
         println!(
             "{} - {}",
             song.name,
-            entries.durations.get(song).unwrap().display()
+            entries.durations().get(song).unwrap().display()
         );
-        alb_dur += *entries.durations.get(song).unwrap();
+        alb_dur += *entries.durations().get(song).unwrap();
     }
     dbg!(alb_dur.display(), ct_songs.len());
 
@@ -165,6 +192,7 @@ fn test_two(entries: &SongEntries) {
 /// tests various [`plot`] functions
 #[allow(dead_code)]
 fn test_plot(entries: &SongEntries) {
+    let style = plot::Style::default();
     // plot::absolute::create(entries, &types::Artist::from_str("Sabaton"));
 
     let stand = Album::new("The Last Stand", "Sabaton");
@@ -172,16 +200,20 @@ fn test_plot(entries: &SongEntries) {
     // plot::relative::to_artist(entries, &coat);
 
     // plot::single(plot::absolute::aspect(entries, &stand));
-    plot::single((trace::absolute(entries, &stand), String::from("test")));
+    plot::single(
+        &style,
+        (trace::absolute(entries, &stand, None), String::from("test")),
+    );
 
     let eminem = Artist::new("Eminem");
     plot::compare(
+        &style,
         (
-            trace::relative::to_artist(entries, &stand),
+            trace::relative::to_artist(entries, &stand, None, None),
             String::from("test"),
         ),
         (
-            trace::relative::to_all(entries, &eminem),
+            trace::relative::to_all(entries, &eminem, None, None),
             String::from("test"),
         ),
     );
@@ -1,64 +1,434 @@
 //! Module responsible for plotting/charts
 
-use plotly::{Layout, Plot};
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+use plotly::common::{DashType, Fill, Font, Mode, Orientation, Title};
+use plotly::layout::themes::BuiltinTheme;
+use plotly::layout::{
+    Annotation, Axis, AxisSide, AxisType, BarMode, GridPattern, LayoutGrid, Shape, ShapeLine,
+    ShapeType,
+};
+use plotly::{Bar, Histogram, Layout, Plot, Scatter, ScatterPolar};
+use serde::{Deserialize, Deserializer};
 
 use crate::trace::TraceType;
 
+/// Style and output settings of the plots passed to the plot functions
+///
+/// Loaded from the `[plot]` section of the shell's config file
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style {
+    /// Light or dark background
+    pub theme: Theme,
+    /// Font family of all text, e.g. "Arial"
+    pub font: Option<String>,
+    /// Colors of the traces in order, e.g. `["#1f77b4", "red"]`
+    ///
+    /// Uses plotly's default colors if empty
+    pub colorway: Vec<String>,
+    /// Whether to also write the data of each plot
+    /// to a `.csv` file next to the `.html` one
+    pub csv: bool,
+    /// Whether plots of absolute amounts over time use a logarithmic y-axis,
+    /// e.g. for comparing an artist with thousands of plays against one with a few hundred
+    ///
+    /// Not used for plots of shares or daily averages, which can be zero
+    pub log_scale: bool,
+    /// Events shown on all plots over time
+    pub events: Vec<Event>,
+}
+
+/// A dated event (e.g. "saw them live") shown as a vertical line
+/// on all plots over time
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Event {
+    /// Day of the event, in the YYYY-MM-DD format in the config file
+    #[serde(deserialize_with = "deserialize_date")]
+    pub date: NaiveDate,
+    /// Text shown next to the line
+    pub label: String,
+}
+
+/// Used to deserialize [`Event::date`] from a YYYY-MM-DD string
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    let date = String::deserialize(deserializer)?;
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(serde::de::Error::custom)
+}
+
+/// Background of the plots
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    /// plotly's default theme
+    #[default]
+    Light,
+    /// plotly's dark theme
+    Dark,
+}
+
+/// Returns a [`Layout`] with the given title and [`Style`]
+fn layout(style: &Style, title: &str) -> Layout {
+    let theme = match style.theme {
+        Theme::Light => BuiltinTheme::Default,
+        Theme::Dark => BuiltinTheme::PlotlyDark,
+    };
+    let mut layout = Layout::new()
+        .title(format!("<b>{title}</b>"))
+        .template(theme.build());
+
+    if let Some(font) = &style.font {
+        layout = layout.font(Font::new().family(font));
+    }
+    if !style.colorway.is_empty() {
+        layout = layout.colorway(style.colorway.clone());
+    }
+
+    layout
+}
+
+/// Adds the [`Style::events`] as vertical lines to the layout of a plot over time
+fn with_events(style: &Style, layout: Layout) -> Layout {
+    let events = &style.events;
+    if events.is_empty() {
+        return layout;
+    }
+
+    let mut shapes = Vec::with_capacity(events.len());
+    let mut annotations = Vec::with_capacity(events.len());
+    for event in events {
+        let date = event.date.format("%Y-%m-%d").to_string();
+        shapes.push(
+            Shape::new()
+                .shape_type(ShapeType::Line)
+                .x_ref("x")
+                .y_ref("paper")
+                .x0(&date)
+                .x1(&date)
+                .y0(0.0)
+                .y1(1.0)
+                .line(ShapeLine::new().dash(DashType::Dash)),
+        );
+        annotations.push(
+            Annotation::new()
+                .text(&event.label)
+                .x_ref("x")
+                .y_ref("paper")
+                .x(&date)
+                .y(1.0)
+                .show_arrow(false),
+        );
+    }
+
+    layout.shapes(shapes).annotations(annotations)
+}
+
+/// Returns whether a plot of `traces` over time uses a logarithmic y-axis
+///
+/// Only if [`Style::log_scale`] is set and all traces are absolute amounts
+/// (i.e. [`TraceType::Absolute`], [`TraceType::Duration`] or [`TraceType::Binned`])
+fn log_scale<'a>(style: &Style, mut traces: impl Iterator<Item = &'a TraceType>) -> bool {
+    style.log_scale
+        && traces.all(|trace| {
+            matches!(
+                trace,
+                TraceType::Absolute(_) | TraceType::Duration(_) | TraceType::Binned(_)
+            )
+        })
+}
+
+/// Returns `layout` with a logarithmic y-axis
+fn with_log_scale(layout: Layout) -> Layout {
+    layout.y_axis(Axis::new().type_(AxisType::Log))
+}
+
 /// Creates a plot in the `plots/` folder
 ///
 /// Then opens it in the browser
-pub fn single(trace: (TraceType, String)) {
+///
+/// Uses a logarithmic y-axis if [`Style::log_scale`] is set
+/// and the trace is an absolute amount
+pub fn single(style: &Style, trace: (TraceType, String)) {
     let title = trace.1;
+    let log_scale = log_scale(style, std::iter::once(&trace.0));
     let mut plot = Plot::new();
     plot.add_trace(trace.0.get_inner());
 
-    // sets the title of the plot
-    let layout = Layout::new().title(format!("<b>{title}</b>"));
+    // sets the title and style of the plot
+    let mut layout = with_events(style, layout(style, &title));
+    if log_scale {
+        layout = with_log_scale(layout);
+    }
     plot.set_layout(layout);
 
-    write_and_open_plot(&plot, &title);
+    write_and_open_plot(style, &plot, &title);
 }
 
 /// Compares two traces in a single plot in the `plots/` folder
 ///
 /// Then opens it in the browser
-pub fn compare(trace_one: (TraceType, String), trace_two: (TraceType, String)) {
+///
+/// Uses a logarithmic y-axis if [`Style::log_scale`] is set
+/// and both traces are absolute amounts
+pub fn compare(style: &Style, trace_one: (TraceType, String), trace_two: (TraceType, String)) {
     let title = format!("{} vs {}", trace_one.1, trace_two.1);
+    let log_scale = log_scale(style, [&trace_one.0, &trace_two.0].into_iter());
     let mut plot = Plot::new();
     plot.add_trace(trace_one.0.get_inner());
     plot.add_trace(trace_two.0.get_inner());
 
-    // sets the title of the plot
-    let layout = Layout::new().title(format!("<b>{title}</b>"));
+    // sets the title and style of the plot
+    let mut layout = with_events(style, layout(style, &title));
+    if log_scale {
+        layout = with_log_scale(layout);
+    }
     plot.set_layout(layout);
 
-    write_and_open_plot(&plot, &title);
+    write_and_open_plot(style, &plot, &title);
 }
 
 /// Plots multiple traces in a single plot in the `plots/` folder
 ///
 /// Then opens it in the browser
-pub fn multiple(traces: Vec<TraceType>, title: &str) {
+///
+/// Uses a logarithmic y-axis if [`Style::log_scale`] is set,
+/// all traces are absolute amounts and the x-axis is a time axis
+///
+/// `time_axis` - whether the x-axis is a time axis, so that the [`Style::events`]
+/// can be shown on it (not e.g. for the January to December axis of [`trace::years`][crate::trace::years])
+pub fn multiple(style: &Style, traces: Vec<TraceType>, title: &str, time_axis: bool) {
     let mut plot = Plot::new();
 
+    let log_scale = time_axis && log_scale(style, traces.iter());
+
+    let average_title = traces.iter().find_map(|trace| match trace {
+        TraceType::Average(_) => Some("average daily plays"),
+        TraceType::DurationAverage(_) => Some("average daily minutes"),
+        _ => None,
+    });
     for trace in traces {
         plot.add_trace(trace.get_inner());
     }
 
-    // sets the title of the plot
-    let layout = Layout::new().title(format!("<b>{title}</b>"));
+    // sets the title and style of the plot
+    let mut layout = layout(style, title);
+    if time_axis {
+        layout = with_events(style, layout);
+    }
+    if log_scale {
+        layout = with_log_scale(layout);
+    }
+    if let Some(average_title) = average_title {
+        // daily averages are way smaller than the other traces
+        layout = layout.y_axis2(
+            Axis::new()
+                .title(Title::new(average_title))
+                .overlaying("y")
+                .side(AxisSide::Right),
+        );
+    }
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a horizontal bar chart in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// * `bars` - labels with their values, from top to bottom
+/// * `unit` - what the values are, e.g. "plays" or "minutes"
+pub fn bar(style: &Style, bars: Vec<(String, usize)>, title: &str, unit: &str) {
+    // plotly draws horizontal bars from bottom to top
+    let (labels, values): (Vec<String>, Vec<usize>) = bars.into_iter().rev().unzip();
+    let texts = values.iter().map(usize::to_string).collect::<Vec<String>>();
+
+    let trace = Bar::new(values, labels)
+        .orientation(Orientation::Horizontal)
+        .text_array(texts)
+        .name(unit);
+
+    let mut plot = Plot::new();
+    plot.add_trace(trace);
+
+    let layout = layout(style, title)
+        .x_axis(Axis::new().title(Title::new(unit)))
+        .show_legend(false);
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a vertical bar chart in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// * `columns` - labels with their values, from left to right
+/// * `unit` - what the values are, e.g. "plays" or "artists"
+pub fn columns(style: &Style, columns: Vec<(String, usize)>, title: &str, unit: &str) {
+    let (labels, values): (Vec<String>, Vec<usize>) = columns.into_iter().unzip();
+
+    let trace = Bar::new(labels, values).name(unit);
+
+    let mut plot = Plot::new();
+    plot.add_trace(trace);
+
+    let layout = layout(style, title)
+        .y_axis(Axis::new().title(Title::new(unit)))
+        .show_legend(false);
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a radial chart of the hours of the day ("listening clock")
+/// in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// The hours are drawn as a filled area around the center
+/// (the plotly version used doesn't have polar bar charts)
+///
+/// * `hours` - index `0` is the value between 00:00 and 00:59 and so on
+/// * `unit` - what the values are, e.g. "plays" or "minutes"
+pub fn clock(style: &Style, hours: [usize; 24], title: &str, unit: &str) {
+    // the first hour is repeated to close the loop
+    let labels = (0..=24)
+        .map(|hour| format!("{:02}:00", hour % 24))
+        .collect::<Vec<String>>();
+    let values = hours
+        .iter()
+        .chain(hours.first())
+        .copied()
+        .collect::<Vec<usize>>();
+
+    let trace = ScatterPolar::new(labels, values)
+        .mode(Mode::LinesMarkers)
+        .fill(Fill::ToSelf)
+        .name(unit);
+
+    let mut plot = Plot::new();
+    plot.add_trace(trace);
+
+    let layout = layout(style, title).show_legend(false);
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a chart of shares in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// The shares are displayed as a single 100% stacked bar
+/// (the plotly version used doesn't have pie charts)
+///
+/// `shares` - labels with their share in percent, from left to right
+pub fn share(style: &Style, shares: Vec<(String, f64)>, title: &str) {
+    let mut plot = Plot::new();
+
+    for (label, percent) in shares {
+        let trace = Bar::new(vec![percent], vec![String::from("share")])
+            .orientation(Orientation::Horizontal)
+            .text_array(vec![format!("{label}: {percent:.2}%")])
+            .name(label);
+        plot.add_trace(trace);
+    }
+
+    let layout = layout(style, title)
+        .bar_mode(BarMode::Stack)
+        .x_axis(Axis::new().title(Title::new("%")));
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a plot of listening sessions in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// * `lengths` - length of each session in minutes, shown as a histogram
+/// * `per_month` - months (`YYYY-MM`) with their number of sessions,
+///   shown as a bar chart below the histogram
+pub fn sessions(style: &Style, lengths: Vec<i64>, per_month: Vec<(String, usize)>, title: &str) {
+    let histogram = Histogram::new(lengths).name("sessions");
+
+    let (months, counts): (Vec<String>, Vec<usize>) = per_month.into_iter().unzip();
+    let bar = Bar::new(months, counts)
+        .name("sessions per month")
+        .x_axis("x2")
+        .y_axis("y2");
+
+    let mut plot = Plot::new();
+    plot.add_trace(histogram);
+    plot.add_trace(bar);
+
+    let layout = layout(style, title)
+        .grid(
+            LayoutGrid::new()
+                .rows(2)
+                .columns(1)
+                .pattern(GridPattern::Independent),
+        )
+        .x_axis(Axis::new().title(Title::new("session length in minutes")))
+        .y_axis(Axis::new().title(Title::new("sessions")))
+        .x_axis2(Axis::new().title(Title::new("month")))
+        .y_axis2(Axis::new().title(Title::new("sessions")))
+        .show_legend(false);
     plot.set_layout(layout);
 
-    write_and_open_plot(&plot, title);
+    write_and_open_plot(style, &plot, title);
+}
+
+/// Creates a plot of ranks over time in the `plots/` folder
+///
+/// Then opens it in the browser
+///
+/// `ranks` - months (`YYYY-MM`) with the rank in them, 1 being shown at the top
+#[allow(clippy::cast_precision_loss)]
+pub fn rank(style: &Style, ranks: Vec<(String, usize)>, title: &str) {
+    let worst = ranks.iter().map(|(_, rank)| *rank).max().unwrap_or(1);
+    let (months, ranks): (Vec<String>, Vec<usize>) = ranks.into_iter().unzip();
+
+    let trace = Scatter::new(months, ranks)
+        .mode(Mode::LinesMarkers)
+        .name("rank");
+
+    let mut plot = Plot::new();
+    plot.add_trace(trace);
+
+    // reversed range so that rank 1 is at the top
+    let layout = with_events(style, layout(style, title))
+        .y_axis(
+            Axis::new()
+                .title(Title::new("rank"))
+                .range(vec![worst as f64 + 0.5, 0.5]),
+        )
+        .show_legend(false);
+    plot.set_layout(layout);
+
+    write_and_open_plot(style, &plot, title);
 }
 
 /// Creates the plot .html in the plots/ folder and opens it in the browser
-fn write_and_open_plot(plot: &Plot, title: &str) {
+fn write_and_open_plot(style: &Style, plot: &Plot, title: &str) {
     // creates plots/ folder
     std::fs::create_dir_all("plots").unwrap();
 
     let title = normalize_path(title);
 
+    if style.csv {
+        let path = format!("plots/{title}.csv");
+        let written = to_csv(&plot.to_json())
+            .map_err(std::io::Error::from)
+            .and_then(|csv| std::fs::write(&path, csv));
+        if let Err(e) = written {
+            eprintln!("Couldn't write the plot data to {path}: {e}");
+        }
+    }
+
     // opens the plot in the browser
     match std::env::consts::OS {
         // see https://github.com/igiagkiozis/plotly/issues/132#issuecomment-1488920563
@@ -107,14 +477,49 @@ fn write_and_open_plot(plot: &Plot, title: &str) {
                 }
             }
         }
-    };
+    }
+}
+
+/// Converts the data of a plot (see [`Plot::to_json`]) to CSV
+///
+/// Each row is one point of a trace: the trace name and its x and y value.
+/// The y value is empty for traces without one (e.g. histograms)
+fn to_csv(plot_json: &str) -> Result<String, serde_json::Error> {
+    /// Returns the value as a CSV field, quoted if necessary
+    fn field(value: Option<&serde_json::Value>) -> String {
+        let text = match value {
+            None | Some(serde_json::Value::Null) => return String::new(),
+            Some(serde_json::Value::String(text)) => text.clone(),
+            Some(other) => other.to_string(),
+        };
+        if text.contains([',', '"', '\n']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text
+        }
+    }
+
+    let plot: serde_json::Value = serde_json::from_str(plot_json)?;
+
+    let mut csv = String::from("trace,x,y\n");
+    for trace in plot["data"].as_array().into_iter().flatten() {
+        let name = field(trace.get("name"));
+        let xs = trace["x"].as_array().map_or(&[][..], Vec::as_slice);
+        let ys = trace["y"].as_array().map_or(&[][..], Vec::as_slice);
+        for i in 0..xs.len().max(ys.len()) {
+            // unwrap ok - writing to a String can't fail
+            writeln!(csv, "{name},{},{}", field(xs.get(i)), field(ys.get(i))).unwrap();
+        }
+    }
+
+    Ok(csv)
 }
 
 /// Replaces Windows forbidden symbols in path with a '_'
 ///
 /// Also removes whitespace and replaces empty
 /// strings with "_"
-fn normalize_path(path: &str) -> String {
+pub(crate) fn normalize_path(path: &str) -> String {
     // https://stackoverflow.com/a/31976060
     // Array > HashSet bc of overhead
     let forbidden_characters = [' ', '<', '>', ':', '"', '/', '\\', '|', '?', '*'];
@@ -141,6 +546,38 @@ fn normalize_path(path: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn plot_data_to_csv() {
+        let json = r#"{"data": [
+            {"type": "scatter", "name": "Sabaton", "x": ["2020-01-01", "2020-01-02"], "y": [1, 2]},
+            {"type": "histogram", "name": "a, \"b\"", "x": [3.5]}
+        ], "layout": {}}"#;
+        assert_eq!(
+            to_csv(json).unwrap(),
+            "trace,x,y\nSabaton,2020-01-01,1\nSabaton,2020-01-02,2\n\"a, \"\"b\"\"\",3.5,\n"
+        );
+
+        assert!(to_csv("not json").is_err());
+    }
+
+    #[test]
+    fn log_scale_only_for_absolute_traces() {
+        let scatter = || Scatter::new(vec![String::from("2020-01-01")], vec![1.0]);
+        let absolute = TraceType::Absolute(scatter());
+        let duration = TraceType::Duration(scatter());
+        let relative = TraceType::Relative(scatter());
+        let average = TraceType::Average(scatter());
+
+        let style = Style {
+            log_scale: true,
+            ..Style::default()
+        };
+        assert!(log_scale(&style, [&absolute, &duration].into_iter()));
+        assert!(!log_scale(&style, [&absolute, &average].into_iter()));
+        assert!(!log_scale(&style, std::iter::once(&relative)));
+        assert!(!log_scale(&Style::default(), std::iter::once(&absolute)));
+    }
+
     #[test]
     fn normalize_paths() {
         // should change the forbidden symbols to '_' in these
@@ -2,20 +2,205 @@
 //! in a human-readable format (e.g. as 100 most played songs)
 //! to the [`std::io::stdout`]
 
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::rc::Rc;
 use std::str::FromStr;
 
+use chrono::Datelike;
 use endsong::prelude::*;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::spaces;
 
 /// Length of indent before a sub-aspect
 const INDENT_LENGTH: usize = 4;
 
+/// Length of the longest bar in terminal histograms
+const BAR_LENGTH: usize = 40;
+
+/// Length of the longest bar in top lists, see [`Settings::bars`]
+const TOP_BAR_LENGTH: usize = 20;
+
+/// Playcounts considered to be milestones, used by [`milestone()`]
+const MILESTONES: [usize; 16] = [
+    10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000,
+    500_000, 1_000_000,
+];
+
+/// How lists are printed, see [`Settings`]
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// aligned lists for reading them in the terminal
+    #[default]
+    Pretty,
+    /// headers are omitted and lists are printed as tab-separated columns
+    /// without alignment, e.g. for piping into `grep` or `cut`
+    Plain,
+    /// lists are printed as GitHub-flavored markdown tables,
+    /// e.g. for pasting them into gists or forum posts
+    Markdown,
+    /// top lists and aspects are printed as JSON (see [`top_data()`]
+    /// and [`aspect_data()`]) for other programs to consume,
+    /// everything else is printed like with [`Format::Plain`]
+    Json,
+}
+
+/// What the percentage of the plays shown next to the playcount
+/// is computed against, see [`Settings::share`]
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Share {
+    /// the plays of the whole dataset, e.g. `Powerwolf | 4863 plays (3.1% of all plays)`
+    All,
+    /// the plays of the parent aspect, i.e. the artist of an album
+    /// and the album of a song, e.g. `Blessed & Possessed | 1120 plays (23.0% of the artist)`.
+    /// Artists and top lists are still compared to all plays
+    Parent,
+}
+
+/// Settings of the printed output passed to the print functions,
+/// set by the `[print]` section of the [config][crate::ui::config]
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Format of the output - [`Format::Pretty`] if not set.
+    /// The shell uses [`Format::Plain`] instead if the output isn't a terminal
+    pub format: Option<Format>,
+    /// Whether [`top()`] and [`aspect()`] (and their date variants)
+    /// show the listening time next to the playcount,
+    /// e.g. `Sabaton | 423 plays | 31h 12m`
+    pub show_time: bool,
+    /// Whether and against what [`top()`] and [`aspect()`]
+    /// (and their date variants) show the percentage of the plays
+    /// next to the playcount - not shown if not set
+    pub share: Option<Share>,
+    /// Whether top lists (e.g. [`top()`]) show a bar next to each entry
+    /// proportional to its playcount (or listening time if ranked by it),
+    /// e.g. `#1: ████████████▍ | Sabaton | 423 plays`
+    ///
+    /// Only with [`Format::Pretty`] and [`Format::Markdown`]
+    pub bars: bool,
+    /// Whether [`aspect()`] (and [`aspect_date()`]) show a sparkline
+    /// of the monthly plays from the first play up to the last entry,
+    /// e.g. `▁▃█▅▂ ▁ (2020-04 to 2020-10)`
+    ///
+    /// Only with [`Format::Pretty`] and [`Format::Markdown`]
+    pub sparkline: bool,
+    /// Maximum width (in terminal columns) of names in aligned columns
+    /// like in [`top()`] - longer names are truncated with an ellipsis
+    ///
+    /// Only with [`Format::Pretty`]; [`None`] to never truncate
+    pub max_width: Option<usize>,
+}
+
+impl Settings {
+    /// Returns the format of the output, [`Format::Pretty`] if not set
+    fn format(&self) -> Format {
+        self.format.unwrap_or_default()
+    }
+
+    /// Returns `name` truncated to [`Settings::max_width`]
+    /// with an ellipsis at the end if it's wider
+    fn truncate(&self, name: &str) -> String {
+        match self.max_width {
+            Some(max_width) if name.width() > max_width => {
+                // leaving one column for the ellipsis
+                let mut width = 0;
+                let mut truncated: String = name
+                    .chars()
+                    .take_while(|c| {
+                        width += c.width().unwrap_or(0);
+                        width < max_width
+                    })
+                    .collect();
+                truncated.push('…');
+                truncated
+            }
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// Returns the percentage of `plays` in `total`, e.g. `3.1` for 3.1%
+#[allow(clippy::cast_precision_loss)]
+fn percent(plays: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (plays as f64 / total as f64) * 100.0
+}
+
+/// Returns `text` padded with spaces at the end to `width` terminal columns
+///
+/// Unlike `format!("{text:<width$}")` this takes the display width into account,
+/// e.g. that CJK characters take up two columns
+pub(crate) fn pad(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// Prints a header like `=== TOP 10 SONGS ===`
+///
+/// Omitted with [`Format::Plain`]
+fn header(settings: &Settings, title: std::fmt::Arguments) {
+    match settings.format() {
+        Format::Pretty => println!("=== {title} ==="),
+        Format::Plain | Format::Json => (),
+        Format::Markdown => println!("## {title}\n"),
+    }
+}
+
+/// Prints the first line of an [`aspect()`], e.g. `Sabaton | 423 plays`
+///
+/// As a heading with [`Format::Markdown`]
+fn summary(settings: &Settings, text: std::fmt::Arguments) {
+    match settings.format() {
+        Format::Pretty | Format::Plain | Format::Json => println!("{text}"),
+        Format::Markdown => println!("### {text}\n"),
+    }
+}
+
+/// Prints the header row of a markdown table with the given `columns`
+///
+/// Columns starting with `>` are right-aligned, e.g. for numbers
+fn markdown_header(columns: &[&str]) {
+    let names = columns
+        .iter()
+        .map(|column| column.trim_start_matches('>'))
+        .join(" | ");
+    let alignments = columns
+        .iter()
+        .map(|column| {
+            if column.starts_with('>') {
+                "--:"
+            } else {
+                "---"
+            }
+        })
+        .join("|");
+    println!("| {names} |\n|{alignments}|");
+}
+
+/// Prints `data` as pretty JSON
+fn print_json(data: &impl Serialize) {
+    // the result structs of this module can always be serialized
+    println!("{}", serde_json::to_string_pretty(data).unwrap());
+}
+
+/// Prints a row of a markdown table, escaping pipes in the `cells`
+fn markdown_row(cells: &[&dyn Display]) {
+    let cells = cells
+        .iter()
+        .map(|cell| cell.to_string().replace('|', "\\|"))
+        .join(" | ");
+    println!("| {cells} |");
+}
+
 /// An enum that is among other things used by functions such as
 /// [`top()`] and its derivatives to know whether
 /// to print top songs ([`Aspect::Songs`]), albums ([`Aspect::Albums`])
@@ -88,6 +273,9 @@ pub trait DurationUtils {
     /// Returns a string with the duration in the format `HH:MM:SS`
     /// or `MM:SS` (if the duration is less than an hour)
     fn display(&self) -> String;
+    /// Returns a string with the duration in hours and minutes,
+    /// e.g. `31h 12m` or `45m` (if the duration is less than an hour)
+    fn display_hours(&self) -> String;
 }
 impl DurationUtils for TimeDelta {
     fn display(&self) -> String {
@@ -101,68 +289,557 @@ impl DurationUtils for TimeDelta {
             format!("{minutes:02}:{seconds:02}")
         }
     }
+
+    fn display_hours(&self) -> String {
+        let hours = self.num_hours();
+        let minutes = self.num_minutes() % 60;
+        if hours > 0 {
+            format!("{hours}h {minutes}m")
+        } else {
+            format!("{minutes}m")
+        }
+    }
 }
 
 /// Prints the top `num` of an `asp`
 ///
 /// * `asp` - [`Aspect::Songs`] for top songs, [`Aspect::Albums`]
-///  for top albums and [`Aspect::Artists`] for top artists
+///   for top albums and [`Aspect::Artists`] for top artists
 /// * `num` - number of displayed top aspects.
-/// Will automatically change to total number of that aspect if `num` is higher than that
+///   Will automatically change to total number of that aspect if `num` is higher than that
 /// * `sum_songs_from_different_albums` - only matters if `asp` is [`Aspect::Songs`].
-/// If set to true, it will sum up the plays of
-/// one song across multiple albums it may be in.
-/// The album displayed in the parantheses will be the one it has the
-/// highest amount of listens from.
-pub fn top(entries: &[SongEntry], asp: Aspect, num: usize, sum_songs_from_different_albums: bool) {
+///   If set to true, it will sum up the plays of
+///   one song across multiple albums it may be in.
+///   The album displayed in the parantheses will be the one it has the
+///   highest amount of listens from.
+///
+/// The listening time is shown next to the playcount if set by [`Settings::show_time`]
+/// and the percentage of all plays if set by [`Settings::share`]
+pub fn top(
+    settings: &Settings,
+    entries: &[SongEntry],
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+) {
+    header(
+        settings,
+        format_args!("TOP {num} {}", asp.to_string().to_uppercase()),
+    );
+    print_top(
+        settings,
+        &top_data(entries, asp, num, sum_songs_from_different_albums),
+        false,
+        Some(gather::all_plays(entries)),
+    );
+}
+
+/// An aspect in a top list, see [`top_data()`]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TopItem {
+    /// Position in the list, starting at 1
+    pub position: usize,
+    /// Name of the aspect, e.g. `Sabaton - The Last Stand` for an album
+    pub name: String,
+    /// Playcount of the aspect
+    pub plays: usize,
+    /// Minutes listened, not set for lists that don't need it
+    /// like the top songs of an artist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minutes: Option<i64>,
+}
+
+/// Returns the top `num` of an `asp` as printed by [`top()`]
+///
+/// See [`top()`] for the arguments
+#[must_use]
+pub fn top_data(
+    entries: &[SongEntry],
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+) -> Vec<TopItem> {
     match asp {
+        Aspect::Songs if sum_songs_from_different_albums => {
+            // the album displayed for a summed song can differ between
+            // the playcount and the listening time map
+            let times: HashMap<(Rc<str>, Artist), TimeDelta> =
+                gather::songs_listening_time(entries, true)
+                    .into_iter()
+                    .map(|(song, time)| ((song.name, song.album.artist), time))
+                    .collect();
+            top_items(gather::songs(entries, true), num, |song| {
+                times
+                    .get(&(Rc::clone(&song.name), song.album.artist.clone()))
+                    .copied()
+            })
+        }
         Aspect::Songs => {
-            println!("=== TOP {num} SONGS ===");
-            top_helper(gather::songs(entries, sum_songs_from_different_albums), num);
+            let times = gather::songs_listening_time(entries, false);
+            top_items(gather::songs(entries, false), num, |song| {
+                times.get(song).copied()
+            })
         }
         Aspect::Albums => {
-            println!("=== TOP {num} ALBUMS ===");
-            top_helper(gather::albums(entries), num);
+            let times = gather::albums_listening_time(entries);
+            top_items(gather::albums(entries), num, |alb| times.get(alb).copied())
         }
         Aspect::Artists => {
-            println!("=== TOP {num} ARTISTS ===");
-            top_helper(gather::artists(entries), num);
+            let times = gather::artists_listening_time(entries);
+            top_items(gather::artists(entries), num, |art| times.get(art).copied())
         }
     }
 }
 
+/// Prints the top `num` of an `asp` ranked by listening time
+///
+/// Basically [`top()`] but ranked by the total time played
+/// instead of the playcount, displaying both
+///
+/// * `asp` - [`Aspect::Songs`] (affected by `sum_songs_from_different_albums`),
+///   [`Aspect::Albums`] or [`Aspect::Artists`]
+/// * `num` - number of displayed top aspects.
+///   Will automatically change to total number of that aspect if `num` is higher than that
+/// * `sum_songs_from_different_albums` - only matters if `asp` is [`Aspect::Songs`].
+///   If set to true, it will sum up the plays and time of
+///   one song across multiple albums it may be in.
+pub fn top_by_time(
+    settings: &Settings,
+    entries: &[SongEntry],
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+) {
+    header(
+        settings,
+        format_args!(
+            "TOP {num} {} BY LISTENING TIME",
+            asp.to_string().to_uppercase()
+        ),
+    );
+    match asp {
+        Aspect::Songs if sum_songs_from_different_albums => {
+            // the album displayed for a summed song can differ between
+            // the playcount and the listening time map
+            let plays: HashMap<(Rc<str>, Artist), usize> = gather::songs(entries, true)
+                .into_iter()
+                .map(|(song, plays)| ((song.name, song.album.artist), plays))
+                .collect();
+            top_by_time_helper(
+                settings,
+                gather::songs_listening_time(entries, true),
+                num,
+                |song| {
+                    plays
+                        .get(&(Rc::clone(&song.name), song.album.artist.clone()))
+                        .copied()
+                        .unwrap_or_default()
+                },
+            );
+        }
+        Aspect::Songs => {
+            let plays = gather::songs(entries, false);
+            top_by_time_helper(
+                settings,
+                gather::songs_listening_time(entries, false),
+                num,
+                |song| plays.get(song).copied().unwrap_or_default(),
+            );
+        }
+        Aspect::Albums => {
+            let plays = gather::albums(entries);
+            top_by_time_helper(
+                settings,
+                gather::albums_listening_time(entries),
+                num,
+                |alb| plays.get(alb).copied().unwrap_or_default(),
+            );
+        }
+        Aspect::Artists => {
+            let plays = gather::artists(entries);
+            top_by_time_helper(
+                settings,
+                gather::artists_listening_time(entries),
+                num,
+                |art| plays.get(art).copied().unwrap_or_default(),
+            );
+        }
+    }
+}
+
+/// Used by [`top_by_time()`]
+///
+/// `plays_of` - returns the playcount of an aspect for the second column
+fn top_by_time_helper<Asp: Music>(
+    settings: &Settings,
+    music_dict: HashMap<Asp, TimeDelta>,
+    num: usize,
+    plays_of: impl Fn(&Asp) -> usize,
+) {
+    // same sorting as in top_items(), just by time instead of plays
+    let items = music_dict
+        .into_iter()
+        .sorted_unstable_by_key(|(asp, time)| (Reverse(*time), asp.clone()))
+        .take(num)
+        .zip(1..)
+        .map(|((asp, time), position)| TopItem {
+            position,
+            name: asp.to_string(),
+            plays: plays_of(&asp),
+            minutes: Some(time.num_minutes()),
+        })
+        .collect_vec();
+
+    print_top(settings, &items, true, None);
+}
+
+/// Prints the top `num` of an `asp` in a date range
+///
+/// Basically [`top()`] but with date limitation
+///
+/// # Panics
+///
+/// Panics if `start` is after or equal to `end`
+pub fn top_date(
+    settings: &Settings,
+    entries: &SongEntries,
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+) {
+    assert!(start <= end, "Start date is after end date!");
+    let entries_within_dates = entries.between(start, end);
+    let (start, end) = normalize_dates(entries_within_dates, start, end);
+
+    header(
+        settings,
+        format_args!(
+            "TOP {num} {} | between {} and {}",
+            asp.to_string().to_uppercase(),
+            start.date_naive(),
+            end.date_naive()
+        ),
+    );
+    match asp {
+        Aspect::Songs => top_helper(
+            settings,
+            gather::songs(entries_within_dates, sum_songs_from_different_albums),
+            num,
+        ),
+        Aspect::Albums => top_helper(settings, gather::albums(entries_within_dates), num),
+        Aspect::Artists => top_helper(settings, gather::artists(entries_within_dates), num),
+    }
+}
+
+/// Prints how the top `num` of an `asp` changed between two date ranges
+///
+/// Shows the top list of the `second` range with the change of each rank
+/// compared to the top list of the `first` range (e.g. `▲ 2` or `new`),
+/// followed by the aspects that dropped out of the top list.
+/// See [`top()`] for the other arguments
+///
+/// # Panics
+///
+/// Panics if the start of a range is after its end
+pub fn top_delta(
+    settings: &Settings,
+    entries: &SongEntries,
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+    first: (&DateTime<Local>, &DateTime<Local>),
+    second: (&DateTime<Local>, &DateTime<Local>),
+) {
+    let delta = top_delta_data(
+        entries,
+        asp,
+        num,
+        sum_songs_from_different_albums,
+        first,
+        second,
+    );
+    if settings.format() == Format::Json {
+        return print_json(&delta);
+    }
+
+    // like normalize_dates(), but a range might not contain any entries
+    let date = |date: &DateTime<Local>| {
+        (*date)
+            .clamp(entries.first_date(), entries.last_date())
+            .date_naive()
+    };
+    header(
+        settings,
+        format_args!(
+            "TOP {num} {} | {} to {} vs {} to {}",
+            asp.to_string().to_uppercase(),
+            date(first.0),
+            date(first.1),
+            date(second.0),
+            date(second.1)
+        ),
+    );
+    if settings.format() == Format::Markdown {
+        markdown_header(&[">#", "Name", ">Plays", "Change"]);
+    }
+    let names = delta
+        .top
+        .iter()
+        .map(|item| settings.truncate(&item.name))
+        .collect_vec();
+    let name_width = names.iter().map(|name| name.width()).max().unwrap_or(0);
+
+    let max_num = delta.top.len();
+    for (item, pretty_name) in delta.top.iter().zip(&names) {
+        // top entries always have a position
+        let position = item.position.unwrap_or_default();
+        let change = match item.previous_position {
+            Some(previous) if previous <= num => match previous.cmp(&position) {
+                Ordering::Greater => format!("▲ {}", previous - position),
+                Ordering::Less => format!("▼ {}", position - previous),
+                Ordering::Equal => String::from("="),
+            },
+            Some(previous) => format!("new (was #{previous})"),
+            None => String::from("new"),
+        };
+        match settings.format() {
+            Format::Pretty => {
+                let indent = spaces((max_num.ilog10() - position.ilog10()) as usize);
+                println!(
+                    "{indent}#{position}: {} | {} plays | {change}",
+                    pad(pretty_name, name_width),
+                    item.plays
+                );
+            }
+            Format::Plain | Format::Json => {
+                println!("{position}\t{}\t{}\t{change}", item.name, item.plays);
+            }
+            Format::Markdown => markdown_row(&[&position, &item.name, &item.plays, &change]),
+        }
+    }
+
+    if delta.dropouts.is_empty() {
+        return;
+    }
+    match settings.format() {
+        Format::Pretty => println!("Dropped out:"),
+        Format::Plain | Format::Json => (),
+        Format::Markdown => {
+            println!("\n#### Dropped out\n");
+            markdown_header(&[">Was #", "Name", ">Plays", "Now"]);
+        }
+    }
+    for item in &delta.dropouts {
+        // dropouts were always in the first top list
+        let previous = item.previous_position.unwrap_or_default();
+        let now = item.position.map_or_else(
+            || String::from("no plays"),
+            |position| format!("now #{position}"),
+        );
+        match settings.format() {
+            Format::Pretty => println!(
+                "{}#{previous}: {} | {} plays | {now}",
+                spaces(INDENT_LENGTH),
+                item.name,
+                item.plays
+            ),
+            Format::Plain | Format::Json => {
+                println!(
+                    "-\t{}\t{}\tdropped from #{previous}, {now}",
+                    item.name, item.plays
+                );
+            }
+            Format::Markdown => markdown_row(&[&previous, &item.name, &item.plays, &now]),
+        }
+    }
+}
+
+/// Ranks of two top lists compared by [`top_delta()`]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TopDelta {
+    /// The top list of the second date range
+    pub top: Vec<RankDelta>,
+    /// Aspects in the top list of the first date range
+    /// which aren't in the top list of the second one
+    pub dropouts: Vec<RankDelta>,
+}
+
+/// An aspect ranked in two date ranges, see [`TopDelta`]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RankDelta {
+    /// Name of the aspect, e.g. `Sabaton - The Last Stand` for an album
+    pub name: String,
+    /// Playcount of the aspect in the second date range
+    pub plays: usize,
+    /// Rank in the second date range - not set if it wasn't played
+    pub position: Option<usize>,
+    /// Rank in the first date range - not set if it wasn't played
+    pub previous_position: Option<usize>,
+}
+
+/// Returns the changes between two top lists as printed by [`top_delta()`]
+///
+/// The ranks are those in the full rankings of the date ranges, so an aspect
+/// new to the top list can still have a [`RankDelta::previous_position`]
+///
+/// # Panics
+///
+/// Panics if the start of a range is after its end
+#[must_use]
+pub fn top_delta_data(
+    entries: &SongEntries,
+    asp: Aspect,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+    first: (&DateTime<Local>, &DateTime<Local>),
+    second: (&DateTime<Local>, &DateTime<Local>),
+) -> TopDelta {
+    assert!(first.0 <= first.1, "Start date is after end date!");
+    assert!(second.0 <= second.1, "Start date is after end date!");
+    let ranking = |(start, end): (&DateTime<Local>, &DateTime<Local>)| {
+        top_data(
+            entries.between(start, end),
+            asp,
+            usize::MAX,
+            sum_songs_from_different_albums,
+        )
+    };
+    let previous = ranking(first);
+    let current = ranking(second);
+
+    let position_in = |ranking: &[TopItem], name: &str| {
+        ranking
+            .iter()
+            .find(|item| item.name == name)
+            .map(|item| item.position)
+    };
+
+    let top = current
+        .iter()
+        .take(num)
+        .map(|item| RankDelta {
+            name: item.name.clone(),
+            plays: item.plays,
+            position: Some(item.position),
+            previous_position: position_in(&previous, &item.name),
+        })
+        .collect();
+    let dropouts = previous
+        .iter()
+        .take(num)
+        .filter_map(|item| {
+            let now = current.iter().find(|now| now.name == item.name);
+            if now.is_some_and(|now| now.position <= num) {
+                return None;
+            }
+            Some(RankDelta {
+                name: item.name.clone(),
+                plays: now.map_or(0, |now| now.plays),
+                position: now.map(|now| now.position),
+                previous_position: Some(item.position),
+            })
+        })
+        .collect();
+
+    TopDelta { top, dropouts }
+}
+
 /// Prints top songs or albums from an artist
 ///
 /// * `mode` - [`Mode::Songs`] for top songs and [`Mode::Albums`] for top albums
 /// * `artist` - the [`Artist`] you want the top songs/albums from
 /// * `num` - number of displayed top songs/albums.
-/// Will automatically change to total number of that aspect if `num` is higher than that
-pub fn top_from_artist(entries: &[SongEntry], mode: Mode, artist: &Artist, num: usize) {
+///   Will automatically change to total number of that aspect if `num` is higher than that
+pub fn top_from_artist(
+    settings: &Settings,
+    entries: &[SongEntry],
+    mode: Mode,
+    artist: &Artist,
+    num: usize,
+) {
     match mode {
         Mode::Songs => {
-            println!("=== TOP {num} SONGS FROM {artist} ===");
-            top_helper(gather::songs_from(entries, artist), num);
+            header(settings, format_args!("TOP {num} SONGS FROM {artist}"));
+            top_helper(settings, gather::songs_from(entries, artist), num);
         }
         Mode::Albums => {
-            println!("=== TOP {num} ALBUMS FROM {artist} ===");
-            top_helper(gather::albums_from_artist(entries, artist), num);
+            header(settings, format_args!("TOP {num} ALBUMS FROM {artist}"));
+            top_helper(settings, gather::albums_from_artist(entries, artist), num);
         }
     }
 }
 
+/// Prints top songs from an artist in a date range
+///
+/// Basically [`top_from_artist()`] with [`Mode::Songs`] but with date limitation
+///
+/// * `artist` - the [`Artist`] you want the top songs from
+/// * `num` - number of displayed top songs.
+///   Will automatically change to total number of songs if `num` is higher than that
+/// * `sum_songs_from_different_albums` - if set to true, it will sum up the plays of
+///   one song across multiple albums it may be in (see [`top()`])
+///
+/// # Panics
+///
+/// Panics if `start` is after or equal to `end`
+pub fn top_songs_from_artist_date(
+    settings: &Settings,
+    entries: &SongEntries,
+    artist: &Artist,
+    num: usize,
+    sum_songs_from_different_albums: bool,
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+) {
+    assert!(start <= end, "Start date is after end date!");
+    let entries_within_dates = entries.between(start, end);
+    let (start, end) = normalize_dates(entries_within_dates, start, end);
+
+    header(
+        settings,
+        format_args!(
+            "TOP {num} SONGS FROM {artist} | between {} and {}",
+            start.date_naive(),
+            end.date_naive()
+        ),
+    );
+    top_helper(
+        settings,
+        gather::songs_from_artist(
+            entries_within_dates,
+            artist,
+            sum_songs_from_different_albums,
+        ),
+        num,
+    );
+}
+
 /// Prints top songs from an album
 ///
 /// * `album` - the [`Album`] you want the top songs from
 /// * `num` - number of displayed top songs.
-/// Will automatically change to total number of songs from that album if `num` is higher than that
-pub fn top_from_album(entries: &[SongEntry], album: &Album, num: usize) {
-    println!("=== TOP {num} SONGS FROM {album} ===");
-    top_helper(gather::songs_from(entries, album), num);
+///   Will automatically change to total number of songs from that album if `num` is higher than that
+pub fn top_from_album(settings: &Settings, entries: &[SongEntry], album: &Album, num: usize) {
+    header(settings, format_args!("TOP {num} SONGS FROM {album}"));
+    top_helper(settings, gather::songs_from(entries, album), num);
 }
 
-/// Used by [`top()`]
-fn top_helper<Asp: Music>(music_dict: HashMap<Asp, usize>, num: usize) {
-    let music_vec: Vec<(Asp, usize)> = music_dict
+/// Used by the top functions like [`top_from_artist()`]
+fn top_helper<Asp: Music>(settings: &Settings, music_dict: HashMap<Asp, usize>, num: usize) {
+    print_top(settings, &top_items(music_dict, num, |_| None), false, None);
+}
+
+/// Returns the top `num` aspects of `music_dict` by their playcount
+///
+/// `time_of` - returns the listening time of an aspect if it's known
+fn top_items<Asp: Music>(
+    music_dict: HashMap<Asp, usize>,
+    num: usize,
+    time_of: impl Fn(&Asp) -> Option<TimeDelta>,
+) -> Vec<TopItem> {
+    music_dict
         .into_iter()
         // primary sorting: by plays descending
         // https://stackoverflow.com/a/34555984
@@ -172,76 +849,348 @@ fn top_helper<Asp: Music>(music_dict: HashMap<Asp, usize>, num: usize) {
         // so it's first compared by the plays in ascending order,
         // and if the plays are equal, it's compared by the name (alphabetical)
         .sorted_unstable_by_key(|(asp, plays)| (Reverse(*plays), asp.clone()))
-        // cheap cloning bc Rc::clone() internally
+        // if the number of unique aspects is lower than num, all are taken
+        .take(num)
+        .zip(1..)
+        .map(|((asp, plays), position)| TopItem {
+            position,
+            minutes: time_of(&asp).map(|time| time.num_minutes()),
+            name: asp.to_string(),
+            plays,
+        })
+        .collect()
+}
+
+/// Prints a top list in the current [`Format`]
+///
+/// * `by_time` - whether the list is ranked by listening time, in which case
+///   the minutes are shown before the plays. Otherwise they're only shown after
+///   the plays if set by [`Settings::show_time`] and known, see [`TopItem::minutes`]
+/// * `all_plays` - playcount of the whole dataset for the percentages
+///   shown if set by [`Settings::share`]
+fn print_top(settings: &Settings, items: &[TopItem], by_time: bool, all_plays: Option<usize>) {
+    let with_time =
+        !by_time && settings.show_time && items.iter().all(|item| item.minutes.is_some());
+    let all_plays = all_plays.filter(|_| settings.share.is_some());
+
+    match settings.format() {
+        Format::Json => return print_json(&items),
+        Format::Markdown => {
+            let mut columns = vec![">#", "Name"];
+            if by_time {
+                columns.push(">Minutes");
+            }
+            columns.push(">Plays");
+            if all_plays.is_some() {
+                columns.push(">%");
+            }
+            if with_time {
+                columns.push(">Time");
+            }
+            if settings.bars {
+                columns.push("");
+            }
+            markdown_header(&columns);
+        }
+        Format::Pretty | Format::Plain => (),
+    }
+
+    // what the bars are proportional to
+    let value = |item: &TopItem| {
+        if by_time {
+            usize::try_from(item.minutes.unwrap_or_default()).unwrap_or_default()
+        } else {
+            item.plays
+        }
+    };
+    let max = items.iter().map(value).max().unwrap_or_default();
+
+    let names = items
+        .iter()
+        .map(|item| settings.truncate(&item.name))
         .collect_vec();
-    let length = music_vec.len();
+    let name_width = names.iter().map(|name| name.width()).max().unwrap_or(0);
+
+    let max_num = items.len();
+    for (item, pretty_name) in items.iter().zip(&names) {
+        let TopItem {
+            position,
+            name,
+            plays,
+            minutes,
+        } = item;
+        let minutes = minutes.unwrap_or_default();
+        let percent = all_plays.map(|all_plays| percent(*plays, all_plays));
+        let time = TimeDelta::minutes(minutes).display_hours();
+        let bar = bar(value(item), max, TOP_BAR_LENGTH);
 
-    // if the number of unique aspects is lower than the parsed num
-    let max_num: usize = if length < num { length } else { num };
+        if settings.format() == Format::Pretty {
+            let indent = spaces((max_num.ilog10() - position.ilog10()) as usize);
+            let ranked_time = if by_time {
+                format!(" {minutes} minutes |")
+            } else {
+                String::new()
+            };
+            let share = percent.map_or_else(String::new, |percent| {
+                format!(" ({percent:.1}% of all plays)")
+            });
+            let time = if with_time {
+                format!(" | {time}")
+            } else {
+                String::new()
+            };
+            // padded so that the names stay aligned
+            let bar = if settings.bars {
+                format!("{bar:<TOP_BAR_LENGTH$} | ")
+            } else {
+                String::new()
+            };
+            let name = pad(pretty_name, name_width);
+            println!("{indent}#{position}: {bar}{name} |{ranked_time} {plays} plays{share}{time}");
+            continue;
+        }
+
+        let mut cells = vec![position.to_string(), name.clone()];
+        if by_time {
+            cells.push(minutes.to_string());
+        }
+        cells.push(plays.to_string());
+        if let Some(percent) = percent {
+            cells.push(format!("{percent:.1}"));
+        }
+        if with_time && settings.format() == Format::Markdown {
+            cells.push(time);
+        } else if with_time {
+            cells.push(minutes.to_string());
+        }
+        if settings.bars && settings.format() == Format::Markdown {
+            cells.push(bar);
+        }
 
-    for (i, (asp, plays)) in music_vec.iter().enumerate().take(max_num) {
-        let position = i + 1;
-        let indent = spaces((max_num.ilog10() - position.ilog10()) as usize);
-        println!("{indent}#{position}: {asp} | {plays} plays");
+        if settings.format() == Format::Markdown {
+            markdown_row(&cells.iter().map(|cell| cell as &dyn Display).collect_vec());
+        } else {
+            println!("{}", cells.join("\t"));
+        }
     }
 }
 
 /// Prints a specfic aspect
 ///
 /// * `asp` - the [`AspectFull`] you want information about containing the
-/// relevant struct ([`Artist`], [`Album`] or [`Song`])
-pub fn aspect(entries: &[SongEntry], asp: &AspectFull) {
-    match *asp {
-        AspectFull::Artist(art) => {
-            println!("{} | {} plays", art, gather::plays(entries, art));
-            artist(
-                entries,
-                &gather::albums_from_artist(entries, art),
-                INDENT_LENGTH,
-            );
-        }
-        AspectFull::Album(alb) => {
-            println!("{} | {} plays", alb, gather::plays(entries, alb));
-            album(&gather::songs_from(entries, alb), INDENT_LENGTH);
-        }
-        AspectFull::Song(son) => {
-            println!("{} | {} plays", son, gather::plays(entries, son));
+///   relevant struct ([`Artist`], [`Album`] or [`Song`])
+///
+/// The listening time is shown next to the playcounts if set by [`Settings::show_time`]
+/// and the percentage of the plays if set by [`Settings::share`]
+pub fn aspect(settings: &Settings, entries: &[SongEntry], asp: &AspectFull) {
+    let data = aspect_data(entries, asp);
+    if settings.format() == Format::Json {
+        return print_json(&data);
+    }
+
+    summary(
+        settings,
+        format_args!(
+            "{} | {}",
+            data.name,
+            data.plays_and_time(settings, total_of(settings, entries, asp))
+        ),
+    );
+    print_sparkline(settings, entries, asp);
+    print_children(settings, &data, gather::all_plays(entries), INDENT_LENGTH);
+}
+
+/// A playcount the share of an aspect's plays is computed against,
+/// see [`Share`]
+#[derive(Clone, Copy)]
+struct Total {
+    /// the playcount
+    plays: usize,
+    /// what the playcount is of, e.g. `all plays` or `the artist`
+    of: &'static str,
+}
+
+/// Returns the playcount the share of the plays of the aspect itself
+/// in [`aspect()`] is computed against if set by [`Settings::share`]
+fn total_of(settings: &Settings, entries: &[SongEntry], asp: &AspectFull) -> Option<Total> {
+    let total = match (settings.share?, asp) {
+        (Share::All, _) | (Share::Parent, AspectFull::Artist(_)) => Total {
+            plays: gather::all_plays(entries),
+            of: "all plays",
+        },
+        (Share::Parent, AspectFull::Album(alb)) => Total {
+            plays: gather::plays(entries, &alb.artist),
+            of: "the artist",
+        },
+        (Share::Parent, AspectFull::Song(son)) => Total {
+            plays: gather::plays(entries, &son.album),
+            of: "the album",
+        },
+    };
+    Some(total)
+}
+
+/// An aspect with its playcount and those of its albums or songs,
+/// see [`aspect_data()`]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AspectData {
+    /// Name of the aspect - the full one (e.g. `Sabaton - The Last Stand`)
+    /// for the aspect itself, only its own for its albums and songs
+    pub name: String,
+    /// Playcount of the aspect
+    pub plays: usize,
+    /// Minutes listened to the aspect
+    pub minutes: i64,
+    /// Albums of an artist or songs of an album,
+    /// sorted by their playcount descending and name ascending
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<AspectData>,
+}
+impl AspectData {
+    /// Returns e.g. `423 plays`, `423 plays | 31h 12m`
+    /// if the time is shown (see [`Settings::show_time`])
+    /// or `423 plays (3.1% of all plays)` with a `total`
+    fn plays_and_time(&self, settings: &Settings, total: Option<Total>) -> String {
+        let share = total.map_or_else(String::new, |Total { plays, of }| {
+            format!(" ({:.1}% of {of})", percent(self.plays, plays))
+        });
+        if settings.show_time {
+            let time = TimeDelta::minutes(self.minutes).display_hours();
+            format!("{} plays{share} | {time}", self.plays)
+        } else {
+            format!("{} plays{share}", self.plays)
         }
     }
 }
 
-/// Prints each [`Album`] of `albums` with the playcount
+/// Returns an aspect with its albums and songs as printed by [`aspect()`]
 ///
-/// Preferably `albums` contains only albums from one artist
-fn artist(entries: &[SongEntry], albums: &HashMap<Album, usize>, indent_length: usize) {
-    let indent = spaces(indent_length);
-    // albums sorted by their playcount descending (primary)
-    // and name ascending (secondary) if plays are equal
-    let albums_vec: Vec<(&Album, &usize)> = albums
-        .iter()
-        .sorted_unstable_by_key(|t| (Reverse(t.1), t.0))
-        .collect_vec();
-
-    for (alb, plays) in albums_vec {
-        println!("{indent}{} | {plays} plays", alb.name);
-        album(&gather::songs_from(entries, alb), 2 * indent_length);
+/// * `asp` - the [`AspectFull`] you want information about containing the
+///   relevant struct ([`Artist`], [`Album`] or [`Song`])
+#[must_use]
+pub fn aspect_data(entries: &[SongEntry], asp: &AspectFull) -> AspectData {
+    match *asp {
+        AspectFull::Artist(art) => {
+            let album_times = gather::albums_listening_time(entries);
+            let song_times = gather::songs_listening_time(entries, false);
+            AspectData {
+                name: art.to_string(),
+                plays: gather::plays(entries, art),
+                minutes: gather::listening_time_of(entries, art).num_minutes(),
+                children: gather::albums_from_artist(entries, art)
+                    .into_iter()
+                    .sorted_unstable_by_key(|(alb, plays)| (Reverse(*plays), alb.clone()))
+                    .map(|(alb, plays)| AspectData {
+                        name: alb.name.to_string(),
+                        plays,
+                        minutes: album_times.get(&alb).map_or(0, TimeDelta::num_minutes),
+                        children: songs_data(gather::songs_from(entries, &alb), &song_times),
+                    })
+                    .collect(),
+            }
+        }
+        AspectFull::Album(alb) => AspectData {
+            name: alb.to_string(),
+            plays: gather::plays(entries, alb),
+            minutes: gather::listening_time_of(entries, alb).num_minutes(),
+            children: songs_data(
+                gather::songs_from(entries, alb),
+                &gather::songs_listening_time(entries, false),
+            ),
+        },
+        AspectFull::Song(son) => AspectData {
+            name: son.to_string(),
+            plays: gather::plays(entries, son),
+            minutes: gather::listening_time_of(entries, son).num_minutes(),
+            children: vec![],
+        },
     }
 }
 
-/// Prints each [`Song`] of `songs` with the playcount
+/// Returns each [`Song`] of `songs` with the playcount and the time from `times`,
+/// sorted by playcount descending and name ascending
+fn songs_data(songs: HashMap<Song, usize>, times: &HashMap<Song, TimeDelta>) -> Vec<AspectData> {
+    songs
+        .into_iter()
+        .sorted_unstable_by_key(|(song, plays)| (Reverse(*plays), song.clone()))
+        .map(|(song, plays)| AspectData {
+            minutes: times.get(&song).map_or(0, TimeDelta::num_minutes),
+            name: song.name.to_string(),
+            plays,
+            children: vec![],
+        })
+        .collect()
+}
+
+/// Prints the albums or songs of an [`AspectData`] with their playcount
+///
+/// Albums are followed by their songs with a deeper indent.
+/// With [`Format::Markdown`], each album is a heading followed by a table of its songs
 ///
-/// Preferably `songs` contains only songs from one album
-fn album(songs: &HashMap<Song, usize>, indent_length: usize) {
+/// `all_plays` - playcount of the whole dataset for the percentages
+/// shown if set by [`Settings::share`]
+fn print_children(
+    settings: &Settings,
+    parent: &AspectData,
+    all_plays: usize,
+    indent_length: usize,
+) {
+    let children = &parent.children;
+    if children.is_empty() {
+        return;
+    }
     let indent = spaces(indent_length);
-    // songs sorted by their playcount descending (primary)
-    // and name ascending (secondary) if plays are equal
-    let songs_vec: Vec<(&Song, &usize)> = songs
-        .iter()
-        .sorted_unstable_by_key(|t| (Reverse(t.1), t.0))
-        .collect_vec();
+    // songs don't have children themselves
+    let songs = children.iter().all(|child| child.children.is_empty());
+    let total = settings.share.map(|share| match share {
+        Share::All => Total {
+            plays: all_plays,
+            of: "all plays",
+        },
+        Share::Parent => Total {
+            plays: parent.plays,
+            of: if songs { "the album" } else { "the artist" },
+        },
+    });
 
-    for (song, plays) in songs_vec {
-        println!("{indent}{} | {plays} plays", song.name);
+    if songs && settings.format() == Format::Markdown {
+        let mut columns = vec!["Song", ">Plays"];
+        if total.is_some() {
+            columns.push(">%");
+        }
+        if settings.show_time {
+            columns.push(">Time");
+        }
+        markdown_header(&columns);
+        for song in children {
+            let mut cells = vec![song.name.clone(), song.plays.to_string()];
+            if let Some(Total { plays, .. }) = total {
+                cells.push(format!("{:.1}", percent(song.plays, plays)));
+            }
+            if settings.show_time {
+                cells.push(TimeDelta::minutes(song.minutes).display_hours());
+            }
+            markdown_row(&cells.iter().map(|cell| cell as &dyn Display).collect_vec());
+        }
+        println!();
+        return;
+    }
+
+    for child in children {
+        if settings.format() == Format::Markdown {
+            println!(
+                "#### {} | {}\n",
+                child.name,
+                child.plays_and_time(settings, total)
+            );
+        } else {
+            println!(
+                "{indent}{} | {}",
+                child.name,
+                child.plays_and_time(settings, total)
+            );
+        }
+        print_children(settings, child, all_plays, indent_length + INDENT_LENGTH);
     }
 }
 
@@ -250,12 +1199,13 @@ fn album(songs: &HashMap<Song, usize>, indent_length: usize) {
 /// Basically [`aspect()`] but with date limitations
 ///
 /// * `asp` - the [`AspectFull`] you want information about containing the
-/// relevant struct ([`Artist`], [`Album`] or [`Song`])
+///   relevant struct ([`Artist`], [`Album`] or [`Song`])
 ///
 /// # Panics
 ///
 /// Panics if `start` is after or equal to `end`
 pub fn aspect_date(
+    settings: &Settings,
     entries: &SongEntries,
     asp: &AspectFull,
     start: &DateTime<Local>,
@@ -266,44 +1216,28 @@ pub fn aspect_date(
 
     let (start, end) = normalize_dates(entries_within_dates, start, end);
 
-    match *asp {
-        AspectFull::Artist(art) => {
-            println!(
-                "{} | between {} and {} | {} plays",
-                art,
-                start.date_naive(),
-                end.date_naive(),
-                gather::plays(entries_within_dates, art)
-            );
-            artist(
-                entries_within_dates,
-                &gather::albums_from_artist(entries_within_dates, art),
-                INDENT_LENGTH,
-            );
-        }
-        AspectFull::Album(alb) => {
-            println!(
-                "{} | between {} and {} | {} plays",
-                alb,
-                start.date_naive(),
-                end.date_naive(),
-                gather::plays(entries_within_dates, alb)
-            );
-            album(
-                &gather::songs_from(entries_within_dates, alb),
-                INDENT_LENGTH,
-            );
-        }
-        AspectFull::Song(son) => {
-            println!(
-                "{} | between {} and {} | {} plays",
-                son,
-                start.date_naive(),
-                end.date_naive(),
-                gather::plays(entries_within_dates, son)
-            );
-        }
+    let data = aspect_data(entries_within_dates, asp);
+    if settings.format() == Format::Json {
+        return print_json(&data);
     }
+
+    summary(
+        settings,
+        format_args!(
+            "{} | between {} and {} | {}",
+            data.name,
+            start.date_naive(),
+            end.date_naive(),
+            data.plays_and_time(settings, total_of(settings, entries_within_dates, asp))
+        ),
+    );
+    print_sparkline(settings, entries_within_dates, asp);
+    print_children(
+        settings,
+        &data,
+        gather::all_plays(entries_within_dates),
+        INDENT_LENGTH,
+    );
 }
 
 /// Prints the total time played
@@ -344,6 +1278,454 @@ pub fn time_played_date(entries: &SongEntries, start: &DateTime<Local>, end: &Da
     );
 }
 
+/// Prints an overview of the whole dataset
+///
+/// Includes the number of entries, unique artists/albums/songs,
+/// the date span, total and average listening time,
+/// the day with the most plays and the approximate memory usage
+#[allow(clippy::cast_precision_loss)]
+pub fn stats(settings: &Settings, entries: &SongEntries) {
+    let first = entries.first_date();
+    let last = entries.last_date();
+    // +1 so that a dataset spanning a single day doesn't divide by zero
+    let days = (last.date_naive() - first.date_naive()).num_days() + 1;
+    let duration = gather::listening_time(entries);
+
+    header(settings, format_args!("DATASET STATS"));
+    println!("Entries: {}", entries.len());
+    println!("Unique artists: {}", gather::artists(entries).len());
+    println!("Unique albums: {}", gather::albums(entries).len());
+    println!("Unique songs: {}", gather::songs(entries, false).len());
+    println!(
+        "Date span: {} to {} ({days} days)",
+        first.date_naive(),
+        last.date_naive()
+    );
+    println!(
+        "Total listening time: {} days / {} hours / {} minutes",
+        duration.num_days(),
+        duration.num_hours(),
+        duration.num_minutes()
+    );
+    println!(
+        "Average per day: {:.2} plays / {:.2} minutes",
+        entries.len() as f64 / days as f64,
+        duration.num_minutes() as f64 / days as f64
+    );
+
+    let mut per_day: HashMap<chrono::NaiveDate, (usize, TimeDelta)> = HashMap::new();
+    for entry in entries.iter() {
+        let day = per_day
+            .entry(entry.timestamp.date_naive())
+            .or_insert((0, TimeDelta::zero()));
+        day.0 += 1;
+        day.1 += entry.time_played;
+    }
+    // ties are resolved in favour of the earlier day
+    if let Some((day, (plays, time))) = per_day
+        .into_iter()
+        .max_by(|(d1, (p1, _)), (d2, (p2, _))| p1.cmp(p2).then_with(|| d2.cmp(d1)))
+    {
+        println!(
+            "Biggest day: {day} with {plays} plays / {} minutes",
+            time.num_minutes()
+        );
+    }
+
+    let memory = entries.memory_stats();
+    let mib = |bytes: usize| bytes as f64 / 1024.0 / 1024.0;
+    println!(
+        "Memory usage: ~{:.2} MiB (entries: {:.2} MiB, names: {:.2} MiB, durations: {:.2} MiB, search indices: {:.2} MiB)",
+        mib(memory.total()),
+        mib(memory.entries),
+        mib(memory.names),
+        mib(memory.durations),
+        mib(memory.indices)
+    );
+}
+
+/// Prints a side-by-side comparison table of the given artists
+///
+/// Compares their plays, listening time, first listen,
+/// most played song and the month with the most plays
+#[allow(clippy::too_many_lines)]
+pub fn compare_artists(settings: &Settings, entries: &[SongEntry], artists: &[Artist]) {
+    let column = |f: &dyn Fn(&Artist) -> String| artists.iter().map(f).collect_vec();
+
+    let header = column(&|art| art.name.to_string());
+    let rows = [
+        (
+            "plays",
+            column(&|art| gather::plays(entries, art).to_string()),
+        ),
+        (
+            "minutes",
+            column(&|art| {
+                gather::listening_time_of(entries, art)
+                    .num_minutes()
+                    .to_string()
+            }),
+        ),
+        (
+            "first listen",
+            column(&|art| {
+                gather::first_play(entries, art)
+                    .map_or_else(|| String::from("-"), |date| date.date_naive().to_string())
+            }),
+        ),
+        (
+            "top song",
+            column(&|art| {
+                gather::songs_from(entries, art)
+                    .into_iter()
+                    // most plays, alphabetically first song if plays are equal
+                    .max_by_key(|(song, plays)| (*plays, Reverse(song.clone())))
+                    .map_or_else(
+                        || String::from("-"),
+                        |(song, plays)| format!("{} ({plays} plays)", song.name),
+                    )
+            }),
+        ),
+        (
+            "peak month",
+            column(&|art| {
+                entries
+                    .iter()
+                    .filter(|entry| art.is_entry(entry))
+                    .map(|entry| (entry.timestamp.year(), entry.timestamp.month()))
+                    .counts()
+                    .into_iter()
+                    // most plays, earliest month if plays are equal
+                    .max_by_key(|(month, plays)| (*plays, Reverse(*month)))
+                    .map_or_else(
+                        || String::from("-"),
+                        |((year, month), plays)| format!("{year}-{month:02} ({plays} plays)"),
+                    )
+            }),
+        ),
+    ];
+
+    if settings.format() == Format::Markdown {
+        let columns = std::iter::once("")
+            .chain(header.iter().map(String::as_str))
+            .collect_vec();
+        markdown_header(&columns);
+        for (label, cells) in &rows {
+            let cells = std::iter::once(label as &dyn Display)
+                .chain(cells.iter().map(|cell| cell as &dyn Display))
+                .collect_vec();
+            markdown_row(&cells);
+        }
+        return;
+    }
+
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let header = header
+        .iter()
+        .map(|name| settings.truncate(name))
+        .collect_vec();
+    let rows = rows.map(|(label, cells)| {
+        (
+            label,
+            cells
+                .iter()
+                .map(|cell| settings.truncate(cell))
+                .collect_vec(),
+        )
+    });
+    // each column is as wide as its widest cell
+    let widths = (0..artists.len())
+        .map(|i| {
+            rows.iter()
+                .map(|(_, cells)| cells[i].width())
+                .chain(std::iter::once(header[i].width()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect_vec();
+
+    let print_row = |label: &str, cells: &[String]| {
+        let cells = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| pad(cell, width))
+            .join(" | ");
+        // trimmed because the last column is padded with spaces
+        println!("{}", format!("{label:<label_width$} | {cells}").trim_end());
+    };
+
+    print_row("", &header);
+    println!(
+        "{}",
+        "-".repeat(label_width + widths.iter().map(|width| width + 3).sum::<usize>())
+    );
+    for (label, cells) in &rows {
+        print_row(label, cells);
+    }
+}
+
+/// Prints the artists where a single song accounts for more than
+/// `percent_threshold`% of their plays together with that song
+///
+/// See [`gather::one_hit_wonders()`] for the arguments
+#[allow(clippy::cast_precision_loss)]
+pub fn one_hit_wonders(
+    settings: &Settings,
+    entries: &[SongEntry],
+    percent_threshold: usize,
+    min_plays: usize,
+) {
+    header(
+        settings,
+        format_args!("ONE-HIT WONDERS | one song with more than {percent_threshold}% of the plays"),
+    );
+    if settings.format() == Format::Markdown {
+        markdown_header(&["Artist", "Song", ">Plays", ">Artist plays", ">%"]);
+    }
+    for (song, song_plays, artist_plays) in
+        gather::one_hit_wonders(entries, percent_threshold as f64, min_plays)
+    {
+        let artist = &song.album.artist;
+        let percent = percent(song_plays, artist_plays);
+        match settings.format() {
+            Format::Pretty => println!(
+                "{artist} | {} | {song_plays} of {artist_plays} plays ({percent:.1}%)",
+                song.name
+            ),
+            Format::Plain | Format::Json => println!(
+                "{artist}\t{}\t{song_plays}\t{artist_plays}\t{percent:.1}",
+                song.name
+            ),
+            Format::Markdown => markdown_row(&[
+                artist,
+                &song.name,
+                &song_plays,
+                &artist_plays,
+                &format_args!("{percent:.1}"),
+            ]),
+        }
+    }
+}
+
+/// Prints the date and the entry of the `n`th play of an aspect
+/// and how many plays are left until its next milestones
+pub fn milestone<Asp: Music>(entries: &[SongEntry], aspect: &Asp, n: usize) {
+    let plays = gather::plays(entries, aspect);
+
+    match gather::nth_play(entries, aspect, n) {
+        Some(entry) => println!(
+            "Play #{n} of {aspect} was {} on {}",
+            Song::from(entry),
+            entry.timestamp.format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("{aspect} hasn't reached {n} plays yet ({plays} plays so far)"),
+    }
+
+    for next in MILESTONES.iter().filter(|&&m| m > plays).take(2) {
+        println!("{} plays until {next}", next - plays);
+    }
+}
+
+/// Prints the amount of plays for each hour of the day as a histogram
+///
+/// * `asp` - with [`None`] all plays are counted, otherwise only the plays
+///   of the given [`AspectFull`] ([`Artist`], [`Album`] or [`Song`])
+pub fn clock(settings: &Settings, entries: &[SongEntry], asp: Option<&AspectFull>) {
+    let hours = match asp {
+        None => {
+            header(settings, format_args!("PLAYS PER HOUR OF THE DAY"));
+            gather::plays_per_hour(entries)
+        }
+        Some(AspectFull::Artist(art)) => {
+            header(settings, format_args!("PLAYS PER HOUR OF THE DAY OF {art}"));
+            gather::plays_per_hour_of(entries, *art)
+        }
+        Some(AspectFull::Album(alb)) => {
+            header(settings, format_args!("PLAYS PER HOUR OF THE DAY OF {alb}"));
+            gather::plays_per_hour_of(entries, *alb)
+        }
+        Some(AspectFull::Song(son)) => {
+            header(settings, format_args!("PLAYS PER HOUR OF THE DAY OF {son}"));
+            gather::plays_per_hour_of(entries, *son)
+        }
+    };
+
+    let max = hours.iter().max().copied().unwrap_or(0);
+    let max_digits = max.max(1).ilog10() as usize;
+
+    if settings.format() == Format::Markdown {
+        markdown_header(&["Hour", ">Plays", ""]);
+        for (hour, plays) in hours.iter().enumerate() {
+            let bar = bar(*plays, max, BAR_LENGTH);
+            markdown_row(&[&format_args!("{hour:02}:00"), plays, &bar]);
+        }
+        return;
+    }
+    for (hour, plays) in hours.iter().enumerate() {
+        let indent = spaces(max_digits - plays.max(&1).ilog10() as usize);
+        println!(
+            "{hour:02}:00 | {indent}{plays} | {}",
+            bar(*plays, max, BAR_LENGTH)
+        );
+    }
+}
+
+/// Prints how many plays and how much listening time
+/// came from each [`Platform`] (device class)
+pub fn platforms(settings: &Settings, entries: &[SongEntry]) {
+    header(settings, format_args!("PLATFORMS"));
+    platforms_helper(settings, entries);
+}
+
+/// Prints the platform breakdown in a date range
+///
+/// Basically [`platforms()`] but with date limitation
+///
+/// # Panics
+///
+/// Panics if `start` is after or equal to `end`
+pub fn platforms_date(
+    settings: &Settings,
+    entries: &SongEntries,
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+) {
+    assert!(start <= end, "Start date is after end date!");
+    let entries_within_dates = entries.between(start, end);
+    let (start, end) = normalize_dates(entries_within_dates, start, end);
+
+    header(
+        settings,
+        format_args!(
+            "PLATFORMS | between {} and {}",
+            start.date_naive(),
+            end.date_naive()
+        ),
+    );
+    platforms_helper(settings, entries_within_dates);
+}
+
+/// Used by [`platforms()`] and [`platforms_date()`]
+#[allow(clippy::cast_precision_loss)]
+fn platforms_helper(settings: &Settings, entries: &[SongEntry]) {
+    let plays = gather::platforms(entries);
+    let times = gather::platforms_listening_time(entries);
+    let all_plays = gather::all_plays(entries) as f64;
+
+    if settings.format() == Format::Markdown {
+        markdown_header(&["Platform", ">Plays", ">%", ">Minutes"]);
+    }
+    // sorted by plays descending (primary) and name ascending (secondary)
+    for (platform, plays) in plays.iter().sorted_unstable_by_key(|t| (Reverse(t.1), t.0)) {
+        let minutes = times.get(platform).map_or(0, TimeDelta::num_minutes);
+        let percent = (*plays as f64 / all_plays) * 100.0;
+        match settings.format() {
+            Format::Pretty => {
+                println!("{platform} | {plays} plays ({percent:.2}%) | {minutes} minutes");
+            }
+            Format::Plain | Format::Json => {
+                println!("{platform}\t{plays}\t{percent:.2}\t{minutes}");
+            }
+            Format::Markdown => {
+                markdown_row(&[platform, plays, &format_args!("{percent:.2}"), &minutes]);
+            }
+        }
+    }
+}
+
+/// Creates a horizontal bar out of unicode block characters
+///
+/// The bar has a length of `width` characters if `value` equals `max`
+/// and is proportionally shorter for smaller values
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar(value: usize, max: usize, width: usize) -> String {
+    /// Partial blocks from 1/8 to 7/8 of a full block
+    const PARTIAL: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    if max == 0 {
+        return String::new();
+    }
+
+    // length of the bar in eighths of a character
+    let eighths = ((value as f64 / max as f64) * (width * 8) as f64).round() as usize;
+
+    let mut bar = "█".repeat(eighths / 8);
+    let rest = eighths % 8;
+    if rest > 0 {
+        bar.push(PARTIAL[rest - 1]);
+    }
+    bar
+}
+
+/// Prints a sparkline of the monthly plays of `asp` from its first play
+/// up to the month of the last entry if set by [`Settings::sparkline`]
+///
+/// # Panics
+///
+/// Unwrap used on the conversion of the months to [`usize`],
+/// but won't panic since entries are in chronological order
+fn print_sparkline(settings: &Settings, entries: &[SongEntry], asp: &AspectFull) {
+    if !settings.sparkline || !matches!(settings.format(), Format::Pretty | Format::Markdown) {
+        return;
+    }
+    let years = match asp {
+        AspectFull::Artist(art) => gather::monthly_plays_by_year(entries, *art),
+        AspectFull::Album(alb) => gather::monthly_plays_by_year(entries, *alb),
+        AspectFull::Song(son) => gather::monthly_plays_by_year(entries, *son),
+    };
+    let (Some((first_year, _)), Some(last)) = (years.first(), entries.last()) else {
+        return;
+    };
+
+    // starting in January of the first year with plays
+    let mut plays = years
+        .iter()
+        .flat_map(|(_, months)| months)
+        .copied()
+        .collect_vec();
+    // the aspect has plays, so there's a first month with plays
+    let start = plays
+        .iter()
+        .position(|&plays| plays > 0)
+        .unwrap_or_default();
+    // padded with months without plays up to the last entry
+    let end = usize::try_from(last.timestamp.year() - first_year).unwrap() * 12
+        + last.timestamp.month0() as usize;
+    plays.resize(end + 1, 0);
+
+    let from = format!(
+        "{}-{:02}",
+        first_year + i32::try_from(start / 12).unwrap(),
+        start % 12 + 1
+    );
+    let to = last.timestamp.format("%Y-%m");
+    let line = format!("{} ({from} to {to})", spark(&plays[start..]));
+    match settings.format() {
+        Format::Markdown => println!("{line}\n"),
+        _ => println!("{}{line}", spaces(INDENT_LENGTH)),
+    }
+}
+
+/// Creates a sparkline out of unicode block characters,
+/// one character per value and empty for zeros
+fn spark(values: &[usize]) -> String {
+    /// Blocks from 1/8 to a full block
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max = values.iter().max().copied().unwrap_or_default();
+    values
+        .iter()
+        .map(|&value| {
+            if value == 0 {
+                ' '
+            } else {
+                LEVELS[(value * LEVELS.len()).div_ceil(max) - 1]
+            }
+        })
+        .collect()
+}
+
 /// Used by `*_date` functions to set the start date to
 /// the first entry's date and the end date to the last entry's date
 /// if the inputted dates are before/after those dates
@@ -0,0 +1,1462 @@
+//! Module responsible for summarizing the dataset or parts of it
+//! in a narrative, multi-section format
+//! to the [`std::io::stdout`] or to markdown files, see [`write()`]
+//!
+//! Unlike [`print`][crate::print], which prints one specific thing,
+//! these functions combine many statistics into a report
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Datelike, Local, Month, NaiveDate, TimeDelta, Timelike};
+use endsong::prelude::*;
+use itertools::Itertools;
+use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::plot::normalize_path;
+use crate::print::pad;
+
+/// Number of entries in the top lists of a summary
+const TOP_NUM: usize = 5;
+
+/// Width of a summary card in pixels, see [`card()`]
+const CARD_WIDTH: usize = 1080;
+
+/// Height of a summary card in pixels, see [`card()`]
+const CARD_HEIGHT: usize = 1350;
+
+/// Maximum length of a name on a summary card before it's cut off
+const CARD_NAME_LENGTH: usize = 36;
+
+/// Settings of the summaries,
+/// set by the `[summarize]` section of the [config][crate::ui::config]
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Directory the summaries are written to as markdown files by [`write()`] -
+    /// with [`None`] they're printed to the [`std::io::stdout`]
+    pub reports_dir: Option<PathBuf>,
+}
+
+/// Renders a summary card (see [`card()`]) and writes it as a PNG file named after `name`
+/// to the [`reports_dir`][Settings::reports_dir] or to the `plots/` folder
+///
+/// Returns the path of the written file
+///
+/// # Errors
+///
+/// Returns an error if the card couldn't be rendered or written
+pub fn write_card(settings: &Settings, name: &str, svg: &str) -> io::Result<PathBuf> {
+    let png = render_png(svg)?;
+
+    let dir = settings
+        .reports_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("plots"));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.png", normalize_path(name)));
+    std::fs::write(&path, png)?;
+    Ok(path)
+}
+
+/// Renders an SVG (e.g. from [`card()`]) to the contents of a PNG file
+///
+/// Uses the fonts installed on the system for the text
+///
+/// # Errors
+///
+/// Returns an error if the SVG is invalid or the PNG couldn't be encoded
+fn render_png(svg: &str) -> io::Result<Vec<u8>> {
+    use resvg::usvg::fontdb::{Family, Query};
+
+    let mut options = resvg::usvg::Options::default();
+    let fonts = options.fontdb_mut();
+    fonts.load_system_fonts();
+    // sans-serif is Arial by default, which isn't installed everywhere
+    // (e.g. on most Linux distributions) - otherwise the text would be missing
+    let sans_serif = Query {
+        families: &[Family::SansSerif],
+        ..Query::default()
+    };
+    if fonts.query(&sans_serif).is_none() {
+        // e.g. "DejaVu Sans" or "Noto Sans", otherwise any installed font
+        let families = fonts
+            .faces()
+            .filter_map(|face| face.families.first())
+            .map(|(family, _)| family)
+            .collect_vec();
+        let fallback = families
+            .iter()
+            .find(|family| family.contains("Sans") && !family.contains("Mono"))
+            .or(families.first())
+            .map(|family| (*family).clone());
+        if let Some(family) = fallback {
+            fonts.set_sans_serif_family(family);
+        }
+    }
+
+    let tree = resvg::usvg::Tree::from_str(svg, &options).map_err(io::Error::other)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| io::Error::other("the card has no size"))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+
+    pixmap.encode_png().map_err(io::Error::other)
+}
+
+/// Writes a summary with `summarize` (e.g. [`year()`])
+/// either to the [`std::io::stdout`] or, if the [`reports_dir`][Settings::reports_dir]
+/// is set, to a markdown file in that directory named after `name`
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+pub fn write(
+    settings: &Settings,
+    name: &str,
+    summarize: impl FnOnce(&mut Report<Box<dyn Write>>) -> io::Result<()>,
+) -> io::Result<()> {
+    let Some(reports_dir) = &settings.reports_dir else {
+        return summarize(&mut Report::new(Box::new(io::stdout().lock()), false));
+    };
+
+    std::fs::create_dir_all(reports_dir)?;
+    let path = reports_dir.join(format!("{}.md", normalize_path(name)));
+    let mut report = Report::new(
+        Box::new(BufWriter::new(File::create(&path)?)) as Box<dyn Write>,
+        true,
+    );
+    summarize(&mut report)?;
+    report.out.flush()?;
+    eprintln!("Wrote the summary to {}", path.display());
+    Ok(())
+}
+
+/// Where a summary is written to and how
+///
+/// Either plain text with `=== TITLE ===` and `--- Section ---` headers
+/// like the rest of the shell or markdown, e.g. for a report file
+pub struct Report<W: Write> {
+    /// the destination, e.g. [`std::io::Stdout`] or a [`std::fs::File`]
+    out: W,
+    /// whether the summary is written as markdown
+    markdown: bool,
+}
+impl<W: Write> Report<W> {
+    /// Creates a new [`Report`] writing to `out`
+    pub fn new(out: W, markdown: bool) -> Self {
+        Self { out, markdown }
+    }
+
+    /// Writes the title of a summary
+    fn title(&mut self, text: std::fmt::Arguments) -> io::Result<()> {
+        if self.markdown {
+            writeln!(self.out, "# {text}\n")
+        } else {
+            writeln!(self.out, "=== {text} ===")
+        }
+    }
+
+    /// Writes the header of a section of a summary
+    fn section(&mut self, text: std::fmt::Arguments) -> io::Result<()> {
+        if self.markdown {
+            writeln!(self.out, "\n## {text}\n")
+        } else {
+            writeln!(self.out, "--- {text} ---")
+        }
+    }
+
+    /// Writes a line of a summary - a list item in markdown
+    fn line(&mut self, text: std::fmt::Arguments) -> io::Result<()> {
+        if self.markdown {
+            writeln!(self.out, "- {text}")
+        } else {
+            writeln!(self.out, "{text}")
+        }
+    }
+
+    /// Writes a table with the given `header` and `rows`
+    /// - with aligned columns outside of markdown
+    fn table<const N: usize>(&mut self, header: [&str; N], rows: &[[String; N]]) -> io::Result<()> {
+        if self.markdown {
+            // a table right after a list item would become part of it
+            writeln!(self.out, "\n| {} |", header.join(" | "))?;
+            writeln!(self.out, "|{}", " --- |".repeat(N))?;
+            for row in rows {
+                // pipes in names would end the cell early
+                let cells = row.iter().map(|cell| cell.replace('|', "\\|"));
+                writeln!(self.out, "| {} |", cells.format(" | "))?;
+            }
+            return writeln!(self.out);
+        }
+
+        let widths: [usize; N] = std::array::from_fn(|column| {
+            rows.iter()
+                .map(|row| row[column].width())
+                .chain([header[column].width()])
+                .max()
+                .unwrap_or_default()
+        });
+        let line = |cells: [&str; N]| {
+            cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| pad(cell, width))
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+        writeln!(self.out, "{}", line(header))?;
+        for row in rows {
+            writeln!(self.out, "{}", line(row.each_ref().map(String::as_str)))?;
+        }
+        Ok(())
+    }
+
+    /// Writes an empty line - skipped in markdown
+    /// where sections are already separated by one
+    fn blank(&mut self) -> io::Result<()> {
+        if self.markdown {
+            Ok(())
+        } else {
+            writeln!(self.out)
+        }
+    }
+}
+
+/// Prints a summary of the whole dataset
+///
+/// Includes the lifetime totals, top 10s, diversity metrics,
+/// the busiest periods and how many new artists were discovered each year
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+#[allow(clippy::cast_precision_loss)]
+pub fn all(report: &mut Report<impl Write>, entries: &SongEntries) -> io::Result<()> {
+    report.title(format_args!("YOUR LISTENING LIFE"))?;
+
+    // TOTALS
+    let first = entries.first_date();
+    let last = entries.last_date();
+    let days = days_in(entries);
+    let time = gather::listening_time(entries);
+    let artists = gather::artists(entries);
+    report.line(format_args!(
+        "Between {} and {} ({days} days) you played {} songs",
+        first.date_naive(),
+        last.date_naive(),
+        entries.len()
+    ))?;
+    report.line(format_args!(
+        "for a total of {} days / {} hours / {} minutes,",
+        time.num_days(),
+        time.num_hours(),
+        time.num_minutes()
+    ))?;
+    report.line(format_args!(
+        "that's {:.2} plays and {:.2} minutes a day on average.",
+        entries.len() as f64 / days as f64,
+        time.num_minutes() as f64 / days as f64
+    ))?;
+    report.line(format_args!(
+        "You listened to {} different artists, {} albums and {} songs.",
+        artists.len(),
+        gather::albums(entries).len(),
+        gather::songs(entries, true).len()
+    ))?;
+
+    // TOP LISTS
+    report.blank()?;
+    report.section(format_args!("Top 10 artists"))?;
+    top(report, gather::artists(entries), 10)?;
+    report.section(format_args!("Top 10 albums"))?;
+    top(report, gather::albums(entries), 10)?;
+    report.section(format_args!("Top 10 songs"))?;
+    top(report, gather::songs(entries, true), 10)?;
+
+    // DIVERSITY
+    report.blank()?;
+    report.section(format_args!("Diversity"))?;
+    diversity(report, &ranked(artists), entries.len())?;
+
+    // BUSIEST PERIODS
+    report.blank()?;
+    report.section(format_args!("Busiest periods"))?;
+    busiest_periods(report, entries)?;
+
+    // DISCOVERY
+    report.blank()?;
+    report.section(format_args!("Discovery"))?;
+    discovery(report, entries)?;
+    Ok(())
+}
+
+/// Prints a summary of an `artist`
+///
+/// Includes the totals, the rank among all artists, when they were discovered,
+/// the longest streak and break, the busiest day, the listening clock
+/// and the top albums and songs
+///
+/// Prints a message instead if the artist isn't in the dataset
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+///
+/// # Panics
+///
+/// Unwraps used on the rank, streak and busiest day of the artist,
+/// but won't panic since the artist has been played at that point
+pub fn artist(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    artist: &Artist,
+) -> io::Result<()> {
+    let Some(first_play) = gather::nth_play(entries, artist, 1) else {
+        report.line(format_args!("There's no data for {artist}!"))?;
+        return Ok(());
+    };
+
+    report.title(format_args!("YOU AND {artist}"))?;
+
+    // OVERVIEW
+    let plays = gather::plays(entries, artist);
+    let time = gather::listening_time_of(entries, artist);
+    report.line(format_args!(
+        "You played {artist} {plays} times for a total of {} hours ({} minutes).",
+        time.num_hours(),
+        time.num_minutes()
+    ))?;
+    let artists = ranked(gather::artists(entries));
+    // unwrap ok because the artist is in the dataset
+    let rank = artists.iter().position(|(art, _)| art == artist).unwrap() + 1;
+    report.line(format_args!(
+        "That makes them #{rank} of the {} artists you've listened to.",
+        artists.len()
+    ))?;
+    let songs = gather::songs_from(entries, artist);
+    // the size of their whole catalog isn't in the dataset,
+    // so only the distinct songs played can be shown
+    report.line(format_args!(
+        "You've heard {} different songs on {} albums.",
+        songs.len(),
+        gather::albums_from_artist(entries, artist).len()
+    ))?;
+
+    // DISCOVERY
+    report.blank()?;
+    report.section(format_args!("Discovery"))?;
+    report.line(format_args!(
+        "You discovered them on {} with {}.",
+        first_play.timestamp.date_naive(),
+        first_play.track
+    ))?;
+
+    // HABITS
+    report.blank()?;
+    report.section(format_args!("Habits"))?;
+    // unwrap ok because the artist is in the dataset
+    let (start, end) = gather::longest_streak(entries, artist).unwrap();
+    report.line(format_args!(
+        "Your longest streak was {} days in a row from {start} to {end}.",
+        (end - start).num_days() + 1
+    ))?;
+    match gather::listening_gaps(entries, artist)
+        .into_iter()
+        .max_by_key(|(date, gap)| (*gap, Reverse(*date)))
+    {
+        Some((date, gap)) => report.line(format_args!(
+            "Your longest break from them was {} days, until {}.",
+            gap.num_days(),
+            date.date_naive()
+        ))?,
+        None => report.line(format_args!(
+            "You've played them only once, so there were no breaks."
+        ))?,
+    }
+    // unwrap ok because the artist is in the dataset
+    let (day, day_plays) = entries
+        .iter()
+        .filter(|entry| artist.is_entry(entry))
+        .counts_by(|entry| entry.timestamp.date_naive())
+        .into_iter()
+        .max_by_key(|(day, plays)| (*plays, Reverse(*day)))
+        .unwrap();
+    report.line(format_args!(
+        "Your busiest day with them was {day} with {day_plays} plays."
+    ))?;
+    clock(report, &gather::plays_per_hour_of(entries, artist), plays)?;
+
+    // TOP LISTS
+    report.blank()?;
+    report.section(format_args!("Top albums"))?;
+    top(report, gather::albums_from_artist(entries, artist), TOP_NUM)?;
+    report.section(format_args!("Top songs"))?;
+    top(report, songs, TOP_NUM)?;
+    Ok(())
+}
+
+/// Prints the hour with the most plays and how the plays
+/// are spread across the parts of the day
+///
+/// `hours` - plays of each hour of the day, see [`gather::plays_per_hour`];
+/// `plays` - sum of the plays
+///
+/// Used by [`artist()`]
+#[allow(clippy::cast_precision_loss)]
+fn clock(report: &mut Report<impl Write>, hours: &[usize; 24], plays: usize) -> io::Result<()> {
+    // unwrap ok because there are 24 hours
+    let (peak, peak_plays) = hours
+        .iter()
+        .enumerate()
+        .max_by_key(|(hour, plays)| (**plays, Reverse(*hour)))
+        .unwrap();
+    report.line(format_args!(
+        "You played them the most at {peak:02}:00 ({peak_plays} plays)."
+    ))?;
+
+    let parts = [
+        ("at night", 0..6),
+        ("in the morning", 6..12),
+        ("in the afternoon", 12..18),
+        ("in the evening", 18..24),
+    ]
+    .map(|(part, range)| {
+        let part_plays: usize = hours[range].iter().sum();
+        format!("{:.2}% {part}", part_plays as f64 / plays as f64 * 100.0)
+    });
+    report.line(format_args!("Your plays were {}.", parts.join(", ")))?;
+    Ok(())
+}
+
+/// Prints how concentrated the plays are on a few artists
+///
+/// `artists` - all artists sorted with [`ranked()`];
+/// `all_plays` - number of all plays
+///
+/// Used by [`all()`]
+#[allow(clippy::cast_precision_loss)]
+fn diversity(
+    report: &mut Report<impl Write>,
+    artists: &[(Artist, usize)],
+    all_plays: usize,
+) -> io::Result<()> {
+    let top_ten_plays: usize = artists.iter().take(10).map(|(_, plays)| plays).sum();
+    report.line(format_args!(
+        "Your top 10 artists make up {:.2}% of your plays.",
+        top_ten_plays as f64 / all_plays as f64 * 100.0
+    ))?;
+
+    // number of top artists needed to reach half of all plays
+    let mut sum = 0;
+    let half = artists
+        .iter()
+        .take_while(|(_, plays)| {
+            let below = sum * 2 < all_plays;
+            sum += plays;
+            below
+        })
+        .count();
+    report.line(format_args!("Half of your plays come from {half} artists."))?;
+
+    let one_timers = artists.iter().filter(|(_, plays)| *plays == 1).count();
+    report.line(format_args!(
+        "{one_timers} artists ({:.2}%) have been played only once.",
+        one_timers as f64 / artists.len() as f64 * 100.0
+    ))?;
+    report.line(format_args!(
+        "On average you played an artist {:.2} times.",
+        all_plays as f64 / artists.len() as f64
+    ))?;
+    Ok(())
+}
+
+/// Prints the busiest year, month, week and day
+///
+/// Used by [`all()`]
+fn busiest_periods(report: &mut Report<impl Write>, entries: &SongEntries) -> io::Result<()> {
+    // unwraps ok because there's at least one entry
+    let (year, plays) = entries
+        .iter()
+        .counts_by(|entry| entry.timestamp.year())
+        .into_iter()
+        .max_by_key(|(year, plays)| (*plays, Reverse(*year)))
+        .unwrap();
+    report.line(format_args!(
+        "Your busiest year was {year} with {plays} plays."
+    ))?;
+
+    let ((year, month), plays) = entries
+        .iter()
+        .counts_by(|entry| (entry.timestamp.year(), entry.timestamp.month()))
+        .into_iter()
+        .max_by_key(|(month, plays)| (*plays, Reverse(*month)))
+        .unwrap();
+    report.line(format_args!(
+        "Your busiest month was {} {year} with {plays} plays.",
+        month_name(month)
+    ))?;
+
+    // unwrap ok because a week is a sane time span
+    let (time, start, end) = entries.max_listening_time(TimeDelta::try_weeks(1).unwrap());
+    report.line(format_args!(
+        "Your busiest week was from {} to {} with {} minutes of listening.",
+        start.date_naive(),
+        end.date_naive(),
+        time.num_minutes()
+    ))?;
+
+    let (day, plays) = entries
+        .iter()
+        .counts_by(|entry| entry.timestamp.date_naive())
+        .into_iter()
+        .max_by_key(|(day, plays)| (*plays, Reverse(*day)))
+        .unwrap();
+    report.line(format_args!(
+        "Your busiest day was {day} with {plays} plays."
+    ))?;
+    Ok(())
+}
+
+/// Prints how many new artists were discovered each year
+/// and which share of that year's plays went to them
+///
+/// Used by [`all()`]
+#[allow(clippy::cast_precision_loss)]
+fn discovery(report: &mut Report<impl Write>, entries: &SongEntries) -> io::Result<()> {
+    let mut known_artists: HashSet<Artist> = HashSet::new();
+
+    for year in entries.first_date().year()..=entries.last_date().year() {
+        let this_year = entries_of_year(entries, year);
+        if this_year.is_empty() {
+            continue;
+        }
+
+        let new_artists: HashSet<Artist> = gather::artists(this_year)
+            .into_keys()
+            .filter(|art| !known_artists.contains(art))
+            .collect();
+        let new_plays = this_year
+            .iter()
+            .filter(|entry| new_artists.contains(&Artist::from(*entry)))
+            .count();
+
+        report.line(format_args!(
+            "{year}: {} new artists ({:.2}% of the plays that year)",
+            new_artists.len(),
+            new_plays as f64 / this_year.len() as f64 * 100.0
+        ))?;
+
+        known_artists.extend(new_artists);
+    }
+    Ok(())
+}
+
+/// Prints a summary of the given `year`
+///
+/// Includes the totals, top artists/albums/songs,
+/// the top artist of every month and comparisons to the previous year
+///
+/// Prints a message instead if there's no data for that year
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+pub fn year(report: &mut Report<impl Write>, entries: &SongEntries, year: i32) -> io::Result<()> {
+    let this_year = entries_of_year(entries, year);
+    if this_year.is_empty() {
+        report.line(format_args!("There's no data for {year}!"))?;
+        return Ok(());
+    }
+    let previous_year = entries_of_year(entries, year - 1);
+
+    report.title(format_args!("YOUR {year} IN MUSIC"))?;
+    period(
+        report,
+        entries,
+        (this_year, &format!("in {year}")),
+        (previous_year, &(year - 1).to_string()),
+    )
+}
+
+/// Prints a summary of the period between `start` and `end`
+/// (e.g. a semester or a job) with the same sections as [`year()`]
+///
+/// It's compared to the period of the same length right before `start`
+///
+/// Prints a message instead if there's no data for that period
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+pub fn range(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+) -> io::Result<()> {
+    let (start_date, end_date) = (start.date_naive(), end.date_naive());
+    let this_period = entries_between(entries, start, end);
+    if this_period.is_empty() {
+        report.line(format_args!(
+            "There's no data between {start_date} and {end_date}!"
+        ))?;
+        return Ok(());
+    }
+    let previous_period = entries_between(entries, &(*start - (*end - *start)), start);
+
+    report.title(format_args!("YOUR {start_date} – {end_date} IN MUSIC"))?;
+    period(
+        report,
+        entries,
+        (this_period, &format!("between {start_date} and {end_date}")),
+        (
+            previous_period,
+            &format!("the {} days before", (end_date - start_date).num_days()),
+        ),
+    )
+}
+
+/// Prints the sections of [`year()`] and [`range()`]
+///
+/// `current` and `previous` are the entries of the summarized period
+/// and of the one it's compared to together with how they're described
+/// in a sentence, e.g. "in 2021" and "2020"
+///
+/// `current` mustn't be empty
+#[allow(clippy::cast_precision_loss)]
+fn period(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    current: (&[SongEntry], &str),
+    previous: (&[SongEntry], &str),
+) -> io::Result<()> {
+    let (this_period, when) = current;
+    let (previous_period, previous_name) = previous;
+
+    // OVERVIEW
+    let plays = this_period.len();
+    let time = gather::listening_time(this_period);
+    let days = days_in(this_period);
+    report.line(format_args!(
+        "{} you played {plays} songs for a total of {} hours ({} minutes),",
+        capitalize(when),
+        time.num_hours(),
+        time.num_minutes()
+    ))?;
+    report.line(format_args!(
+        "that's {:.2} plays and {:.2} minutes a day on average.",
+        plays as f64 / days as f64,
+        time.num_minutes() as f64 / days as f64
+    ))?;
+    report.line(format_args!(
+        "You listened to {} different artists, {} albums and {} songs.",
+        gather::artists(this_period).len(),
+        gather::albums(this_period).len(),
+        gather::songs(this_period, true).len()
+    ))?;
+
+    if previous_period.is_empty() {
+        report.line(format_args!(
+            "There's no data for {previous_name} to compare to."
+        ))?;
+    } else {
+        let previous_time = gather::listening_time(previous_period);
+        report.line(format_args!(
+            "Compared to {previous_name} that's {} plays and {} listening time.",
+            change(plays as f64, previous_period.len() as f64),
+            change(
+                time.num_minutes() as f64,
+                previous_time.num_minutes() as f64
+            )
+        ))?;
+    }
+
+    // artists that haven't been listened to before this period
+    let first_index = entries.partition_point(|entry| entry.timestamp < this_period[0].timestamp);
+    let known_artists: HashSet<Artist> = entries[..first_index].iter().map(Artist::from).collect();
+    let new_artists = gather::artists(this_period)
+        .into_keys()
+        .filter(|art| !known_artists.contains(art))
+        .count();
+    report.line(format_args!(
+        "You discovered {new_artists} artists you had never listened to before."
+    ))?;
+
+    // TOP LISTS
+    report.blank()?;
+    report.section(format_args!("Top artists"))?;
+    top_with_previous(
+        report,
+        gather::artists(this_period),
+        gather::artists(previous_period),
+        previous_name,
+    )?;
+    report.section(format_args!("Top albums"))?;
+    top_with_previous(
+        report,
+        gather::albums(this_period),
+        gather::albums(previous_period),
+        previous_name,
+    )?;
+    report.section(format_args!("Top songs"))?;
+    top_with_previous(
+        report,
+        gather::songs(this_period, true),
+        gather::songs(previous_period, true),
+        previous_name,
+    )?;
+
+    // MONTH BY MONTH
+    report.blank()?;
+    months(report, this_period)?;
+    Ok(())
+}
+
+/// Prints a table with the plays, minutes, top artist and top song
+/// of every month in `entries` and the busiest month and day
+///
+/// Months are labelled with their year if `entries` span more than one year
+///
+/// Used by [`period()`]
+fn months(report: &mut Report<impl Write>, entries: &[SongEntry]) -> io::Result<()> {
+    report.section(format_args!("Month by month"))?;
+    // entries are sorted by timestamp
+    let months = entries
+        .chunk_by(|a, b| {
+            (a.timestamp.year(), a.timestamp.month()) == (b.timestamp.year(), b.timestamp.month())
+        })
+        .collect_vec();
+    let several_years = entries.first().map(|entry| entry.timestamp.year())
+        != entries.last().map(|entry| entry.timestamp.year());
+    let label = |month_entries: &[SongEntry]| {
+        let timestamp = month_entries[0].timestamp;
+        if several_years {
+            format!("{} {}", month_name(timestamp.month()), timestamp.year())
+        } else {
+            month_name(timestamp.month()).to_string()
+        }
+    };
+
+    let rows = months
+        .iter()
+        .map(|month_entries| {
+            // unwraps ok because chunks are never empty
+            let (top_artist, top_artist_plays) = ranked(gather::artists(month_entries))
+                .into_iter()
+                .next()
+                .unwrap();
+            let (top_song, top_song_plays) = ranked(gather::songs(month_entries, true))
+                .into_iter()
+                .next()
+                .unwrap();
+            [
+                label(month_entries),
+                month_entries.len().to_string(),
+                gather::listening_time(month_entries)
+                    .num_minutes()
+                    .to_string(),
+                format!("{top_artist} ({top_artist_plays})"),
+                format!("{top_song} ({top_song_plays})"),
+            ]
+        })
+        .collect_vec();
+    report.table(
+        ["Month", "Plays", "Minutes", "Top artist", "Top song"],
+        &rows,
+    )?;
+
+    // BUSIEST PERIODS
+    // unwraps ok because there's at least one entry
+    let busiest_month = months
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, month_entries)| (month_entries.len(), Reverse(*i)))
+        .map(|(_, month_entries)| *month_entries)
+        .unwrap();
+    let (busiest_day, busiest_day_plays) = entries
+        .iter()
+        .counts_by(|entry| entry.timestamp.date_naive())
+        .into_iter()
+        .max_by_key(|(day, plays)| (*plays, Reverse(*day)))
+        .unwrap();
+    report.line(format_args!(
+        "Your busiest month was {} with {} plays and your busiest day was {busiest_day} with {busiest_day_plays} plays.",
+        label(busiest_month),
+        busiest_month.len()
+    ))?;
+    Ok(())
+}
+
+/// Prints a joint summary of two years, `first` and `second`
+///
+/// Includes the difference in plays and listening time,
+/// the artists that rose and fell the most, how many of the artists
+/// discovered in `first` were still played in `second`
+/// and the overlap of the top 10 artists and songs
+///
+/// Prints a message instead if there's no data for one of the years
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+#[allow(clippy::cast_precision_loss)]
+pub fn compare(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    first: i32,
+    second: i32,
+) -> io::Result<()> {
+    let first_year = entries_of_year(entries, first);
+    let second_year = entries_of_year(entries, second);
+    for (year, entries_of_year) in [(first, first_year), (second, second_year)] {
+        if entries_of_year.is_empty() {
+            report.line(format_args!("There's no data for {year}!"))?;
+            return Ok(());
+        }
+    }
+
+    report.title(format_args!("{first} VS {second}"))?;
+
+    // OVERVIEW
+    let first_time = gather::listening_time(first_year);
+    let second_time = gather::listening_time(second_year);
+    for (year, entries_of_year, time) in [
+        (first, first_year, first_time),
+        (second, second_year, second_time),
+    ] {
+        report.line(format_args!(
+            "In {year} you played {} songs by {} artists for a total of {} hours ({} minutes).",
+            entries_of_year.len(),
+            gather::artists(entries_of_year).len(),
+            time.num_hours(),
+            time.num_minutes()
+        ))?;
+    }
+    let minutes_delta = (second_time - first_time).num_minutes();
+    report.line(format_args!(
+        "That's {} plays and {} listening time ({}{minutes_delta} minutes) in {second}.",
+        change(second_year.len() as f64, first_year.len() as f64),
+        change(
+            second_time.num_minutes() as f64,
+            first_time.num_minutes() as f64
+        ),
+        if minutes_delta > 0 { "+" } else { "" }
+    ))?;
+
+    // RISERS AND FALLERS
+    let first_artists = gather::artists(first_year);
+    let second_artists = gather::artists(second_year);
+    let deltas = first_artists
+        .keys()
+        .chain(second_artists.keys())
+        .unique()
+        .map(|art| {
+            let before = first_artists.get(art).copied().unwrap_or_default();
+            let after = second_artists.get(art).copied().unwrap_or_default();
+            #[allow(clippy::cast_possible_wrap)]
+            (art, after as isize - before as isize, before, after)
+        })
+        .sorted_unstable_by_key(|(art, delta, _, _)| (Reverse(*delta), *art))
+        .collect_vec();
+    report.blank()?;
+    report.section(format_args!("Biggest risers"))?;
+    for (art, delta, before, after) in deltas
+        .iter()
+        .filter(|(_, delta, _, _)| *delta > 0)
+        .take(TOP_NUM)
+    {
+        report.line(format_args!("{art} | +{delta} plays ({before} -> {after})"))?;
+    }
+    report.section(format_args!("Biggest fallers"))?;
+    for (art, delta, before, after) in deltas
+        .iter()
+        .filter(|(_, delta, _, _)| *delta < 0)
+        .sorted_unstable_by_key(|(art, delta, _, _)| (*delta, *art))
+        .take(TOP_NUM)
+    {
+        report.line(format_args!("{art} | {delta} plays ({before} -> {after})"))?;
+    }
+
+    // DISCOVERIES KEPT VS ABANDONED
+    report.blank()?;
+    report.section(format_args!("Discoveries of {first}"))?;
+    let first_index = entries.partition_point(|entry| entry.timestamp.year() < first);
+    let known_artists: HashSet<Artist> = entries[..first_index].iter().map(Artist::from).collect();
+    let (kept, abandoned): (Vec<_>, Vec<_>) = first_artists
+        .keys()
+        .filter(|art| !known_artists.contains(art))
+        .partition(|art| second_artists.contains_key(art));
+    report.line(format_args!(
+        "You discovered {} artists in {first}, {} of them you still played in {second} and {} you abandoned.",
+        kept.len() + abandoned.len(),
+        kept.len(),
+        abandoned.len()
+    ))?;
+    let kept_ranked = kept
+        .into_iter()
+        .map(|art| (art, second_artists[art]))
+        .sorted_unstable_by_key(|(art, plays)| (Reverse(*plays), *art))
+        .take(TOP_NUM)
+        .map(|(art, plays)| format!("{art} ({plays} plays)"))
+        .join(", ");
+    if !kept_ranked.is_empty() {
+        report.line(format_args!("Kept the most: {kept_ranked}"))?;
+    }
+
+    // TOP 10 OVERLAP
+    report.blank()?;
+    report.section(format_args!("Top 10 overlap"))?;
+    overlap(report, "artists", first_artists, second_artists)?;
+    overlap(
+        report,
+        "songs",
+        gather::songs(first_year, true),
+        gather::songs(second_year, true),
+    )?;
+    Ok(())
+}
+
+/// Prints how many and which aspects are in the top 10 of both years
+///
+/// Used by [`compare()`]
+fn overlap<Asp: Music + std::hash::Hash>(
+    report: &mut Report<impl Write>,
+    kind: &str,
+    first_year: HashMap<Asp, usize>,
+    second_year: HashMap<Asp, usize>,
+) -> io::Result<()> {
+    let first_top: HashSet<Asp> = ranked(first_year)
+        .into_iter()
+        .take(10)
+        .map(|(asp, _)| asp)
+        .collect();
+    let shared = ranked(second_year)
+        .into_iter()
+        .take(10)
+        .filter(|(asp, _)| first_top.contains(asp))
+        .map(|(asp, _)| asp)
+        .collect_vec();
+    report.line(format_args!(
+        "{} of the top 10 {kind} are in both years{}",
+        shared.len(),
+        if shared.is_empty() {
+            String::from(".")
+        } else {
+            format!(": {}", shared.iter().join(", "))
+        }
+    ))?;
+    Ok(())
+}
+
+/// Prints a summary of the listening sessions (see [`gather::sessions`])
+/// which end after more than `max_gap` of silence
+///
+/// Includes the average and median session length, the longest session,
+/// the most common start hour and how many songs are played per session
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+///
+/// # Panics
+///
+/// Unwraps used on the longest session and most common start hour,
+/// but won't panic since there's at least one session
+#[allow(clippy::cast_precision_loss)]
+pub fn sessions(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    max_gap: TimeDelta,
+) -> io::Result<()> {
+    let sessions = gather::sessions(entries, max_gap);
+    let lengths = sessions
+        .iter()
+        .map(|session| gather::session_length(session))
+        .sorted_unstable()
+        .collect_vec();
+
+    report.title(format_args!(
+        "YOUR LISTENING SESSIONS (MAX. {} MINUTES PAUSE)",
+        max_gap.num_minutes()
+    ))?;
+
+    // LENGTHS
+    let total: TimeDelta = lengths.iter().sum();
+    let median = if lengths.len() % 2 == 0 {
+        (lengths[lengths.len() / 2 - 1] + lengths[lengths.len() / 2]) / 2
+    } else {
+        lengths[lengths.len() / 2]
+    };
+    report.line(format_args!(
+        "You had {} sessions with {:.2} songs each on average.",
+        sessions.len(),
+        entries.len() as f64 / sessions.len() as f64
+    ))?;
+    report.line(format_args!(
+        "An average session lasted {:.2} minutes, the median one {} minutes.",
+        total.num_minutes() as f64 / lengths.len() as f64,
+        median.num_minutes()
+    ))?;
+
+    let longest = sessions
+        .iter()
+        .max_by_key(|session| gather::session_length(session))
+        .unwrap();
+    let (top_artist, top_plays) = ranked(gather::artists(longest)).into_iter().next().unwrap();
+    report.line(format_args!(
+        "Your longest session started on {} and lasted {} minutes with {} songs, {top_plays} of them by {top_artist}.",
+        (longest[0].timestamp - longest[0].time_played).format("%Y-%m-%d %H:%M"),
+        gather::session_length(longest).num_minutes(),
+        longest.len()
+    ))?;
+
+    let start_hours = sessions
+        .iter()
+        .counts_by(|session| (session[0].timestamp - session[0].time_played).hour());
+    let (hour, hour_sessions) = start_hours
+        .into_iter()
+        .max_by_key(|(hour, count)| (*count, Reverse(*hour)))
+        .unwrap();
+    report.line(format_args!(
+        "Most of your sessions started at {hour:02}:00 ({hour_sessions} sessions)."
+    ))?;
+
+    // SONGS PER SESSION
+    report.blank()?;
+    report.section(format_args!("Songs per session"))?;
+    let buckets: [(usize, usize, &str); 5] = [
+        (1, 1, "1"),
+        (2, 5, "2-5"),
+        (6, 10, "6-10"),
+        (11, 20, "11-20"),
+        (21, usize::MAX, "21+"),
+    ];
+    let rows = buckets
+        .iter()
+        .map(|(min, max, label)| {
+            let count = sessions
+                .iter()
+                .filter(|session| (*min..=*max).contains(&session.len()))
+                .count();
+            [
+                (*label).to_string(),
+                count.to_string(),
+                format!("{:.2}%", count as f64 / sessions.len() as f64 * 100.0),
+            ]
+        })
+        .collect_vec();
+    report.table(["Songs", "Sessions", "Share"], &rows)?;
+    Ok(())
+}
+
+/// Prints a summary of a single `day`
+///
+/// Includes the totals, the top artist and album,
+/// a timeline of the listening sessions (see [`gather::sessions`])
+/// with every song played and the minutes per hour
+///
+/// Prints a message instead if there's no data for that day
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+///
+/// # Panics
+///
+/// Unwraps used on the top artist and album,
+/// but won't panic since there's at least one play that day
+pub fn day(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    day: NaiveDate,
+) -> io::Result<()> {
+    // entries are sorted by timestamp
+    let start = entries.partition_point(|entry| entry.timestamp.date_naive() < day);
+    let end = entries.partition_point(|entry| entry.timestamp.date_naive() <= day);
+    let entries_of_day = &entries[start..end];
+    if entries_of_day.is_empty() {
+        report.line(format_args!("There's no data for {day}!"))?;
+        return Ok(());
+    }
+
+    report.title(format_args!("YOUR {day} ({})", day.format("%A")))?;
+
+    // OVERVIEW
+    let (top_artist, top_artist_plays) = ranked(gather::artists(entries_of_day))
+        .into_iter()
+        .next()
+        .unwrap();
+    let (top_album, top_album_plays) = ranked(gather::albums(entries_of_day))
+        .into_iter()
+        .next()
+        .unwrap();
+    report.line(format_args!(
+        "You played {} songs by {} artists for a total of {} minutes.",
+        entries_of_day.len(),
+        gather::artists(entries_of_day).len(),
+        gather::listening_time(entries_of_day).num_minutes()
+    ))?;
+    report.line(format_args!(
+        "Your top artist was {top_artist} ({top_artist_plays} plays) and your top album {top_album} ({top_album_plays} plays)."
+    ))?;
+
+    // TIMELINE
+    report.blank()?;
+    report.section(format_args!("Timeline"))?;
+    // same default as the prompt of `plot sessions`
+    let max_gap = TimeDelta::try_minutes(30).unwrap();
+    for session in gather::sessions(entries_of_day, max_gap) {
+        let first = &session[0];
+        report.line(format_args!(
+            "{}–{} ({} minutes, {} songs)",
+            (first.timestamp - first.time_played).format("%H:%M"),
+            session[session.len() - 1].timestamp.format("%H:%M"),
+            gather::session_length(session).num_minutes(),
+            session.len()
+        ))?;
+        let rows = session
+            .iter()
+            .map(|entry| {
+                [
+                    (entry.timestamp - entry.time_played)
+                        .format("%H:%M")
+                        .to_string(),
+                    Song::from(entry).to_string(),
+                ]
+            })
+            .collect_vec();
+        report.table(["Time", "Song"], &rows)?;
+    }
+
+    // PER HOUR
+    report.blank()?;
+    report.section(format_args!("Hour by hour"))?;
+    let rows = entries_of_day
+        .iter()
+        .chunk_by(|entry| entry.timestamp.hour())
+        .into_iter()
+        .map(|(hour, plays)| {
+            let plays = plays.collect_vec();
+            [
+                format!("{hour:02}:00"),
+                plays.len().to_string(),
+                plays
+                    .iter()
+                    .map(|entry| entry.time_played)
+                    .sum::<TimeDelta>()
+                    .num_minutes()
+                    .to_string(),
+            ]
+        })
+        .collect_vec();
+    report.table(["Hour", "Plays", "Minutes"], &rows)?;
+    Ok(())
+}
+
+/// Prints the `num` days with the most listening time
+/// of all plays or only of `artist` and what dominated each of them
+///
+/// For all plays that's the top artist and song of the day,
+/// for an artist their top album and song of the day
+///
+/// # Errors
+///
+/// Returns an error if the summary couldn't be written
+///
+/// # Panics
+///
+/// Unwraps used on the top aspects of each day,
+/// but won't panic since only days with plays are included
+pub fn top_days(
+    report: &mut Report<impl Write>,
+    entries: &SongEntries,
+    artist: Option<&Artist>,
+    num: usize,
+) -> io::Result<()> {
+    let plays = entries
+        .iter()
+        .filter(|entry| match artist {
+            Some(art) => art.is_entry(entry),
+            None => true,
+        })
+        .collect_vec();
+
+    match artist {
+        Some(art) => report.title(format_args!("YOUR TOP {num} DAYS OF {art}"))?,
+        None => report.title(format_args!("YOUR TOP {num} DAYS"))?,
+    }
+    if plays.is_empty() {
+        report.line(format_args!("There's no data for that!"))?;
+        return Ok(());
+    }
+
+    // entries are sorted by timestamp
+    let days = plays
+        .into_iter()
+        .chunk_by(|entry| entry.timestamp.date_naive())
+        .into_iter()
+        .map(|(day, plays)| {
+            let plays: Vec<SongEntry> = plays.cloned().collect();
+            let time: TimeDelta = plays.iter().map(|entry| entry.time_played).sum();
+            (day, time, plays)
+        })
+        .sorted_unstable_by_key(|(day, time, _)| (Reverse(*time), *day))
+        .take(num)
+        .collect_vec();
+
+    for (i, (day, time, plays)) in days.iter().enumerate() {
+        let dominated_by = if artist.is_some() {
+            let (top_album, top_plays) = ranked(gather::albums(plays)).into_iter().next().unwrap();
+            format!("mostly {} ({top_plays} plays)", top_album.name)
+        } else {
+            let (top_artist, top_plays) =
+                ranked(gather::artists(plays)).into_iter().next().unwrap();
+            format!("mostly {top_artist} ({top_plays} plays)")
+        };
+        let (top_song, top_song_plays) = ranked(gather::songs(plays, true))
+            .into_iter()
+            .next()
+            .unwrap();
+        report.line(format_args!(
+            "#{}: {day} ({}) | {} minutes, {} plays | {dominated_by}, top song: {} ({top_song_plays} plays)",
+            i + 1,
+            day.format("%a"),
+            time.num_minutes(),
+            plays.len(),
+            // the artist is already in the title
+            if artist.is_some() {
+                top_song.name.to_string()
+            } else {
+                format!("{} - {}", top_song.album.artist, top_song.name)
+            }
+        ))?;
+    }
+    Ok(())
+}
+
+/// Returns a shareable "card" with the listening time
+/// and the top artists and songs between `start` and `end` as an SVG
+///
+/// Like the official Spotify Wrapped, but for any period.
+/// Use [`write_card()`] to save it as a PNG
+///
+/// # Panics
+///
+/// Panics if `start` is after `end`
+#[must_use]
+pub fn card(entries: &SongEntries, start: &DateTime<Local>, end: &DateTime<Local>) -> String {
+    let period = entries.between(start, end);
+    let title = if start.date_naive() <= entries.first_date().date_naive()
+        && end.date_naive() >= entries.last_date().date_naive()
+    {
+        String::from("MY LISTENING LIFE")
+    } else {
+        format!("{} – {}", start.date_naive(), end.date_naive())
+    };
+
+    let top_artists = ranked(gather::artists(period))
+        .into_iter()
+        .take(TOP_NUM)
+        .map(|(art, _)| art.to_string())
+        .collect_vec();
+    let top_songs = ranked(gather::songs(period, true))
+        .into_iter()
+        .take(TOP_NUM)
+        .map(|(song, _)| format!("{} – {}", song.name, song.album.artist))
+        .collect_vec();
+
+    let mut elements = vec![
+        format!(r##"<rect width="100%" height="100%" fill="#121212"/>"##),
+        card_text(CARD_WIDTH / 2, 130, 56, "#1db954", &title, true),
+        card_text(
+            CARD_WIDTH / 2,
+            280,
+            140,
+            "#ffffff",
+            &gather::listening_time(period).num_minutes().to_string(),
+            true,
+        ),
+        card_text(
+            CARD_WIDTH / 2,
+            350,
+            40,
+            "#b3b3b3",
+            &format!("minutes listened · {} plays", period.len()),
+            true,
+        ),
+    ];
+    for (heading, names, y) in [
+        ("Top artists", top_artists, 480),
+        ("Top songs", top_songs, 900),
+    ] {
+        elements.push(card_text(80, y, 48, "#1db954", heading, false));
+        for (i, name) in names.iter().enumerate() {
+            elements.push(card_text(
+                80,
+                y + 70 * (i + 1),
+                40,
+                "#ffffff",
+                &format!("{}. {name}", i + 1),
+                false,
+            ));
+        }
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CARD_WIDTH}\" height=\"{CARD_HEIGHT}\" font-family=\"sans-serif\">\n{}\n</svg>\n",
+        elements.join("\n")
+    )
+}
+
+/// Returns an SVG text element at `x`/`y` for [`card()`]
+///
+/// `text` is escaped and cut off after [`CARD_NAME_LENGTH`] characters
+fn card_text(x: usize, y: usize, size: usize, color: &str, text: &str, centered: bool) -> String {
+    let mut cut: String = text.chars().take(CARD_NAME_LENGTH).collect();
+    if cut.len() < text.len() {
+        cut.push('…');
+    }
+    let escaped = cut
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let anchor = if centered { "middle" } else { "start" };
+    format!(
+        r#"<text x="{x}" y="{y}" font-size="{size}" fill="{color}" text-anchor="{anchor}">{escaped}</text>"#
+    )
+}
+
+/// Returns the entries of the given `year`
+///
+/// Unlike [`SongEntries::between`] it returns an empty slice
+/// if there are no entries in that year
+fn entries_of_year(entries: &SongEntries, year: i32) -> &[SongEntry] {
+    // entries are sorted by timestamp
+    let start = entries.partition_point(|entry| entry.timestamp.year() < year);
+    let end = entries.partition_point(|entry| entry.timestamp.year() <= year);
+    &entries[start..end]
+}
+
+/// Returns the entries between `start` and `end` (both inclusive)
+///
+/// Unlike [`SongEntries::between`] it returns an empty slice
+/// if there are no entries in that period
+fn entries_between<'a>(
+    entries: &'a SongEntries,
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+) -> &'a [SongEntry] {
+    // entries are sorted by timestamp
+    let first = entries.partition_point(|entry| entry.timestamp < *start);
+    let last = entries.partition_point(|entry| entry.timestamp <= *end);
+    &entries[first..last.max(first)]
+}
+
+/// Returns `text` with its first letter in uppercase
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the number of days between the first and last entry (both inclusive)
+///
+/// Returns 1 if `entries` is empty
+fn days_in(entries: &[SongEntry]) -> i64 {
+    match (entries.first(), entries.last()) {
+        (Some(first), Some(last)) => {
+            (last.timestamp.date_naive() - first.timestamp.date_naive()).num_days() + 1
+        }
+        _ => 1,
+    }
+}
+
+/// Returns the aspects sorted by their plays (descending) and name (ascending)
+fn ranked<Asp: Music>(music_dict: HashMap<Asp, usize>) -> Vec<(Asp, usize)> {
+    music_dict
+        .into_iter()
+        .sorted_unstable_by_key(|(asp, plays)| (Reverse(*plays), asp.clone()))
+        .collect_vec()
+}
+
+/// Prints the top `num` aspects with their plays
+fn top<Asp: Music>(
+    report: &mut Report<impl Write>,
+    music_dict: HashMap<Asp, usize>,
+    num: usize,
+) -> io::Result<()> {
+    for (i, (asp, plays)) in ranked(music_dict).into_iter().take(num).enumerate() {
+        report.line(format_args!("#{}: {asp} | {plays} plays", i + 1))?;
+    }
+    Ok(())
+}
+
+/// Prints the top [`TOP_NUM`] aspects with their position in the previous period
+/// named `previous`, e.g. "2020"
+fn top_with_previous<Asp: Music + std::hash::Hash>(
+    report: &mut Report<impl Write>,
+    this_year: HashMap<Asp, usize>,
+    previous_year: HashMap<Asp, usize>,
+    previous: &str,
+) -> io::Result<()> {
+    let previous_positions: HashMap<Asp, usize> = ranked(previous_year)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (asp, _))| (asp, i + 1))
+        .collect();
+
+    for (i, (asp, plays)) in ranked(this_year).into_iter().take(TOP_NUM).enumerate() {
+        let before = match previous_positions.get(&asp) {
+            Some(position) => format!("#{position} in {previous}"),
+            None => format!("not played in {previous}"),
+        };
+        report.line(format_args!("#{}: {asp} | {plays} plays ({before})", i + 1))?;
+    }
+    Ok(())
+}
+
+/// Describes the relative change from `before` to `now`,
+/// e.g. "12.34% more" or "the same amount of"
+///
+/// "new" if there's nothing to compare to, i.e. `before` is `0`
+fn change(now: f64, before: f64) -> String {
+    if before == 0.0 {
+        return if now == 0.0 {
+            String::from("the same amount of")
+        } else {
+            String::from("new")
+        };
+    }
+    let percent = (now - before) / before * 100.0;
+    if percent > 0.0 {
+        format!("{percent:.2}% more")
+    } else if percent < 0.0 {
+        format!("{:.2}% less", -percent)
+    } else {
+        String::from("the same amount of")
+    }
+}
+
+/// Returns the English name of a month (1-12)
+///
+/// # Panics
+///
+/// Panics if `month` isn't within 1-12
+fn month_name(month: u32) -> &'static str {
+    // unwrap ok because chrono months are always within 1-12
+    Month::try_from(u8::try_from(month).unwrap())
+        .unwrap()
+        .name()
+}
@@ -1,24 +1,51 @@
 //! Module for creating traces used in [`plot`][crate::plot]
+//!
+//! The data itself comes from [`series`], this module only turns it into
+//! [`plotly`] traces
 
+use chrono::Datelike;
 use endsong::prelude::*;
-use plotly::{Scatter, Trace};
+use endsong::series::{Bin, Series};
+use plotly::common::Mode;
+use plotly::{Bar, Scatter, Trace};
+
+use crate::print::Aspect;
 
 /// Wrapper to use instead of [`Box<dyn Trace>`][plotly::Trace]
 /// to access internal methods
 #[allow(clippy::module_name_repetitions)]
 pub enum TraceType {
     /// trace of absolute amount of plays
-    Absolute(Box<Scatter<String, usize>>),
-    /// trace of relative amount of plays
+    Absolute(Box<Scatter<String, f64>>),
+    /// trace of relative amount of plays (or minutes)
     Relative(Box<Scatter<String, f64>>),
+    /// trace of absolute amount of minutes listened
+    Duration(Box<Scatter<String, f64>>),
+    /// trace of plays or minutes within each [`Bin`]
+    Binned(Box<Bar<String, f64>>),
+    /// trace of the days since the previous play, see [`gaps`]
+    Gaps(Box<Scatter<String, f64>>),
+    /// trace of the [`rolling_average`] of daily plays
+    ///
+    /// Drawn on a secondary y-axis, since it's way smaller than the other traces
+    Average(Box<Scatter<String, f64>>),
+    /// trace of the [`rolling_average`] of daily minutes listened
+    ///
+    /// Drawn on a secondary y-axis like [`TraceType::Average`]
+    DurationAverage(Box<Scatter<String, f64>>),
 }
 impl TraceType {
     /// Returns the inner trace that can be added to the [`Plot`][plotly::Plot]
     #[must_use]
     pub fn get_inner(self) -> Box<dyn Trace> {
         match self {
-            TraceType::Absolute(trace) => trace,
-            TraceType::Relative(trace) => trace,
+            TraceType::Absolute(trace)
+            | TraceType::Relative(trace)
+            | TraceType::Duration(trace)
+            | TraceType::Gaps(trace)
+            | TraceType::Average(trace)
+            | TraceType::DurationAverage(trace) => trace,
+            TraceType::Binned(trace) => trace,
         }
     }
 }
@@ -30,66 +57,283 @@ fn format_date(date: &DateTime<Local>) -> String {
     date.format("%Y-%m-%d %H:%M").to_string()
 }
 
+/// Formats date for x-axis to `%Y-%m-%d`
+///
+/// Used for series with one point per day, week etc.
+fn format_day(date: &DateTime<Local>) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Splits a [`Series`] into the x-axis (dates formatted with `format`)
+/// and y-axis values of a trace
+fn unzip(series: Series, format: fn(&DateTime<Local>) -> String) -> (Vec<String>, Vec<f64>) {
+    series
+        .into_iter()
+        .map(|(date, value)| (format(&date), value))
+        .unzip()
+}
+
+/// Keeps only the last point of each [`Bin`] if `granularity` is set,
+/// see [`series::coarsen`]
+fn coarsen(series: Series, granularity: Option<Bin>) -> Series {
+    match granularity {
+        Some(bin) => series::coarsen(series, bin),
+        None => series,
+    }
+}
+
 /// Creates a trace of the absolute amount of plays
 ///
+/// With one point per play, or per [`Bin`] if `granularity` is set,
+/// which shrinks the plot a lot for aspects with many plays
+///
 /// Creates an empty trace if `aspect` is not in `entries`
 #[must_use]
-pub fn absolute<Asp: Music>(entries: &SongEntries, aspect: &Asp) -> TraceType {
+pub fn absolute<Asp: Music>(
+    entries: &SongEntries,
+    aspect: &Asp,
+    granularity: Option<Bin>,
+) -> TraceType {
+    let series = coarsen(series::absolute(entries, aspect), granularity);
+    let (times, plays) = unzip(series, format_date);
+
+    let title = format!("{aspect}");
+    let trace = Scatter::new(times, plays).name(title);
+
+    TraceType::Absolute(trace)
+}
+
+/// Creates a trace of the absolute amount of minutes listened
+///
+/// With one point per play, or per [`Bin`] if `granularity` is set
+///
+/// Creates an empty trace if `aspect` is not in `entries`
+#[must_use]
+pub fn absolute_duration<Asp: Music>(
+    entries: &SongEntries,
+    aspect: &Asp,
+    granularity: Option<Bin>,
+) -> TraceType {
+    let series = coarsen(series::absolute_duration(entries, aspect), granularity);
+    let (times, minutes) = unzip(series, format_date);
+
+    let title = format!("{aspect} | minutes");
+    let trace = Scatter::new(times, minutes).name(title);
+
+    TraceType::Duration(trace)
+}
+
+/// Creates a trace of the absolute amount of plays
+/// of all versions of a song (e.g. the single and the album version) summed up
+///
+/// `versions` are the same song in different albums, see [`find::song`]
+///
+/// Creates an empty trace if `versions` is empty
+#[must_use]
+pub fn absolute_versions(entries: &SongEntries, versions: &[Song]) -> TraceType {
     let mut times = Vec::<String>::with_capacity(entries.len());
-    let mut plays = Vec::<usize>::with_capacity(entries.len());
+    let mut plays = Vec::<f64>::with_capacity(entries.len());
 
-    // since each date represents a single listen, we can just count up
-    let mut aspect_plays = 0;
+    let mut song_plays = 0.0;
 
-    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
-        aspect_plays += 1;
+    for entry in entries
+        .iter()
+        .filter(|entry| versions.iter().any(|song| song.is_entry(entry)))
+    {
+        song_plays += 1.0;
         times.push(format_date(&entry.timestamp));
-        plays.push(aspect_plays);
+        plays.push(song_plays);
     }
 
-    let title = format!("{aspect}");
+    let title = match versions.first() {
+        Some(song) => format!("{} - {} | all versions", song.album.artist, song.name),
+        None => String::new(),
+    };
     let trace = Scatter::new(times, plays).name(title);
 
     TraceType::Absolute(trace)
 }
 
+/// Creates a trace of how many distinct artists, albums or songs
+/// had been listened to up to each date, see [`gather::library_growth`]
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn library_growth(entries: &SongEntries, aspect: Aspect) -> TraceType {
+    let growth = match aspect {
+        Aspect::Artists => gather::library_growth::<Artist>(entries),
+        Aspect::Albums => gather::library_growth::<Album>(entries),
+        Aspect::Songs => gather::library_growth::<Song>(entries),
+    };
+    let (times, counts): (Vec<String>, Vec<f64>) = growth
+        .into_iter()
+        .map(|(date, count)| (format_date(&date), count as f64))
+        .unzip();
+
+    let title = format!("distinct {aspect}");
+    let trace = Scatter::new(times, counts).name(title);
+
+    TraceType::Absolute(trace)
+}
+
+/// Creates a trace of the days since the previous play at each play,
+/// see [`gather::listening_gaps`]
+///
+/// High points show when `aspect` was drifted away from
+/// and at which play it came back
+///
+/// Creates an empty trace if `aspect` is played less than twice in `entries`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn gaps<Asp: Music>(entries: &SongEntries, aspect: &Asp) -> TraceType {
+    let (times, days): (Vec<String>, Vec<f64>) = gather::listening_gaps(entries, aspect)
+        .into_iter()
+        // in fractional days, so that several plays a day don't all show up as 0
+        .map(|(date, gap)| (format_date(&date), gap.num_seconds() as f64 / 86_400.0))
+        .unzip();
+
+    let title = format!("{aspect} | days since the previous play");
+    let trace = Scatter::new(times, days).name(title).mode(Mode::Markers);
+
+    TraceType::Gaps(trace)
+}
+
+/// Creates a trace for each year with the plays of `aspect` in each month,
+/// on a shared January to December axis, see [`gather::monthly_plays_by_year`]
+///
+/// If `cumulative` is true, the plays are summed up over the year,
+/// otherwise each month only shows its own plays.
+/// The months after the end of the data are left out.
+///
+/// Creates no traces if `aspect` is not in `entries`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn years<Asp: Music>(entries: &SongEntries, aspect: &Asp, cumulative: bool) -> Vec<TraceType> {
+    /// Names of the months on the x-axis
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let last_date = entries.last_date();
+
+    gather::monthly_plays_by_year(entries, aspect)
+        .into_iter()
+        .map(|(year, months)| {
+            let end = if year == last_date.year() {
+                last_date.month() as usize
+            } else {
+                MONTHS.len()
+            };
+
+            let mut sum = 0;
+            let (names, plays): (Vec<String>, Vec<f64>) = MONTHS
+                .iter()
+                .zip(months)
+                .take(end)
+                .map(|(name, plays)| {
+                    sum = if cumulative { sum + plays } else { plays };
+                    ((*name).to_string(), sum as f64)
+                })
+                .unzip();
+
+            let trace = Scatter::new(names, plays).name(year.to_string());
+            TraceType::Absolute(trace)
+        })
+        .collect()
+}
+
+/// Creates a trace of the amount of plays (or minutes if `minutes` is true)
+/// within each [`Bin`]
+///
+/// See [`series::binned`]
+#[must_use]
+pub fn binned<Asp: Music>(
+    entries: &SongEntries,
+    aspect: &Asp,
+    bin: Bin,
+    minutes: bool,
+) -> TraceType {
+    let (dates, values) = unzip(series::binned(entries, aspect, bin, minutes), format_day);
+
+    let unit = if minutes { "minutes" } else { "plays" };
+    let title = format!("{aspect} | {unit} per {bin}");
+    let trace = Bar::new(dates, values).name(title);
+
+    TraceType::Binned(trace)
+}
+
+/// Creates a trace of the average of daily plays (or minutes listened
+/// if `minutes` is `true`) over the last `days` days
+///
+/// See [`series::rolling_average`]
+///
+/// # Panics
+///
+/// Panics if `days` is 0
+#[must_use]
+pub fn rolling_average<Asp: Music>(
+    entries: &SongEntries,
+    aspect: &Asp,
+    days: usize,
+    minutes: bool,
+) -> TraceType {
+    let (dates, averages) = unzip(
+        series::rolling_average(entries, aspect, days, minutes),
+        format_day,
+    );
+
+    let unit = if minutes { "minutes" } else { "plays" };
+    let title = format!("{aspect} | {days}-day average of daily {unit}");
+    let trace = Scatter::new(dates, averages).name(title).y_axis("y2");
+
+    if minutes {
+        TraceType::DurationAverage(trace)
+    } else {
+        TraceType::Average(trace)
+    }
+}
+
 /// Module for relative traces
 ///
-/// Either to all plays, the artist or the album
+/// Either to all plays, the artist, the album or all plays of each period
 pub mod relative {
     use endsong::prelude::*;
+    use endsong::series::Bin;
+    use itertools::Itertools;
     use plotly::Scatter;
 
-    use super::{format_date, TraceType};
+    use endsong::series::{Series, Smoothing};
+
+    use super::{coarsen, format_date, format_day, unzip, TraceType};
+
+    /// Smooths `series` if `smoothing` is set, then [coarsens][coarsen] it,
+    /// so that the smoothing still uses every play
+    fn shape(series: Series, granularity: Option<Bin>, smoothing: Option<Smoothing>) -> Series {
+        let series = match smoothing {
+            Some(smoothing) => series::smooth(series, smoothing),
+            None => series,
+        };
+        coarsen(series, granularity)
+    }
 
     /// Creates a trace of the amount of plays of an [`Music`] relative to all plays
     ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
     /// Creates an empty trace if `aspect` is not in `entries`
     #[must_use]
-    pub fn to_all<Asp: Music>(entries: &SongEntries, aspect: &Asp) -> TraceType {
-        let mut times = Vec::<String>::with_capacity(entries.len());
-        // percentages relative to the sum of all plays
-        let mut plays = Vec::<f64>::with_capacity(entries.len());
-
-        let mut aspect_plays = 0.0;
-        let mut all_plays = 0.0;
-
-        // the plot should start at the first time the aspect is played
-        let mut aspect_found = false;
-
-        for entry in entries.iter() {
-            all_plays += 1.0;
-
-            if aspect.is_entry(entry) {
-                aspect_found = true;
-                aspect_plays += 1.0;
-            }
-            if aspect_found {
-                times.push(format_date(&entry.timestamp));
-                // *100 so that the percentage is easier to read...
-                plays.push(100.0 * (aspect_plays / all_plays));
-            }
-        }
+    pub fn to_all<Asp: Music>(
+        entries: &SongEntries,
+        aspect: &Asp,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::to_all(entries, aspect),
+            granularity,
+            smoothing,
+        );
+        let (times, plays) = unzip(series, format_date);
 
         let title = format!("{aspect} | relative to all plays");
         let trace = Scatter::new(times, plays).name(title);
@@ -100,76 +344,180 @@ pub mod relative {
     /// Creates a plot of the amount of plays of an [`Album`] or [`Song`]
     /// relative to total plays of the corresponding [`Artist`]
     ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
     /// Creates an empty trace if `aspect` is not in `entries`
     #[must_use]
-    pub fn to_artist<Asp: AsRef<Album> + Music>(entries: &SongEntries, aspect: &Asp) -> TraceType {
-        let artist = &aspect.as_ref().artist;
+    pub fn to_artist<Asp: AsRef<Album> + Music>(
+        entries: &SongEntries,
+        aspect: &Asp,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::to_artist(entries, aspect),
+            granularity,
+            smoothing,
+        );
+        let (times, plays) = unzip(series, format_date);
 
-        let mut times = Vec::<String>::new();
-        // percentages relative to the sum of respective artist plays
-        let mut plays = Vec::<f64>::new();
-
-        let mut aspect_plays = 0.0;
-        let mut artist_plays = 0.0;
+        let title = format!("{aspect} | relative to the artist");
+        let trace = Scatter::new(times, plays).name(title);
 
-        // the plot should start at the first time the aspect is played
-        let mut aspect_found = false;
+        TraceType::Relative(trace)
+    }
 
-        for entry in entries.iter().filter(|entry| artist.is_entry(entry)) {
-            artist_plays += 1.0;
+    /// Creates a plot of the amount of plays of a [`Song`]
+    /// relative to total plays of the corresponding [`Album`]
+    ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
+    /// Creates an empty trace if `song` is not in `entries`
+    #[must_use]
+    pub fn to_album(
+        entries: &SongEntries,
+        song: &Song,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::to_album(entries, song),
+            granularity,
+            smoothing,
+        );
+        let (times, plays) = unzip(series, format_date);
 
-            if aspect.is_entry(entry) {
-                aspect_found = true;
-                aspect_plays += 1.0;
-            }
+        let title = format!("{song} | relative to the album");
+        let trace = Scatter::new(times, plays).name(title);
 
-            if aspect_found {
-                times.push(format_date(&entry.timestamp));
-                // *100 so that the percentage is easier to read...
-                plays.push(100.0 * (aspect_plays / artist_plays));
-            }
-        }
+        TraceType::Relative(trace)
+    }
 
-        let title = format!("{aspect} | relative to the artist");
+    /// Creates a trace of the amount of plays of an [`Music`]
+    /// relative to the plays of a custom `group` of aspects,
+    /// e.g. a song relative to all power metal artists
+    ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
+    /// Creates an empty trace if `aspect` is not in `entries`
+    #[must_use]
+    pub fn to_aspects<Asp: Music, Grp: Music>(
+        entries: &SongEntries,
+        aspect: &Asp,
+        group: &[Grp],
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::to_aspects(entries, aspect, group),
+            granularity,
+            smoothing,
+        );
+        let (times, plays) = unzip(series, format_date);
+
+        let group_name = group.iter().join(", ");
+        let title = format!("{aspect} | relative to {group_name}");
         let trace = Scatter::new(times, plays).name(title);
 
         TraceType::Relative(trace)
     }
 
-    /// Creates a plot of the amount of plays of a [`Song`]
-    /// relative to total plays of the corresponding [`Album`]
+    /// Creates a trace of the minutes listened to an [`Music`]
+    /// relative to the minutes listened to anything
     ///
-    /// Creates an empty trace if `song` is not in `entries`
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
+    /// Creates an empty trace if `aspect` is not in `entries`
     #[must_use]
-    pub fn to_album(entries: &SongEntries, song: &Song) -> TraceType {
-        let album = &song.album;
+    pub fn duration_to_all<Asp: Music>(
+        entries: &SongEntries,
+        aspect: &Asp,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::duration_to_all(entries, aspect),
+            granularity,
+            smoothing,
+        );
+        let (times, minutes) = unzip(series, format_date);
+
+        let title = format!("{aspect} | minutes relative to all minutes");
+        let trace = Scatter::new(times, minutes).name(title);
 
-        let mut times = Vec::<String>::new();
-        // percentages relative to the sum of respective album plays
-        let mut plays = Vec::<f64>::new();
+        TraceType::Relative(trace)
+    }
 
-        let mut song_plays = 0.0;
-        let mut album_plays = 0.0;
+    /// Creates a trace of the minutes listened to an [`Album`] or [`Song`]
+    /// relative to the minutes listened to the corresponding [`Artist`]
+    ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
+    /// Creates an empty trace if `aspect` is not in `entries`
+    #[must_use]
+    pub fn duration_to_artist<Asp: AsRef<Album> + Music>(
+        entries: &SongEntries,
+        aspect: &Asp,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::duration_to_artist(entries, aspect),
+            granularity,
+            smoothing,
+        );
+        let (times, minutes) = unzip(series, format_date);
+
+        let title = format!("{aspect} | minutes relative to the artist");
+        let trace = Scatter::new(times, minutes).name(title);
 
-        // the plot should start at the first time the aspect is played
-        let mut song_found = false;
+        TraceType::Relative(trace)
+    }
 
-        for entry in entries.iter().filter(|entry| album.is_entry(entry)) {
-            album_plays += 1.0;
+    /// Creates a trace of the minutes listened to a [`Song`]
+    /// relative to the minutes listened to the corresponding [`Album`]
+    ///
+    /// With one point per play, or per [`Bin`] if `granularity` is set,
+    /// smoothed if `smoothing` is set
+    ///
+    /// Creates an empty trace if `song` is not in `entries`
+    #[must_use]
+    pub fn duration_to_album(
+        entries: &SongEntries,
+        song: &Song,
+        granularity: Option<Bin>,
+        smoothing: Option<Smoothing>,
+    ) -> TraceType {
+        let series = shape(
+            series::relative::duration_to_album(entries, song),
+            granularity,
+            smoothing,
+        );
+        let (times, minutes) = unzip(series, format_date);
+
+        let title = format!("{song} | minutes relative to the album");
+        let trace = Scatter::new(times, minutes).name(title);
 
-            if song.is_entry(entry) {
-                song_found = true;
-                song_plays += 1.0;
-            }
+        TraceType::Relative(trace)
+    }
 
-            if song_found {
-                times.push(format_date(&entry.timestamp));
-                // *100 so that the percentage is easier to read...
-                plays.push(100.0 * (song_plays / album_plays));
-            }
-        }
+    /// Creates a trace of the share of plays of an [`Music`]
+    /// among all plays within each [`Bin`]
+    ///
+    /// See [`series::relative::to_period`]
+    #[must_use]
+    pub fn to_period<Asp: Music>(entries: &SongEntries, aspect: &Asp, bin: Bin) -> TraceType {
+        let (times, plays) = unzip(
+            series::relative::to_period(entries, aspect, bin),
+            format_day,
+        );
 
-        let title = format!("{song} | relative to the album");
+        let title = format!("{aspect} | relative to each {bin}");
         let trace = Scatter::new(times, plays).name(title);
 
         TraceType::Relative(trace)
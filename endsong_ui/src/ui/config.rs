@@ -0,0 +1,196 @@
+//! Module containing the user configuration of the shell
+//!
+//! It's loaded from [`CONFIG_PATH`] if it exists, e.g.
+//! ```toml
+//! [theme]
+//! monochrome = false
+//! command = "green"
+//! main = "cyan"
+//! secondary = "red"
+//! title = "light_green"
+//! alias = "pink"
+//!
+//! [plot]
+//! theme = "dark"
+//! font = "Arial"
+//! colorway = ["#1f77b4", "red"]
+//! csv = true
+//! log_scale = true
+//!
+//! [[plot.events]]
+//! date = "2020-05-01"
+//! label = "moved to Berlin"
+//!
+//! [print]
+//! format = "markdown"
+//! show_time = true
+//! share = "parent"
+//! bars = true
+//! sparkline = true
+//! max_width = 40
+//!
+//! [summarize]
+//! reports_dir = "reports"
+//! ```
+//! All fields are optional. Colors are disabled regardless of the config
+//! if the `NO_COLOR` environment variable is set
+//! or if the output isn't a terminal (see <https://no-color.org>).
+//! This doesn't affect the `[plot]` section.
+//! Without a `format`, the output is plain if it isn't a terminal.
+//! Besides `"pretty"`, `"plain"` and `"markdown"`, `"json"` prints
+//! top lists and aspects as JSON for other programs.
+//! `show_time` adds the listening time next to the playcount
+//! in top lists and aspects and `share` the percentage of `"all"` plays
+//! or of the plays of the `"parent"` aspect (e.g. the artist of an album).
+//! `bars` draws a bar proportional to the playcount next to each entry of top lists
+//! and `sparkline` the monthly plays below the name of an artist, album or song.
+//! Names wider than `max_width` columns are truncated with an ellipsis in aligned lists.
+//! With a `reports_dir`, summaries are written as markdown files to that directory
+//! instead of being printed.
+//! `log_scale` in `[plot]` uses a logarithmic y-axis for plots of absolute plays or minutes over time
+//! (not for shares, daily averages or the month axis of `plot years`)
+
+use std::io::IsTerminal;
+
+use serde::Deserialize;
+
+use super::Color;
+use crate::plot;
+use crate::print;
+use crate::summarize;
+
+/// Path of the config file
+pub const CONFIG_PATH: &str = ".rep_config.toml";
+
+/// User configuration of the shell
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Colors used by the shell
+    pub theme: Theme,
+    /// Style of the plots
+    pub plot: plot::Style,
+    /// Settings of the printed output
+    pub print: print::Settings,
+    /// Settings of the summaries
+    pub summarize: summarize::Settings,
+}
+impl Config {
+    /// Loads the config from [`CONFIG_PATH`]
+    ///
+    /// Falls back to the default config if the file doesn't exist
+    /// or is invalid (the latter with an error message)
+    pub fn load() -> Self {
+        let mut config = match std::fs::read_to_string(CONFIG_PATH) {
+            Ok(contents) => toml::from_str(&contents).unwrap_or_else(|e| {
+                eprintln!("Failed to parse config file at {CONFIG_PATH}: {e}");
+                Config::default()
+            }),
+            Err(_) => Config::default(),
+        };
+
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty());
+        if no_color || !std::io::stdout().is_terminal() {
+            config.theme.monochrome = true;
+        }
+
+        config
+    }
+}
+
+/// Colors used by the shell
+///
+/// Each field is the color of one kind of text
+#[derive(Deserialize, Debug, Copy, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Disables all colors if `true`
+    pub monochrome: bool,
+    /// Color of the top-level command prompt
+    pub command: Color,
+    /// Color of the main argument prompt
+    pub main: Color,
+    /// Color of the secondary argument prompt and of commands in `help`
+    pub secondary: Color,
+    /// Color of the section titles in `help`
+    pub title: Color,
+    /// Color of the command aliases in `help`
+    pub alias: Color,
+}
+impl Theme {
+    /// Returns `text` in the given `color`
+    ///
+    /// Returns `text` unchanged if the theme is [monochrome][Theme::monochrome]
+    pub fn paint(self, color: Color, text: &str) -> String {
+        if self.monochrome {
+            return text.to_string();
+        }
+        format!("{color}{text}{}", Color::Reset)
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            monochrome: false,
+            command: Color::Green,
+            main: Color::Cyan,
+            secondary: Color::Red,
+            title: Color::LightGreen,
+            alias: Color::Pink,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config: Config = toml::from_str("[theme]\nmain = \"yellow\"").unwrap();
+        assert!(matches!(config.theme.main, Color::Yellow));
+        // unspecified fields fall back to the default
+        assert!(matches!(config.theme.command, Color::Green));
+
+        let config: Config =
+            toml::from_str("[plot]\ntheme = \"dark\"\ncolorway = [\"red\"]").unwrap();
+        assert!(matches!(config.plot.theme, plot::Theme::Dark));
+        assert_eq!(config.plot.colorway, vec!["red"]);
+        assert!(config.plot.font.is_none());
+        assert!(!config.plot.log_scale);
+        let config: Config = toml::from_str("[plot]\nlog_scale = true").unwrap();
+        assert!(config.plot.log_scale);
+
+        let config: Config =
+            toml::from_str("[[plot.events]]\ndate = \"2020-05-01\"\nlabel = \"moved\"").unwrap();
+        assert_eq!(config.plot.events[0].date.to_string(), "2020-05-01");
+        assert!(
+            toml::from_str::<Config>("[[plot.events]]\ndate = \"May\"\nlabel = \"moved\"").is_err()
+        );
+
+        let config: Config = toml::from_str("[print]\nformat = \"markdown\"").unwrap();
+        assert_eq!(config.print.format, Some(print::Format::Markdown));
+        let config: Config = toml::from_str("[print]\nformat = \"json\"").unwrap();
+        assert_eq!(config.print.format, Some(print::Format::Json));
+        assert!(!config.print.show_time);
+        let config: Config = toml::from_str("[print]\nshow_time = true").unwrap();
+        assert!(config.print.show_time);
+        let config: Config = toml::from_str("[print]\nshare = \"parent\"").unwrap();
+        assert_eq!(config.print.share, Some(print::Share::Parent));
+        assert!(!config.print.bars);
+        assert!(!config.print.sparkline);
+        let config: Config = toml::from_str("[print]\nmax_width = 40").unwrap();
+        assert_eq!(config.print.max_width, Some(40));
+        assert!(toml::from_str::<Config>("[print]\nformat = \"html\"").is_err());
+
+        assert!(config.summarize.reports_dir.is_none());
+        let config: Config = toml::from_str("[summarize]\nreports_dir = \"reports\"").unwrap();
+        assert_eq!(
+            config.summarize.reports_dir,
+            Some(std::path::PathBuf::from("reports"))
+        );
+
+        assert!(toml::from_str::<Config>("[theme]\nmain = \"reset\"").is_err());
+        assert!(toml::from_str::<Config>("[theme]\nmain = \"not_a_color\"").is_err());
+    }
+}
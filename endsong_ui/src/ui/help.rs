@@ -1,6 +1,7 @@
 //! Module containing stuff for the `help` command
 
-use super::Color;
+use super::config::Theme;
+use super::{PROMPT_COMMAND, PROMPT_MAIN, PROMPT_SECONDARY};
 
 /// Represents a command
 ///
@@ -8,25 +9,573 @@ use super::Color;
 #[derive(Copy, Clone, Debug)]
 struct Command(&'static str, &'static str, &'static str);
 
+/// Represents a prompt of a command, used by [`help_command()`]
+///
+/// Fields are question, accepted values, example answer, prompt
+#[derive(Copy, Clone, Debug)]
+struct Prompt(&'static str, &'static str, &'static str, &'static str);
+
+/// Prompt for an artist name
+const ARTIST: Prompt = Prompt(
+    "Artist name?",
+    "any artist in the dataset (case-insensitive)",
+    "Sabaton",
+    PROMPT_MAIN,
+);
+
+/// Prompt for an album name
+const ALBUM: Prompt = Prompt(
+    "Album name?",
+    "any album of that artist (case-insensitive)",
+    "Coat of Arms",
+    PROMPT_MAIN,
+);
+
+/// Prompt for a song name from an album
+const SONG: Prompt = Prompt(
+    "Song name?",
+    "any song of that album (case-insensitive)",
+    "The Final Solution",
+    PROMPT_MAIN,
+);
+
+/// Prompt for a song name from an artist
+const SONG_OF_ARTIST: Prompt = Prompt(
+    "Song name?",
+    "any song of that artist (case-insensitive)",
+    "The Final Solution",
+    PROMPT_MAIN,
+);
+
+/// Prompt for the start of a date range
+const START_DATE: Prompt = Prompt(
+    "Start date? YYYY-MM-DD or 'start'",
+    "a date like 2020-01-01 or 'start' for the first entry",
+    "2020-01-01",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for the end of a date range
+const END_DATE: Prompt = Prompt(
+    "End date? YYYY-MM-DD or 'now'",
+    "a date like 2020-12-31 or 'now' (has to be after the start date)",
+    "now",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for whether to sum up songs from different albums
+const SUM_SONGS: Prompt = Prompt(
+    "Do you want to sum songs from different albums? (y/n)",
+    "y, yes, n, no",
+    "y",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for ranking top lists
+const RANK: Prompt = Prompt(
+    "Rank by plays or listening time, or show both?",
+    "plays, time, both",
+    "time",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for what to plot
+const PLOT_ASPECT: Prompt = Prompt(
+    "What do you want to plot? artist, album or song?",
+    "artist, album, song - followed by the respective name prompts",
+    "album",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for cumulative or binned traces
+const BIN: Prompt = Prompt(
+    "Cumulative or per day, week or month? (leave empty for cumulative)",
+    "cumulative, day, week, month or nothing - followed by the plays or minutes prompt, cumulative also by the granularity prompt",
+    "week",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for what to count in absolute traces
+const BIN_UNIT: Prompt = Prompt(
+    "Plays or minutes? (leave empty for plays)",
+    "plays, minutes or nothing",
+    "plays",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for the amount of points in cumulative traces
+const GRANULARITY: Prompt = Prompt(
+    "One point per play or per day, week or month? (leave empty for per play)",
+    "play, day, week, month or nothing - the latter three make plots of aspects with many plays way smaller",
+    "week",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for relative traces to all plays so far or to each period
+const PERIOD: Prompt = Prompt(
+    "Share of all plays so far or of the plays within each day, week or month?",
+    "cumulative, day, week, month - the former is followed by the granularity and smoothing prompts, the latter three skip the \"Relative to\" prompt",
+    "cumulative",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for smoothing cumulative relative traces
+const SMOOTHING: Prompt = Prompt(
+    "Smooth over how many plays? (e.g. 50, leave empty for none)",
+    "any whole number or nothing",
+    "50",
+    PROMPT_SECONDARY,
+);
+
+/// Prompt for the rolling average overlay of absolute traces
+const AVERAGE: Prompt = Prompt(
+    "Overlay a rolling average of daily plays (or minutes) over how many days? (e.g. 30, leave empty for none)",
+    "any whole number or nothing",
+    "30",
+    PROMPT_SECONDARY,
+);
+
 /// Used by [`match_input()`][`super::match_input()`] for `help` command
 ///
 /// Prints the available commands to the [`std::io::stdout`]
-pub fn help() {
+pub fn help(theme: Theme) {
     // each entry: ["command", "alias", "description"]
 
     // META COMMANDS
-    print("meta", meta_commands());
+    print(theme, "meta", meta_commands());
 
     // PRINT COMMANDS
-    print("print", print_commands());
-    print("print top", print_top_commands());
+    print(theme, "print", print_commands());
+    print(theme, "print top", print_top_commands());
+
+    // OTHER COMMANDS
+    print(theme, "other", other_commands());
+
+    // SUMMARIZE COMMANDS
+    print(theme, "summarize", summarize_commands());
+
+    // COMPARE COMMANDS
+    print(theme, "compare", compare_commands());
 
     // GRAPH COMMANDS
-    print("graph/plot", plot_commands());
+    print(theme, "graph/plot", plot_commands());
+}
+
+/// Used by [`match_input()`][`super::match_input()`] for `help <command>` command
+///
+/// Prints the description, the prompts with their accepted values
+/// and an example transcript of the command with the given name or alias
+pub fn help_command(theme: Theme, name: &str) {
+    let Some(&Command(command, alias, description)) = meta_commands()
+        .iter()
+        .chain(print_commands())
+        .chain(print_top_commands())
+        .chain(other_commands())
+        .chain(summarize_commands())
+        .chain(compare_commands())
+        .chain(plot_commands())
+        .find(|Command(command, alias, _)| name == *command || name == *alias)
+    else {
+        println!(
+            "Command not found! Type {} to print available commands",
+            theme.paint(theme.secondary, "help")
+        );
+        return;
+    };
+
+    println!(
+        "{}",
+        theme.paint(theme.title, &format!("=== {command} ==="))
+    );
+    println!("{description}");
+    println!("{}", theme.paint(theme.alias, &format!("alias: {alias}")));
+
+    let prompts = prompts(command);
+    if prompts.is_empty() {
+        println!("This command doesn't ask for anything.");
+        return;
+    }
+
+    println!();
+    println!("{}", theme.paint(theme.title, "prompts:"));
+    for Prompt(question, accepted, _, _) in prompts {
+        println!("{question}");
+        println!("    accepts: {accepted}");
+    }
+
+    println!();
+    println!("{}", theme.paint(theme.title, "example:"));
+    println!("{}{command}", theme.paint(theme.command, PROMPT_COMMAND));
+    for Prompt(question, _, example, prompt) in prompts {
+        println!("{question}");
+        let color = if *prompt == PROMPT_MAIN {
+            theme.main
+        } else {
+            theme.secondary
+        };
+        println!("{}{example}", theme.paint(color, prompt));
+    }
+}
+
+/// Returns the prompts of a command in the order they're asked
+///
+/// `command` - full name of the command, see [`Command`]
+#[allow(clippy::too_many_lines)]
+fn prompts(command: &str) -> &'static [Prompt] {
+    match command {
+        "print time date" | "print platforms date" | "summarize card" | "summarize range" => {
+            &[START_DATE, END_DATE]
+        }
+        "print max time" => &[
+            Prompt(
+                "Input time period in days or weeks?",
+                "days, weeks",
+                "weeks",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "What's the time period? Whole numbers only",
+                "any whole number",
+                "4",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "print clock" => &[
+            Prompt(
+                "Clock of all plays or of an artist, album or song?",
+                "all, artist, album, song - the latter three are followed by the respective name prompts",
+                "artist",
+                PROMPT_SECONDARY,
+            ),
+            ARTIST,
+        ],
+        "print milestone" => &[
+            ARTIST,
+            Prompt(
+                "Which play? E.g. 1000 for the 1000th play",
+                "any whole number",
+                "1000",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "print one hit wonders" => &[
+            Prompt(
+                "More than what % of an artist's plays? (e.g. 50, leave empty for 50)",
+                "any whole number from 0 to 100 or nothing",
+                "50",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Minimum plays of an artist? (e.g. 10, leave empty for 10)",
+                "any whole number or nothing",
+                "10",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "print artist" | "plot artist albums" | "plot gaps" | "summarize artist" => &[ARTIST],
+        "summarize year" => &[Prompt(
+            "Which year?",
+            "any year in the dataset",
+            "2021",
+            PROMPT_SECONDARY,
+        )],
+        "summarize day" => &[Prompt(
+            "Which day? YYYY-MM-DD or 'now'",
+            "a date like 2021-03-27 or 'now' for today",
+            "2021-03-27",
+            PROMPT_SECONDARY,
+        )],
+        "summarize top days" => &[
+            Prompt(
+                "Top days of all plays or of an artist?",
+                "all, artist - the latter is followed by the artist prompt",
+                "all",
+                PROMPT_SECONDARY,
+            ),
+            ARTIST,
+            Prompt("How many days?", "any whole number", "10", PROMPT_SECONDARY),
+        ],
+        "summarize compare" => &[
+            Prompt(
+                "First year?",
+                "any year in the dataset",
+                "2020",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Second year?",
+                "any year in the dataset",
+                "2021",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "print album" => &[ARTIST, ALBUM],
+        "print song" => &[ARTIST, ALBUM, SONG],
+        "print songs" | "plot song versions" => &[ARTIST, SONG_OF_ARTIST],
+        "print artist date" => &[ARTIST, START_DATE, END_DATE],
+        "print album date" => &[ARTIST, ALBUM, START_DATE, END_DATE],
+        "print song date" => &[ARTIST, ALBUM, SONG, START_DATE, END_DATE],
+        "print songs date" => &[ARTIST, SONG_OF_ARTIST, START_DATE, END_DATE],
+        "print top artists" => &[
+            Prompt("How many Top artists?", "any whole number", "10", PROMPT_MAIN),
+            RANK,
+        ],
+        "print top albums" => &[
+            Prompt("How many Top albums?", "any whole number", "10", PROMPT_MAIN),
+            RANK,
+        ],
+        "print top songs" => &[
+            Prompt("How many Top songs?", "any whole number", "10", PROMPT_MAIN),
+            SUM_SONGS,
+            RANK,
+        ],
+        "print top artists date" => &[
+            START_DATE,
+            END_DATE,
+            Prompt("How many Top artists?", "any whole number", "10", PROMPT_MAIN),
+        ],
+        "print top albums date" => &[
+            START_DATE,
+            END_DATE,
+            Prompt("How many Top albums?", "any whole number", "10", PROMPT_MAIN),
+        ],
+        "print top songs date" => &[
+            START_DATE,
+            END_DATE,
+            Prompt("How many Top songs?", "any whole number", "10", PROMPT_MAIN),
+            SUM_SONGS,
+        ],
+        "print top songs from artist date" => &[
+            ARTIST,
+            START_DATE,
+            END_DATE,
+            Prompt("How many Top songs?", "any whole number", "10", PROMPT_MAIN),
+            SUM_SONGS,
+        ],
+        "print top changes" => &[
+            Prompt(
+                "Top artists, albums or songs?",
+                "artist/s, album/s, song/s",
+                "artists",
+                PROMPT_MAIN,
+            ),
+            START_DATE,
+            END_DATE,
+            START_DATE,
+            END_DATE,
+            Prompt("How many Top artists?", "any whole number", "10", PROMPT_MAIN),
+            Prompt(
+                "Do you want to sum songs from different albums? (y/n)",
+                "y, yes, n, no - only asked for songs",
+                "y",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "random" => &[
+            Prompt(
+                "Pick from all songs, songs of an artist or songs played within a date range?",
+                "all, artist, date - the latter two are followed by the artist or date prompts",
+                "artist",
+                PROMPT_SECONDARY,
+            ),
+            ARTIST,
+            Prompt(
+                "Should songs you've played more often be more likely? (y/n)",
+                "y, yes, n, no",
+                "y",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "compare artists" => &[
+            Prompt(
+                "Artist name? (leave empty to start comparing)",
+                "any artist in the dataset, asked until the input is empty",
+                "Sabaton",
+                PROMPT_MAIN,
+            ),
+            Prompt(
+                "Artist name? (leave empty to start comparing)",
+                "another artist or nothing to start comparing",
+                "Powerwolf",
+                PROMPT_MAIN,
+            ),
+            Prompt(
+                "Artist name? (leave empty to start comparing)",
+                "another artist or nothing to start comparing",
+                "",
+                PROMPT_MAIN,
+            ),
+        ],
+        "plot" => &[BIN, BIN_UNIT, GRANULARITY, AVERAGE, PLOT_ASPECT, ARTIST, ALBUM],
+        "plot rel" => &[
+            PERIOD,
+            GRANULARITY,
+            SMOOTHING,
+            PLOT_ASPECT,
+            ARTIST,
+            ALBUM,
+            Prompt(
+                "Relative to all, artist or a group of artists?",
+                "all, group (for albums also artist, for songs also artist and album)",
+                "group",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Comma-separated list of the artists in the group?",
+                "any artist names",
+                "Sabaton, Powerwolf, Orden Ogan",
+                PROMPT_MAIN,
+            ),
+        ],
+        "plot compare" => &[
+            BIN,
+            BIN_UNIT,
+            GRANULARITY,
+            AVERAGE,
+            Prompt(
+                "Trace #1: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+                "artist, album, song - followed by the prompts of `plot`; a list of artist names; done - asked again until \"done\"",
+                "Sabaton, Powerwolf, Amaranthe",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Trace #4: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+                "see above",
+                "done",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "plot compare rel" => &[
+            PERIOD,
+            GRANULARITY,
+            SMOOTHING,
+            Prompt(
+                "Trace #1: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+                "artist, album, song - followed by the prompts of `plot rel`; a list of artist names; done - asked again until \"done\"",
+                "artist",
+                PROMPT_SECONDARY,
+            ),
+            ARTIST,
+            Prompt(
+                "Trace #2: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+                "see above",
+                "Powerwolf, Amaranthe",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Trace #4: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+                "see above",
+                "done",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "plot bar" => &[
+            Prompt(
+                "What do you want to plot? Top artists, albums or songs?",
+                "artist/s, album/s, song/s",
+                "artists",
+                PROMPT_MAIN,
+            ),
+            Prompt(
+                "How many top artists to plot?",
+                "any whole number",
+                "10",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Plays or listening time?",
+                "plays, time",
+                "time",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "plot share" => &[
+            Prompt(
+                "How many top artists to show? The rest will be summed up as \"other\"",
+                "any whole number",
+                "10",
+                PROMPT_SECONDARY,
+            ),
+            Prompt(
+                "Of all plays or within a date range?",
+                "all, date - the latter is followed by the date prompts",
+                "date",
+                PROMPT_SECONDARY,
+            ),
+            START_DATE,
+            END_DATE,
+        ],
+        "plot sessions" | "summarize sessions" => &[Prompt(
+            "After how many minutes of silence should a session end? (e.g. 30)",
+            "any whole number",
+            "30",
+            PROMPT_SECONDARY,
+        )],
+        "plot rank" => &[
+            ARTIST,
+            Prompt(
+                "Rank by plays so far or by plays in each month?",
+                "lifetime, month",
+                "month",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "plot library" => &[Prompt(
+            "Library growth of artists, albums or songs?",
+            "artist/s, album/s, song/s",
+            "songs",
+            PROMPT_MAIN,
+        )],
+        "plot years" => &[
+            Prompt(
+                "Plays per month or cumulative over each year?",
+                "month, cumulative",
+                "cumulative",
+                PROMPT_SECONDARY,
+            ),
+            PLOT_ASPECT,
+            ARTIST,
+            ALBUM,
+        ],
+        "plot clock" => &[
+            Prompt(
+                "Clock of all plays or of an artist?",
+                "all, artist - the latter is followed by the artist prompt",
+                "artist",
+                PROMPT_SECONDARY,
+            ),
+            ARTIST,
+            Prompt(
+                "Plays or minutes?",
+                "plays, minutes",
+                "minutes",
+                PROMPT_SECONDARY,
+            ),
+        ],
+        "plot top" => &[
+            Prompt(
+                "What do you want to plot? Top artists, albums or songs?",
+                "artist/s, album/s, song/s",
+                "artists",
+                PROMPT_MAIN,
+            ),
+            Prompt(
+                "How many top artists to plot? (recommended: ~5)",
+                "any whole number",
+                "5",
+                PROMPT_SECONDARY,
+            ),
+            BIN,
+            BIN_UNIT,
+            GRANULARITY,
+            AVERAGE,
+        ],
+        _ => &[],
+    }
 }
 
 /// Prints the commands
-fn print(title: &str, commands: &[Command]) {
+fn print(theme: Theme, title: &str, commands: &[Command]) {
     /// Length of command name
     const COMMAND_LENGTH: usize = 20;
 
@@ -53,20 +602,21 @@ fn print(title: &str, commands: &[Command]) {
     // see https://doc.rust-lang.org/std/fmt/#fillalignment
     let centered_title = format!("{phrase:=^FULL_LENGTH$}");
 
-    let reset = Color::Reset;
-    let (light_green, red, pink) = (Color::LightGreen, Color::Red, Color::Pink);
-
-    println!("{light_green}{centered_title}{reset}");
+    println!("{}", theme.paint(theme.title, &centered_title));
 
     for Command(command, alias, description) in commands {
         let description_lines = textwrap::wrap(description, DESCRIPTION_LENGTH);
         let description_first = description_lines.first().unwrap();
 
-        println!("{red}{command:>COMMAND_LENGTH$}{reset}{ARROW}{description_first}");
+        let command = theme.paint(theme.secondary, &format!("{command:>COMMAND_LENGTH$}"));
+        println!("{command}{ARROW}{description_first}");
         for line in description_lines.iter().skip(1) {
-            println!("{INDENT}{line}",);
+            println!("{INDENT}{line}");
         }
-        println!("{pink}{INDENT}{ALIAS}{alias}{reset}");
+        println!(
+            "{}",
+            theme.paint(theme.alias, &format!("{INDENT}{ALIAS}{alias}"))
+        );
     }
 }
 
@@ -74,6 +624,11 @@ fn print(title: &str, commands: &[Command]) {
 const fn meta_commands() -> &'static [Command] {
     &[
         Command("help", "h", "prints this command list"),
+        Command(
+            "help <command>",
+            "h <command>",
+            "prints the prompts of a command, the values they accept and an example",
+        ),
         Command("exit", "quit", "exits the program"),
     ]
 }
@@ -92,6 +647,36 @@ const fn print_commands() -> &'static [Command] {
             "pmt",
             "calculates the dates during which you listened the most to music for a given duration",
         ),
+        Command(
+            "print clock",
+            "pc",
+            "prints the plays for each hour of the day of all plays or of an artist, album or song",
+        ),
+        Command(
+            "print milestone",
+            "pms",
+            "prints the date of the nth play of an artist and the plays left until its next milestones",
+        ),
+        Command(
+            "print one hit wonders",
+            "pohw",
+            "prints the artists where a single song accounts for most of their plays",
+        ),
+        Command(
+            "print stats",
+            "pst",
+            "prints an overview of the dataset (entries, unique aspects, date span, listening time, biggest day, memory usage)",
+        ),
+        Command(
+            "print platforms",
+            "pplat",
+            "prints how many plays and minutes came from each device class",
+        ),
+        Command(
+            "print platforms date",
+            "pplatd",
+            "prints how many plays and minutes came from each device class within a date range",
+        ),
         Command(
             "print artist",
             "part",
@@ -134,39 +719,195 @@ const fn print_commands() -> &'static [Command] {
 /// Returns print top commands
 const fn print_top_commands() -> &'static [Command] {
     &[
-        Command("print top artists", "ptarts", "prints top n artists"),
-        Command("print top albums", "ptalbs", "prints top n albums"),
-        Command("print top songs", "ptsons", "prints top n songs"),
+        Command(
+            "print top artists",
+            "ptarts",
+            "prints top n artists by plays or listening time",
+        ),
+        Command(
+            "print top albums",
+            "ptalbs",
+            "prints top n albums by plays or listening time",
+        ),
+        Command(
+            "print top songs",
+            "ptsons",
+            "prints top n songs by plays or listening time",
+        ),
+        Command(
+            "print top artists date",
+            "ptartd",
+            "prints top n artists within a date range",
+        ),
+        Command(
+            "print top albums date",
+            "ptalbd",
+            "prints top n albums within a date range",
+        ),
+        Command(
+            "print top songs date",
+            "ptsond",
+            "prints top n songs within a date range",
+        ),
+        Command(
+            "print top songs from artist date",
+            "ptsonsad",
+            "prints top n songs from the given artist within a date range",
+        ),
+        Command(
+            "print top changes",
+            "ptc",
+            "prints how the top n artists, albums or songs changed between two date ranges",
+        ),
+    ]
+}
+
+/// Returns other commands
+const fn other_commands() -> &'static [Command] {
+    &[Command(
+        "random",
+        "r",
+        "picks a random song (optionally of an artist, within a date range or weighted by plays) and prints its stats",
+    )]
+}
+
+/// Returns summarize commands
+const fn summarize_commands() -> &'static [Command] {
+    &[
+        Command(
+        "summarize year",
+        "sy",
+        "prints a summary of a year with top lists, the top artist of each month and comparisons to the previous year; the year can also be given directly like `summarize year 2021`",
+        ),
+        Command(
+            "summarize all",
+            "sa",
+            "prints a summary of the whole dataset with totals, top 10s, diversity, busiest periods and new artists per year",
+        ),
+        Command(
+            "summarize artist",
+            "sart",
+            "prints a summary of an artist with totals, rank, discovery, longest streak and break, busiest day, listening clock and top albums and songs",
+        ),
+        Command(
+            "summarize compare",
+            "sc",
+            "prints a joint summary of two years with the difference in listening time, the biggest risers and fallers, how many discoveries were kept and the overlap of the top 10s; the years can also be given directly like `summarize compare 2020 2021`",
+        ),
+        Command(
+            "summarize card",
+            "scard",
+            "creates a shareable PNG card with the listening time and top artists and songs of a period in the reports directory or plots/",
+        ),
+        Command(
+            "summarize sessions",
+            "sse",
+            "prints a summary of your listening sessions with their average and median length, the longest session, the most common start hour and how many songs are played per session",
+        ),
+        Command(
+            "summarize day",
+            "sd",
+            "prints a summary of a single day with its top artist and album, a timeline of its sessions and the minutes per hour; the date can also be given directly like `summarize day 2021-03-27`",
+        ),
+        Command(
+            "summarize top days",
+            "std",
+            "prints the days with the most listening time of all plays or of an artist and what dominated each of them",
+        ),
+        Command(
+            "summarize range",
+            "sr",
+            "prints a summary of any period (e.g. a semester or a job) like `summarize year` compared to the period of the same length before it; the dates can also be given directly like `summarize range 2020-10-01 2021-03-31`",
+        ),
     ]
 }
 
+/// Returns compare commands
+const fn compare_commands() -> &'static [Command] {
+    &[Command(
+        "compare artists",
+        "ca",
+        "prints a table comparing the plays, minutes, first listen, top song and peak month of two or more artists",
+    )]
+}
+
 /// Returns graph commands
 const fn plot_commands() -> &'static [Command] {
     &[
         Command(
             "plot",
             "g",
-            "creates a plot of the absolute amount of plays of the given aspect and opens it in the web browser",
+            "creates a plot of the amount of plays (cumulative or per day/week/month) of the given aspect and opens it in the web browser",
         ),
         Command(
             "plot rel",
             "gr",
-            "creates a plot of the amount of plays of the given aspect relative to all, the artist or album and opens it in the web browser",
+            "creates a plot of the amount of plays of the given aspect relative to all, the artist or album, or its share of each day, week or month and opens it in the web browser",
         ),
         Command(
             "plot compare",
             "gc",
-            "creates a plot of two traces (see `plot`) and opens it in the web browser",
+            "creates a plot of two or more traces (see `plot`) and opens it in the web browser",
         ),
         Command(
             "plot compare rel",
             "gcr",
-            "creates a plot of two relative traces (see `plot rel`) and opens it in the web browser",
+            "creates a plot of two or more relative traces (see `plot rel`) and opens it in the web browser",
         ),
         Command(
             "plot top",
             "gt",
-            "creates a plot of the absolute traces of top n aspects and opens it in the web browser",
+            "creates a plot of the traces (see `plot`) of top n aspects and opens it in the web browser",
+        ),
+        Command(
+            "plot bar",
+            "gb",
+            "creates a horizontal bar chart of the top n artists, albums or songs by plays or minutes and opens it in the web browser",
+        ),
+        Command(
+            "plot share",
+            "gs",
+            "creates a chart of the share of listening time of the top n artists (the rest summed up as \"other\") of all plays or within a date range and opens it in the web browser",
+        ),
+        Command(
+            "plot sessions",
+            "gse",
+            "creates a histogram of the lengths of listening sessions and a chart of sessions per month and opens it in the web browser",
+        ),
+        Command(
+            "plot song versions",
+            "gsv",
+            "creates a plot of the absolute traces of every album version of a song and of all of them summed up and opens it in the web browser",
+        ),
+        Command(
+            "plot rank",
+            "grk",
+            "creates a plot of the rank of an artist among all artists at the end of each month (by plays so far or in that month) and opens it in the web browser",
+        ),
+        Command(
+            "plot discoveries",
+            "gd",
+            "creates a chart of how many new artists you discovered in each month and opens it in the web browser",
+        ),
+        Command(
+            "plot clock",
+            "gcl",
+            "creates a radial chart of the plays or minutes per hour of the day of all plays or an artist and opens it in the web browser",
+        ),
+        Command(
+            "plot library",
+            "gl",
+            "creates a plot of how many distinct artists, albums or songs you had listened to up to each date and opens it in the web browser",
+        ),
+        Command(
+            "plot gaps",
+            "gg",
+            "creates a plot of the days since the previous play of an artist at each of its plays and opens it in the web browser",
+        ),
+        Command(
+            "plot years",
+            "gy",
+            "creates a plot overlaying the plays per month of each year of an artist, album or song and opens it in the web browser",
         ),
         Command(
             "plot artist albums",
@@ -1,24 +1,36 @@
 //! Module responsible for handling the CLI
+//!
+//! Questions asked by the prompts are printed to [`std::io::stderr`]
+//! so that [`std::io::stdout`] only contains the results and can be piped
 
+mod config;
 mod help;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+use chrono::Datelike;
 use endsong::prelude::*;
 use itertools::Itertools;
 use plotly::Scatter;
+use rand::seq::SliceRandom;
 use rustyline::{completion::Completer, Helper, Hinter, Validator};
 use rustyline::{
-    error::ReadlineError, highlight::Highlighter, history::FileHistory, ColorMode, Config, Editor,
+    error::ReadlineError, highlight::Highlighter, history::FileHistory, Behavior, ColorMode,
+    Config, Editor,
 };
+use serde::Deserialize;
 use thiserror::Error;
 
 use crate::plot;
 use crate::print;
+use crate::summarize;
 use crate::trace;
+use config::Theme;
 use print::{Aspect, AspectFull};
 use trace::TraceType;
 
@@ -61,9 +73,18 @@ enum UiError {
     /// Used when the end date is before the start date
     #[error("Date range is in wrong order - end date is before start date!")]
     DateWrongOrder,
+    /// Used when less aspects than needed for a comparison have been given
+    #[error("You have to input at least {0} to compare!")]
+    TooFewAspects(&'static str),
+    /// Used when writing a file fails
+    #[error("Couldn't write the file: {0}")]
+    Io(#[from] std::io::Error),
     /// Used when absurdly high time period would lead to panic (shouldn't happen)
     #[error("Use a sane time period")]
     TimeDeltaOverflow,
+    /// Used when there's nothing to plot in the chosen date range
+    #[error("Nothing has been listened to in this date range!")]
+    NothingListened,
 }
 
 /// Helper for [`Editor`]
@@ -71,13 +92,30 @@ enum UiError {
 struct ShellHelper {
     /// List containing all the possible completes for Tab
     completer_list: Vec<Rc<str>>,
+    /// Colors used for the prompts and `help`
+    theme: Theme,
+    /// Settings passed to the [`print`] functions
+    print: print::Settings,
+    /// Style passed to the [`plot`] functions
+    plot: plot::Style,
+    /// Settings passed to [`summarize::write()`] and [`summarize::write_card()`]
+    summarize: summarize::Settings,
 }
 impl ShellHelper {
     /// Creates a new [`ShellHelper`]
     /// with an empty tab auto-complete list
-    fn new() -> Self {
+    fn new(
+        theme: Theme,
+        print: print::Settings,
+        plot: plot::Style,
+        summarize: summarize::Settings,
+    ) -> Self {
         Self {
             completer_list: vec![],
+            theme,
+            print,
+            plot,
+            summarize,
         }
     }
 
@@ -93,6 +131,12 @@ impl ShellHelper {
             "print time",
             "print time date",
             "print max time",
+            "print clock",
+            "print milestone",
+            "print one hit wonders",
+            "print platforms",
+            "print stats",
+            "print platforms date",
             "print artist",
             "print album",
             "print song",
@@ -103,11 +147,37 @@ impl ShellHelper {
             "print songs date",
             "print top artists",
             "print top songs",
+            "print top artists date",
+            "print top albums date",
+            "print top songs date",
+            "print top songs from artist date",
+            "print top changes",
+            "compare artists",
+            "random",
+            "summarize year",
+            "summarize all",
+            "summarize artist",
+            "summarize compare",
+            "summarize card",
+            "summarize sessions",
+            "summarize day",
+            "summarize top days",
+            "summarize range",
             "plot",
             "plot rel",
             "plot compare",
             "plot compare rel",
             "plot top",
+            "plot bar",
+            "plot share",
+            "plot sessions",
+            "plot song versions",
+            "plot rank",
+            "plot discoveries",
+            "plot clock",
+            "plot library",
+            "plot gaps",
+            "plot years",
             "plot artist albums",
         ]);
     }
@@ -117,6 +187,38 @@ impl ShellHelper {
         self.completer_list = string_vec(&["artist", "album", "song"]);
     }
 
+    /// Changes tab-complete to dates derived from the dataset
+    ///
+    /// Offers `keyword` (i.e. `"start"` or `"now"`), the first and last date,
+    /// the first and last year and the 12 most recent `YYYY-MM-` prefixes
+    fn complete_dates(&mut self, entries: &SongEntries, keyword: &str) {
+        let first = entries.first_date();
+        let last = entries.last_date();
+
+        let mut dates = vec![
+            Rc::from(keyword),
+            Rc::from(first.format("%Y-%m-%d").to_string()),
+            Rc::from(last.format("%Y-%m-%d").to_string()),
+            Rc::from(first.format("%Y-").to_string()),
+            Rc::from(last.format("%Y-").to_string()),
+        ];
+        // entries are sorted by timestamp, so dedup() is enough
+        dates.extend(
+            entries
+                .iter()
+                .rev()
+                .map(|entry| entry.timestamp.format("%Y-%m-").to_string())
+                .dedup()
+                .take(12)
+                .map(Rc::from),
+        );
+        // first and last year could be the same
+        dates.sort_unstable();
+        dates.dedup();
+
+        self.complete_list(dates);
+    }
+
     /// Changes tab-complete to the given list of valid inputs - list should be unsorted
     /// because it will be sorted here anyway
     fn complete_list(&mut self, completer_list: Vec<Rc<str>>) {
@@ -131,20 +233,11 @@ impl Highlighter for ShellHelper {
         prompt: &'p str,
         _default: bool,
     ) -> std::borrow::Cow<'b, str> {
+        let theme = self.theme;
         match prompt {
-            PROMPT_COMMAND => Cow::Owned(format!(
-                "{}{}{}",
-                Color::Green,
-                PROMPT_COMMAND,
-                Color::Reset
-            )),
-            PROMPT_MAIN => Cow::Owned(format!("{}{}{}", Color::Cyan, PROMPT_MAIN, Color::Reset)),
-            PROMPT_SECONDARY => Cow::Owned(format!(
-                "{}{}{}",
-                Color::Red,
-                PROMPT_SECONDARY,
-                Color::Reset
-            )),
+            PROMPT_COMMAND => Cow::Owned(theme.paint(theme.command, PROMPT_COMMAND)),
+            PROMPT_MAIN => Cow::Owned(theme.paint(theme.main, PROMPT_MAIN)),
+            PROMPT_SECONDARY => Cow::Owned(theme.paint(theme.secondary, PROMPT_SECONDARY)),
             _ => Cow::Borrowed(prompt),
         }
     }
@@ -174,9 +267,13 @@ impl Completer for ShellHelper {
 /// ANSI Colors
 ///
 /// See <https://bixense.com/clicolors>
-#[derive(Debug, Copy, Clone)]
+///
+/// Used in the [theme][config::Theme] (in `snake_case`)
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
 enum Color {
     /// Resets the following text with `\x1b[0m`
+    #[serde(skip)]
     Reset,
     /// Makes the following text green with `\x1b[1;32m`
     Green,
@@ -188,6 +285,10 @@ enum Color {
     Red,
     /// Makes the following text pink with `\x1b[1;35m`
     Pink,
+    /// Makes the following text yellow with `\x1b[1;33m`
+    Yellow,
+    /// Makes the following text blue with `\x1b[1;34m`
+    Blue,
 }
 impl Display for Color {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -198,6 +299,8 @@ impl Display for Color {
             Color::Cyan => write!(f, "\x1b[1;36m"),
             Color::Red => write!(f, "\x1b[1;31m"),
             Color::Pink => write!(f, "\x1b[1;35m"),
+            Color::Yellow => write!(f, "\x1b[1;33m"),
+            Color::Blue => write!(f, "\x1b[1;34m"),
         }
     }
 }
@@ -210,31 +313,27 @@ fn string_vec(slice: &[&str]) -> Vec<Rc<str>> {
 }
 
 /// Starts the CLI/shell instance
+///
+/// `paths` - paths of the files `entries` were created from,
+/// used to keep a separate history for each dataset (see [`history_path()`])
 #[allow(clippy::missing_panics_doc)]
-pub fn start(entries: &SongEntries) {
+pub fn start<P: AsRef<Path>>(entries: &SongEntries, paths: &[P]) {
     println!("=== INTERACTIVE MODE ACTIVATED ===");
     println!("PRESS 'CTRL+C' TO EXIT THE PROGRAM");
     println!("TYPE 'help' FOR AVAILABLE COMMANDS");
     println!("DO NOT FORGET TO USE THE TABULATOR");
 
-    // inspired by
-    // https://github.com/trane-project/trane-cli/blob/master/src/main.rs
-    let config = Config::builder()
-        .auto_add_history(true)
-        .color_mode(ColorMode::Enabled)
-        .history_ignore_space(true)
-        .completion_type(rustyline::CompletionType::List)
-        .build();
-
-    let mut rl = Editor::<ShellHelper, FileHistory>::with_config(config)
-        .expect("Sorry, there's been an error!");
-
-    let mut helper = ShellHelper::new();
-    helper.complete_commands();
-    rl.set_helper(Some(helper));
+    let mut rl = editor();
+    rl.helper_mut().unwrap().complete_commands();
 
-    let history_path = std::path::Path::new(".rep_history");
-    if !history_path.try_exists().unwrap() {
+    let history_path = history_path(paths);
+    let history_path = history_path.as_path();
+    if let Some(parent) = history_path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            eprintln!("Failed to create history directory: {e}");
+        }
+    }
+    if !history_path.try_exists().unwrap_or(false) {
         if let Err(e) = std::fs::File::create(history_path) {
             eprintln!("Failed to create history file: {e}");
         }
@@ -282,6 +381,101 @@ pub fn start(entries: &SongEntries) {
     }
 }
 
+/// Returns the path of the history file for the dataset made of `paths`
+///
+/// It's `<data dir>/endsong_ui/history/<hash of paths>` (see [`dirs::data_dir()`])
+/// so that the history of different datasets doesn't get mixed up.
+/// Falls back to `.rep_history` in the current directory
+/// if there's no data directory on this platform.
+fn history_path<P: AsRef<Path>>(paths: &[P]) -> PathBuf {
+    let Some(data_dir) = dirs::data_dir() else {
+        return PathBuf::from(".rep_history");
+    };
+
+    // the same dataset should map to the same file
+    // regardless of the order of the paths or how they're written
+    let paths = paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            path.to_string_lossy().into_owned()
+        })
+        .sorted_unstable()
+        .join("\0");
+
+    // FNV-1a, because std's DefaultHasher isn't guaranteed
+    // to be the same across Rust versions
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in paths.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    data_dir
+        .join("endsong_ui")
+        .join("history")
+        .join(format!("{hash:016x}"))
+}
+
+/// Executes a single `command` non-interactively
+///
+/// Inputs for the prompts of the command are read line by line from
+/// [`std::io::stdin`] if it's not a terminal, e.g.
+/// `printf '10\nplays\n' | endsong_ui --exec "print top artists" | grep Sabaton`
+///
+/// Returns whether the command was executed successfully
+#[must_use]
+pub fn exec(entries: &SongEntries, command: &str) -> bool {
+    let mut rl = editor();
+
+    match match_input(command, entries, &mut rl) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        }
+    }
+}
+
+/// Creates the line editor used by [`start()`] and [`exec()`]
+/// with the [user config][config::Config] applied
+fn editor() -> Editor<ShellHelper, FileHistory> {
+    // inspired by
+    // https://github.com/trane-project/trane-cli/blob/master/src/main.rs
+    let config = Config::builder()
+        .auto_add_history(true)
+        .color_mode(ColorMode::Enabled)
+        // so that prompts don't end up in stdout if it's piped
+        .behavior(Behavior::PreferTerm)
+        .history_ignore_space(true)
+        .completion_type(rustyline::CompletionType::List)
+        .build();
+
+    let mut rl = Editor::<ShellHelper, FileHistory>::with_config(config)
+        .expect("Sorry, there's been an error!");
+
+    let user_config = config::Config::load();
+    let print = print::Settings {
+        format: Some(user_config.print.format.unwrap_or_else(|| {
+            if std::io::stdout().is_terminal() {
+                print::Format::Pretty
+            } else {
+                print::Format::Plain
+            }
+        })),
+        ..user_config.print
+    };
+    rl.set_helper(Some(ShellHelper::new(
+        user_config.theme,
+        print,
+        user_config.plot,
+        user_config.summarize,
+    )));
+
+    rl
+}
+
 /// Decides what to do with user input
 fn match_input(
     inp: &str,
@@ -291,10 +485,16 @@ fn match_input(
     match inp {
         // every new command added has to have an entry in `help`!
         // and in Shellhelper::complete_commands()
-        "help" | "h" => help::help(),
+        "help" | "h" => help::help(rl.helper().unwrap().theme),
         "print time" | "pt" => print::time_played(entries),
         "print time date" | "ptd" => match_print_time_date(entries, rl)?,
         "print max time" | "pmt" => match_print_max_time(entries, rl)?,
+        "print clock" | "pc" => match_print_clock(entries, rl)?,
+        "print milestone" | "pms" => match_print_milestone(entries, rl)?,
+        "print one hit wonders" | "pohw" => match_print_one_hit_wonders(entries, rl)?,
+        "print stats" | "pst" => print::stats(&rl.helper().unwrap().print, entries),
+        "print platforms" | "pplat" => print::platforms(&rl.helper().unwrap().print, entries),
+        "print platforms date" | "pplatd" => match_print_platforms_date(entries, rl)?,
         "print artist" | "part" => match_print_artist(entries, rl)?,
         "print album" | "palb" => match_print_album(entries, rl)?,
         "print song" | "pson" => match_print_song(entries, rl)?,
@@ -306,19 +506,84 @@ fn match_input(
         "print top artists" | "ptarts" => match_print_top(entries, rl, Aspect::Artists, false)?,
         "print top albums" | "ptalbs" => match_print_top(entries, rl, Aspect::Albums, false)?,
         "print top songs" | "ptsons" => match_print_top(entries, rl, Aspect::Songs, true)?,
+        "print top artists date" | "ptartd" => {
+            match_print_top_date(entries, rl, Aspect::Artists, false)?;
+        }
+        "print top albums date" | "ptalbd" => {
+            match_print_top_date(entries, rl, Aspect::Albums, false)?;
+        }
+        "print top songs date" | "ptsond" => {
+            match_print_top_date(entries, rl, Aspect::Songs, true)?;
+        }
+        "print top songs from artist date" | "ptsonsad" => {
+            match_print_top_songs_from_artist_date(entries, rl)?;
+        }
+        "print top changes" | "ptc" => match_print_top_changes(entries, rl)?,
+        "compare artists" | "ca" => match_compare_artists(entries, rl)?,
+        "random" | "r" => match_random(entries, rl)?,
         "plot" | "g" => match_plot(entries, rl)?,
         "plot rel" | "gr" => match_plot_relative(entries, rl)?,
         "plot compare" | "gc" => match_plot_compare(entries, rl)?,
         "plot compare rel" | "gcr" => match_plot_compare_relative(entries, rl)?,
         "plot top" | "gt" => match_plot_top(entries, rl)?,
+        "plot bar" | "gb" => match_plot_bar(entries, rl)?,
+        "plot share" | "gs" => match_plot_share(entries, rl)?,
+        "plot sessions" | "gse" => match_plot_sessions(entries, rl)?,
+        "plot song versions" | "gsv" => match_plot_song_versions(entries, rl)?,
+        "plot rank" | "grk" => match_plot_rank(entries, rl)?,
+        "plot discoveries" | "gd" => plot_discoveries(&rl.helper().unwrap().plot, entries),
+        "plot clock" | "gcl" => match_plot_clock(entries, rl)?,
+        "plot library" | "gl" => match_plot_library(entries, rl)?,
+        "plot gaps" | "gg" => match_plot_gaps(entries, rl)?,
+        "plot years" | "gy" => match_plot_years(entries, rl)?,
         "plot artist albums" | "gaa" => match_plot_artist_albums(entries, rl)?,
+        "summarize year" | "sy" => match_summarize_year(entries, rl, None)?,
+        "summarize all" | "sa" => {
+            summarize::write(&rl.helper().unwrap().summarize, "summary", |report| {
+                summarize::all(report, entries)
+            })?;
+        }
+        "summarize artist" | "sart" => match_summarize_artist(entries, rl)?,
+        "summarize compare" | "sc" => match_summarize_compare(entries, rl, None)?,
+        "summarize card" | "scard" => match_summarize_card(entries, rl)?,
+        "summarize sessions" | "sse" => match_summarize_sessions(entries, rl)?,
+        "summarize day" | "sd" => match_summarize_day(entries, rl, None)?,
+        "summarize top days" | "std" => match_summarize_top_days(entries, rl)?,
+        "summarize range" | "sr" => match_summarize_range(entries, rl, None)?,
+        dates if dates.starts_with("summarize range ") || dates.starts_with("sr ") => {
+            // unwrap ok because of the space in the match guard
+            let (_, dates) = dates.split_once(' ').unwrap();
+            let dates = dates.trim_start_matches("range ").trim();
+            match_summarize_range(entries, rl, dates.split_once(' '))?;
+        }
+        day if day.starts_with("summarize day ") || day.starts_with("sd ") => {
+            // unwrap ok because of the space in the match guard
+            let (_, day) = day.rsplit_once(' ').unwrap();
+            match_summarize_day(entries, rl, Some(day))?;
+        }
+        years if years.starts_with("summarize compare ") || years.starts_with("sc ") => {
+            // unwrap ok because of the space in the match guard
+            let (_, years) = years.split_once(' ').unwrap();
+            let years = years.trim_start_matches("compare ").trim();
+            match_summarize_compare(entries, rl, years.split_once(' '))?;
+        }
+        year if year.starts_with("summarize year ") || year.starts_with("sy ") => {
+            // unwrap ok because of the space in the match guard
+            let (_, year) = year.rsplit_once(' ').unwrap();
+            match_summarize_year(entries, rl, Some(year))?;
+        }
+        help if help.starts_with("help ") || help.starts_with("h ") => {
+            // unwrap ok because of the space in the match guard
+            let (_, command) = help.split_once(' ').unwrap();
+            help::help_command(rl.helper().unwrap().theme, command.trim());
+        }
         // when you press ENTER -> nothing happens, new prompt
         "" => (),
         _ => {
+            let theme = rl.helper().unwrap().theme;
             println!(
-                "Command not found! Type {}help{} to print available commands",
-                Color::Red,
-                Color::Reset
+                "Command not found! Type {} to print available commands",
+                theme.paint(theme.secondary, "help")
             );
         }
     }
@@ -331,7 +596,7 @@ fn match_print_time_date(
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
     // 1st + 2nd prompt: start + end date
-    let (start_date, end_date) = read_dates(rl)?;
+    let (start_date, end_date) = read_dates(rl, entries)?;
 
     print::time_played_date(entries, &start_date, &end_date);
     Ok(())
@@ -347,15 +612,15 @@ fn match_print_max_time(
     rl.helper_mut()
         .unwrap()
         .complete_list(string_vec(&valid_inputs));
-    println!("Input time period in days or weeks?");
+    eprintln!("Input time period in days or weeks?");
     let duration_type = rl.readline(PROMPT_SECONDARY)?;
     if !valid_inputs.iter().any(|&s| s == duration_type) {
         return Err(UiError::InvalidArgument("days, weeks"));
-    };
+    }
 
     rl.helper_mut().unwrap().reset();
     // 2nd prompt: actual duration number
-    println!("What's the time period? Whole numbers only");
+    eprintln!("What's the time period? Whole numbers only");
     let usr_input_duration = rl.readline(PROMPT_SECONDARY)?;
     let duration_num = usr_input_duration.parse::<i64>()?;
 
@@ -376,15 +641,119 @@ fn match_print_max_time(
     Ok(())
 }
 
+/// Used by [`match_input()`] for `print clock` command
+fn match_print_clock(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
+    // 1st prompt: all plays or a specific aspect
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["all", "artist", "album", "song"]));
+    eprintln!("Clock of all plays or of an artist, album or song?");
+    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+
+    // other prompts: the aspect
+    match usr_input_asp.as_str() {
+        "all" => print::clock(&settings, entries, None),
+        "artist" => {
+            let art = read_artist(rl, entries)?;
+            print::clock(&settings, entries, Some(&AspectFull::Artist(&art)));
+        }
+        "album" => {
+            let art = read_artist(rl, entries)?;
+            let alb = read_album(rl, entries, &art)?;
+            print::clock(&settings, entries, Some(&AspectFull::Album(&alb)));
+        }
+        "song" => {
+            let art = read_artist(rl, entries)?;
+            let alb = read_album(rl, entries, &art)?;
+            let son = read_song(rl, entries, &alb)?;
+            print::clock(&settings, entries, Some(&AspectFull::Song(&son)));
+        }
+        _ => return Err(UiError::InvalidArgument("all, artist, album, song")),
+    }
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `print milestone` command
+fn match_print_milestone(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: artist name
+    let art = read_artist(rl, entries)?;
+
+    // 2nd prompt: which play
+    rl.helper_mut().unwrap().reset();
+    eprintln!("Which play? E.g. 1000 for the 1000th play");
+    let usr_input_n = rl.readline(PROMPT_SECONDARY)?;
+    let n: usize = usr_input_n.parse()?;
+
+    print::milestone(entries, &art, n);
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `print one hit wonders` command
+fn match_print_one_hit_wonders(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
+    // 1st prompt: share of the song
+    rl.helper_mut().unwrap().complete_list(string_vec(&["50"]));
+    eprintln!("More than what % of an artist's plays? (e.g. 50, leave empty for 50)");
+    let usr_input_percent = rl.readline(PROMPT_SECONDARY)?;
+    let percent_threshold: usize = match usr_input_percent.as_str() {
+        "" => 50,
+        percent => percent.parse()?,
+    };
+    if percent_threshold > 100 {
+        return Err(UiError::InvalidArgument("0 to 100"));
+    }
+
+    // 2nd prompt: minimum plays of an artist
+    rl.helper_mut().unwrap().complete_list(string_vec(&["10"]));
+    eprintln!("Minimum plays of an artist? (e.g. 10, leave empty for 10)");
+    let usr_input_min = rl.readline(PROMPT_SECONDARY)?;
+    let min_plays: usize = match usr_input_min.as_str() {
+        "" => 10,
+        min => min.parse()?,
+    };
+
+    print::one_hit_wonders(&settings, entries, percent_threshold, min_plays);
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `print platforms date` command
+fn match_print_platforms_date(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
+    // 1st + 2nd prompt: start + end date
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    print::platforms_date(&settings, entries, &start_date, &end_date);
+    Ok(())
+}
+
 /// Used by [`match_input()`] for `print artist` command
 fn match_print_artist(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // prompt: artist name
     let art = read_artist(rl, entries)?;
 
-    print::aspect(entries, &AspectFull::Artist(&art));
+    print::aspect(&settings, entries, &AspectFull::Artist(&art));
     Ok(())
 }
 
@@ -395,13 +764,21 @@ fn match_print_artist_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
     // 2nd + 3rd prompt: start + end date
-    let (start_date, end_date) = read_dates(rl)?;
-
-    print::aspect_date(entries, &AspectFull::Artist(&art), &start_date, &end_date);
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    print::aspect_date(
+        &settings,
+        entries,
+        &AspectFull::Artist(&art),
+        &start_date,
+        &end_date,
+    );
     Ok(())
 }
 
@@ -410,13 +787,15 @@ fn match_print_album(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
     // 2nd prompt: album name
     let alb = read_album(rl, entries, &art)?;
 
-    print::aspect(entries, &AspectFull::Album(&alb));
+    print::aspect(&settings, entries, &AspectFull::Album(&alb));
     Ok(())
 }
 
@@ -427,6 +806,8 @@ fn match_print_album_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -434,9 +815,15 @@ fn match_print_album_date(
     let alb = read_album(rl, entries, &art)?;
 
     // 3rd + 4th prompt: start + end date
-    let (start_date, end_date) = read_dates(rl)?;
-
-    print::aspect_date(entries, &AspectFull::Album(&alb), &start_date, &end_date);
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    print::aspect_date(
+        &settings,
+        entries,
+        &AspectFull::Album(&alb),
+        &start_date,
+        &end_date,
+    );
     Ok(())
 }
 
@@ -445,6 +832,8 @@ fn match_print_song(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -454,7 +843,7 @@ fn match_print_song(
     // 3rd prompt: song name
     let son = read_song(rl, entries, &alb)?;
 
-    print::aspect(entries, &AspectFull::Song(&son));
+    print::aspect(&settings, entries, &AspectFull::Song(&son));
     Ok(())
 }
 
@@ -465,6 +854,8 @@ fn match_print_song_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -475,9 +866,15 @@ fn match_print_song_date(
     let son = read_song(rl, entries, &alb)?;
 
     // 4th + 5th prompt: start + end date
-    let (start_date, end_date) = read_dates(rl)?;
-
-    print::aspect_date(entries, &AspectFull::Song(&son), &start_date, &end_date);
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    print::aspect_date(
+        &settings,
+        entries,
+        &AspectFull::Song(&son),
+        &start_date,
+        &end_date,
+    );
     Ok(())
 }
 
@@ -486,6 +883,8 @@ fn match_print_songs(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -503,7 +902,7 @@ fn match_print_songs(
         );
     }
     for song in songs {
-        print::aspect(entries, &AspectFull::Song(&song));
+        print::aspect(&settings, entries, &AspectFull::Song(&song));
     }
     Ok(())
 }
@@ -513,6 +912,8 @@ fn match_print_songs_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -520,7 +921,7 @@ fn match_print_songs_date(
     let songs = read_songs(rl, entries, &art)?;
 
     // 3rd + 4th prompt: start + end date
-    let (start_date, end_date) = read_dates(rl)?;
+    let (start_date, end_date) = read_dates(rl, entries)?;
 
     // if there are multiple songs with that name found
     if songs.len() > 1 {
@@ -533,7 +934,13 @@ fn match_print_songs_date(
         );
     }
     for song in songs {
-        print::aspect_date(entries, &AspectFull::Song(&song), &start_date, &end_date);
+        print::aspect_date(
+            &settings,
+            entries,
+            &AspectFull::Song(&song),
+            &start_date,
+            &end_date,
+        );
     }
 
     Ok(())
@@ -546,180 +953,1101 @@ fn match_print_top(
     asp: Aspect,
     ask_for_sum: bool,
 ) -> Result<(), UiError> {
+    let settings = rl.helper().unwrap().print;
+
     rl.helper_mut().unwrap().reset();
     // prompt: top n
-    println!("How many Top {asp}?");
+    eprintln!("How many Top {asp}?");
     let usr_input_n = rl.readline(PROMPT_MAIN)?;
     let num: usize = usr_input_n.parse()?;
 
-    let mut sum_songs_from_different_albums = false;
-    if ask_for_sum {
+    let sum_songs_from_different_albums = if ask_for_sum {
         // prompt: ask if you want to sum songs from different albums
-        rl.helper_mut()
-            .unwrap()
-            .complete_list(string_vec(&["yes", "y", "no", "n"]));
-        println!("Do you want to sum songs from different albums? (y/n)");
-        let usr_input_b = rl.readline(PROMPT_SECONDARY)?;
-        sum_songs_from_different_albums = match usr_input_b.as_str() {
-            "yes" | "y" => true,
-            "no" | "n" => false,
-            _ => {
-                println!("Invalid input. Assuming 'no'.");
-                false
-            }
+        read_sum_songs(rl)?
+    } else {
+        false
+    };
+
+    // prompt: rank by plays or listening time
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["plays", "time", "both"]));
+    eprintln!("Rank by plays or listening time, or show both?");
+    let usr_input_rank = rl.readline(PROMPT_SECONDARY)?;
+    match usr_input_rank.as_str() {
+        "plays" | "" => print::top(
+            &settings,
+            entries,
+            asp,
+            num,
+            sum_songs_from_different_albums,
+        ),
+        "time" => print::top_by_time(
+            &settings,
+            entries,
+            asp,
+            num,
+            sum_songs_from_different_albums,
+        ),
+        "both" => {
+            // ranked by plays with the time shown just for this list
+            let settings = print::Settings {
+                show_time: true,
+                ..settings
+            };
+            print::top(
+                &settings,
+                entries,
+                asp,
+                num,
+                sum_songs_from_different_albums,
+            );
         }
+        _ => return Err(UiError::InvalidArgument("plays, time, both")),
     }
-
-    print::top(entries, asp, num, sum_songs_from_different_albums);
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot` command
-fn match_plot(
+/// Used by [`match_input()`] for `print top artists/albums/songs date` commands
+///
+/// Basically [`match_print_top()`] but with date functionality
+fn match_print_top_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
+    asp: Aspect,
+    ask_for_sum: bool,
 ) -> Result<(), UiError> {
-    // prompt: what to plot
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("What do you want to plot? artist, album or song?");
-    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+    let settings = rl.helper().unwrap().print;
 
-    // other prompts
-    let (trace, title) = get_absolute_trace(entries, rl, usr_input_asp.as_str())?;
+    // 1st + 2nd prompt: start + end date
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    // 3rd prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many Top {asp}?");
+    let usr_input_n = rl.readline(PROMPT_MAIN)?;
+    let num: usize = usr_input_n.parse()?;
 
-    plot::single((TraceType::Absolute(trace), title));
+    let sum_songs_from_different_albums = if ask_for_sum {
+        // 4th prompt: ask if you want to sum songs from different albums
+        read_sum_songs(rl)?
+    } else {
+        false
+    };
 
+    print::top_date(
+        &settings,
+        entries,
+        asp,
+        num,
+        sum_songs_from_different_albums,
+        &start_date,
+        &end_date,
+    );
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot relative` command
-fn match_plot_relative(
+/// Used by [`match_input()`] for `print top changes` command
+fn match_print_top_changes(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
-    // prompt: what to plot
+    let settings = rl.helper().unwrap().print;
+
+    // 1st prompt: artists, albums or songs
     rl.helper_mut().unwrap().complete_aspects();
-    println!("What do you want to plot? artist, album or song?");
-    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+    eprintln!("Top artists, albums or songs?");
+    let usr_input_asp = rl.readline(PROMPT_MAIN)?;
+    let asp: Aspect = usr_input_asp.parse()?;
 
-    // other prompts
-    let (trace, title) = get_relative_trace(entries, rl, usr_input_asp.as_str())?;
+    // 2nd - 5th prompt: start + end date of both top lists
+    eprintln!("Date range of the earlier top list:");
+    let (first_start, first_end) = read_dates(rl, entries)?;
+    eprintln!("Date range of the later top list:");
+    let (second_start, second_end) = read_dates(rl, entries)?;
+
+    // 6th prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many Top {asp}?");
+    let usr_input_n = rl.readline(PROMPT_MAIN)?;
+    let num: usize = usr_input_n.parse()?;
 
-    plot::single((TraceType::Relative(trace), title));
+    let sum_songs_from_different_albums = if matches!(asp, Aspect::Songs) {
+        // 7th prompt: ask if you want to sum songs from different albums
+        read_sum_songs(rl)?
+    } else {
+        false
+    };
 
+    print::top_delta(
+        &settings,
+        entries,
+        asp,
+        num,
+        sum_songs_from_different_albums,
+        (&first_start, &first_end),
+        (&second_start, &second_end),
+    );
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot compare` command
-fn match_plot_compare(
+/// Used by [`match_input()`] for `print top songs from artist date` command
+fn match_print_top_songs_from_artist_date(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
-    // first trace
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("1st trace: artist, album or song?");
-    let usr_input_asp_one = rl.readline(PROMPT_SECONDARY)?;
-    let (trace_one, title_one) = get_absolute_trace(entries, rl, usr_input_asp_one.as_str())?;
+    let settings = rl.helper().unwrap().print;
 
-    // second trace
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("2nd trace: artist, album or song?");
-    let usr_input_asp_two = rl.readline(PROMPT_SECONDARY)?;
-    let (trace_two, title_two) = get_absolute_trace(entries, rl, usr_input_asp_two.as_str())?;
+    // 1st prompt: artist name
+    let art = read_artist(rl, entries)?;
 
-    plot::compare(
-        (TraceType::Absolute(trace_one), title_one),
-        (TraceType::Absolute(trace_two), title_two),
-    );
+    // 2nd + 3rd prompt: start + end date
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    // 4th prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many Top songs?");
+    let usr_input_n = rl.readline(PROMPT_MAIN)?;
+    let num: usize = usr_input_n.parse()?;
 
+    // 5th prompt: ask if you want to sum songs from different albums
+    let sum_songs_from_different_albums = read_sum_songs(rl)?;
+
+    print::top_songs_from_artist_date(
+        &settings,
+        entries,
+        &art,
+        num,
+        sum_songs_from_different_albums,
+        &start_date,
+        &end_date,
+    );
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot compare relative` command
-fn match_plot_compare_relative(
+/// Used by [`match_input()`] for `compare artists` command
+fn match_compare_artists(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
-    // first trace
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("1st trace: artist, album or song?");
-    let usr_input_asp_one = rl.readline(PROMPT_SECONDARY)?;
-    let (trace_one, title_one) = get_relative_trace(entries, rl, usr_input_asp_one.as_str())?;
+    let settings = rl.helper().unwrap().print;
 
-    // second trace
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("2nd trace: artist, album or song?");
-    let usr_input_asp_two = rl.readline(PROMPT_SECONDARY)?;
-    let (trace_two, title_two) = get_relative_trace(entries, rl, usr_input_asp_two.as_str())?;
+    let mut artists: Vec<Artist> = vec![];
 
-    plot::compare(
-        (TraceType::Relative(trace_one), title_one),
-        (TraceType::Relative(trace_two), title_two),
-    );
+    // prompts: artist names until an empty input
+    loop {
+        rl.helper_mut().unwrap().complete_list(entries.artists());
+        eprintln!("Artist name? (leave empty to start comparing)");
+        let usr_input_art = rl.readline(PROMPT_MAIN)?;
+        if usr_input_art.is_empty() {
+            break;
+        }
+        let art = entries
+            .find()
+            .artist(&usr_input_art)
+            .ok_or(UiError::NotFound("artist"))?;
+        artists.push(art);
+    }
+
+    if artists.len() < 2 {
+        return Err(UiError::TooFewAspects("two artists"));
+    }
 
+    print::compare_artists(&settings, entries, &artists);
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot top` command
-fn match_plot_top(
+/// Used by [`match_input()`] for `random` command
+fn match_random(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
-    // prompt: what to plot
-    rl.helper_mut().unwrap().complete_aspects();
-    println!("What do you want to plot? Top artists, albums or songs?");
-    let usr_input_asp = rl.readline(PROMPT_MAIN)?;
-    let aspect: Aspect = usr_input_asp.parse()?;
+    let settings = rl.helper().unwrap().print;
 
-    // prompt: top n
-    rl.helper_mut().unwrap().reset();
-    println!("How many top {aspect} to plot? (recommended: ~5)");
-    let usr_input_n = rl.readline(PROMPT_SECONDARY)?;
-    let num: usize = usr_input_n.parse()?;
+    // 1st prompt: what to pick from
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["all", "artist", "date"]));
+    eprintln!("Pick from all songs, songs of an artist or songs played within a date range?");
+    let usr_input_from = rl.readline(PROMPT_SECONDARY)?;
+
+    // other prompts: artist or date range
+    let songs = match usr_input_from.as_str() {
+        "all" => gather::songs(entries, false),
+        "artist" => {
+            let art = read_artist(rl, entries)?;
+            gather::songs_from(entries, &art)
+        }
+        "date" => {
+            let (start_date, end_date) = read_dates(rl, entries)?;
+            gather::songs(entries.between(&start_date, &end_date), false)
+        }
+        _ => return Err(UiError::InvalidArgument("all, artist, date")),
+    };
 
-    // TODO prompt: sum songs from different albums?
+    // last prompt: weighted or not
+    let weighted = read_yes_no(rl, "Should songs you've played more often be more likely?")?;
 
-    let traces = match aspect {
-        Aspect::Artists => get_traces(entries, &gather::artists(entries), num),
-        Aspect::Albums => get_traces(entries, &gather::albums(entries), num),
-        Aspect::Songs => get_traces(entries, &gather::songs(entries, true), num),
+    let song = pick_random_song(songs, weighted).ok_or(UiError::NotFound("song"))?;
+
+    print::aspect(&settings, entries, &AspectFull::Song(&song));
+    Ok(())
+}
+
+/// Picks a random song out of `songs`
+///
+/// With `weighted` the probability of a song being picked
+/// is proportional to its playcount
+///
+/// Returns [`None`] if `songs` is empty
+fn pick_random_song(songs: HashMap<Song, usize>, weighted: bool) -> Option<Song> {
+    let mut rng = rand::thread_rng();
+    // sorted so that the same seed would always pick the same song
+    let songs = songs.into_iter().sorted_unstable().collect_vec();
+
+    let pick = if weighted {
+        songs.choose_weighted(&mut rng, |(_, plays)| *plays).ok()
+    } else {
+        songs.choose(&mut rng)
+    };
+
+    pick.map(|(song, _)| song.clone())
+}
+
+/// Used by [`match_input()`] for `summarize year` command
+///
+/// `year` - the year if it was given directly like `summarize year 2021`,
+/// otherwise it will be prompted for
+fn match_summarize_year(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    year: Option<&str>,
+) -> Result<(), UiError> {
+    let first_year = entries.first_date().year();
+    let last_year = entries.last_date().year();
+
+    let year: i32 = if let Some(year) = year {
+        year.parse()?
+    } else {
+        // prompt: year
+        let years = (first_year..=last_year)
+            .map(|year| Rc::from(year.to_string()))
+            .collect_vec();
+        rl.helper_mut().unwrap().complete_list(years);
+        eprintln!("Which year?");
+        let usr_input_year = rl.readline(PROMPT_SECONDARY)?;
+        usr_input_year.parse()?
     };
 
-    plot::multiple(traces, &format!("Top {aspect}"));
+    if !(first_year..=last_year).contains(&year) {
+        return Err(UiError::NotFound("year"));
+    }
 
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!("summary {year}"),
+        |report| summarize::year(report, entries, year),
+    )?;
     Ok(())
 }
 
-/// Used by [`match_input()`] for `plot artist albums` command
-fn match_plot_artist_albums(
+/// Used by [`match_input()`] for `summarize artist` command
+fn match_summarize_artist(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
 ) -> Result<(), UiError> {
     // prompt: artist name
     let art = read_artist(rl, entries)?;
 
-    let albums_map = gather::albums_from_artist(entries, &art);
-    let albums = albums_map
-        .iter()
-        .sorted_unstable_by_key(|t| (std::cmp::Reverse(t.1), t.0))
-        .map(|(aspect, _)| aspect)
-        .collect_vec();
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!("summary {art}"),
+        |report| summarize::artist(report, entries, &art),
+    )?;
+    Ok(())
+}
 
-    let mut traces = vec![];
-    for (count, alb) in albums.into_iter().enumerate() {
-        let TraceType::Absolute(trace) = trace::absolute(entries, alb) else {
-            unreachable!()
-        };
+/// Used by [`match_input()`] for `summarize compare` command
+///
+/// `years` - the years if they were given directly like `summarize compare 2020 2021`,
+/// otherwise they will be prompted for
+fn match_summarize_compare(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    years: Option<(&str, &str)>,
+) -> Result<(), UiError> {
+    let first_year = entries.first_date().year();
+    let last_year = entries.last_date().year();
 
-        let trace = trace
-            .legend_group_title(art.name.to_string())
-            .name(&alb.name);
+    let (first, second): (i32, i32) = if let Some((first, second)) = years {
+        (first.trim().parse()?, second.trim().parse()?)
+    } else {
+        let years = (first_year..=last_year)
+            .map(|year| Rc::from(year.to_string()))
+            .collect_vec();
+        rl.helper_mut().unwrap().complete_list(years);
 
-        // only the traces for the 3 albums with most plays are shown by default
-        let trace = if count < 3 {
-            trace
-        } else {
-            // others are hidden and have to be enabled manually
+        // prompt: first year
+        eprintln!("First year?");
+        let usr_input_first = rl.readline(PROMPT_SECONDARY)?;
+
+        // prompt: second year
+        eprintln!("Second year?");
+        let usr_input_second = rl.readline(PROMPT_SECONDARY)?;
+        (usr_input_first.parse()?, usr_input_second.parse()?)
+    };
+
+    if ![first, second]
+        .iter()
+        .all(|year| (first_year..=last_year).contains(year))
+    {
+        return Err(UiError::NotFound("year"));
+    }
+
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!("summary {first} vs {second}"),
+        |report| summarize::compare(report, entries, first, second),
+    )?;
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `summarize card` command
+fn match_summarize_card(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompts: start and end date
+    let (start_date, end_date) = read_dates(rl, entries)?;
+
+    let svg = summarize::card(entries, &start_date, &end_date);
+    let path = summarize::write_card(
+        &rl.helper().unwrap().summarize,
+        &format!("card {} {}", start_date.date_naive(), end_date.date_naive()),
+        &svg,
+    )?;
+    eprintln!("Wrote the card to {}", path.display());
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `summarize sessions` command
+fn match_summarize_sessions(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: max pause within a session
+    rl.helper_mut().unwrap().reset();
+    eprintln!("After how many minutes of silence should a session end? (e.g. 30)");
+    let usr_input_gap = rl.readline(PROMPT_SECONDARY)?;
+    let gap_minutes: i64 = usr_input_gap.parse()?;
+    let max_gap = TimeDelta::try_minutes(gap_minutes).ok_or(UiError::TimeDeltaOverflow)?;
+
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!("summary sessions {gap_minutes}"),
+        |report| summarize::sessions(report, entries, max_gap),
+    )?;
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `summarize day` command
+///
+/// `day` - the date if it was given directly like `summarize day 2021-03-27`,
+/// otherwise it will be prompted for
+fn match_summarize_day(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    day: Option<&str>,
+) -> Result<(), UiError> {
+    let day = if let Some(day) = day {
+        parse_date(day)?
+    } else {
+        // prompt: date
+        rl.helper_mut().unwrap().complete_dates(entries, "now");
+        eprintln!("Which day? YYYY-MM-DD or 'now'");
+        let usr_input_day = rl.readline(PROMPT_SECONDARY)?;
+        parse_date(&usr_input_day)?
+    };
+    let day = day.date_naive();
+
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!("summary {day}"),
+        |report| summarize::day(report, entries, day),
+    )?;
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `summarize top days` command
+fn match_summarize_top_days(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: all plays or an artist
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["all", "artist"]));
+    eprintln!("Top days of all plays or of an artist?");
+    let usr_input_of = rl.readline(PROMPT_SECONDARY)?;
+    let art = match usr_input_of.as_str() {
+        "all" => None,
+        // 2nd prompt: artist name
+        "artist" => Some(read_artist(rl, entries)?),
+        _ => return Err(UiError::InvalidArgument("all, artist")),
+    };
+
+    // last prompt: number of days
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many days?");
+    let usr_input_num = rl.readline(PROMPT_SECONDARY)?;
+    let num: usize = usr_input_num.parse()?;
+
+    let name = match &art {
+        Some(art) => format!("top days {art}"),
+        None => String::from("top days"),
+    };
+    summarize::write(&rl.helper().unwrap().summarize, &name, |report| {
+        summarize::top_days(report, entries, art.as_ref(), num)
+    })?;
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `summarize range` command
+///
+/// `dates` - the start and end date if they were given directly
+/// like `summarize range 2020-10-01 2021-03-31`,
+/// otherwise they will be prompted for
+fn match_summarize_range(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    dates: Option<(&str, &str)>,
+) -> Result<(), UiError> {
+    let (start_date, end_date) = if let Some((start, end)) = dates {
+        let (start_date, end_date) = (parse_date(start)?, parse_date(end)?);
+        if start_date >= end_date {
+            return Err(UiError::DateWrongOrder);
+        }
+        (start_date, end_date)
+    } else {
+        // prompts: start and end date
+        read_dates(rl, entries)?
+    };
+
+    summarize::write(
+        &rl.helper().unwrap().summarize,
+        &format!(
+            "summary {} {}",
+            start_date.date_naive(),
+            end_date.date_naive()
+        ),
+        |report| summarize::range(report, entries, &start_date, &end_date),
+    )?;
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot` command
+fn match_plot(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompts: cumulative or binned + rolling average
+    let options = read_trace_options(rl)?;
+
+    // prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("What do you want to plot? artist, album or song?");
+    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+
+    // other prompts
+    let (traces, title) = get_absolute_trace(entries, rl, usr_input_asp.as_str(), options)?;
+
+    plot::multiple(&rl.helper().unwrap().plot, traces, &title, true);
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot relative` command
+fn match_plot_relative(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: share of all history or of each period
+    let period = read_relative_period(rl)?;
+
+    // prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("What do you want to plot? artist, album or song?");
+    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+
+    // other prompts
+    let (trace, title) = get_relative_trace(entries, rl, usr_input_asp.as_str(), period)?;
+
+    plot::single(
+        &rl.helper().unwrap().plot,
+        (TraceType::Relative(trace), title),
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot compare` command
+fn match_plot_compare(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // cumulative or binned + rolling average (for all traces)
+    let options = read_trace_options(rl)?;
+
+    // other prompts: traces until "done"
+    let traces = read_compare_traces(
+        entries,
+        rl,
+        |rl, usr_input| get_absolute_trace(entries, rl, usr_input, options),
+        |art| absolute_traces(entries, art, options),
+    )?;
+
+    plot_compared(&rl.helper().unwrap().plot, traces);
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot compare relative` command
+fn match_plot_compare_relative(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // share of all history or of each period (for all traces)
+    let period = read_relative_period(rl)?;
+
+    // other prompts: traces until "done"
+    let traces = read_compare_traces(
+        entries,
+        rl,
+        |rl, usr_input| {
+            get_relative_trace(entries, rl, usr_input, period)
+                .map(|(trace, title)| (vec![TraceType::Relative(trace)], title))
+        },
+        |art| vec![relative_to_all(entries, art, period)],
+    )?;
+
+    plot_compared(&rl.helper().unwrap().plot, traces);
+
+    Ok(())
+}
+
+/// Used by [`match_plot_compare()`] and [`match_plot_compare_relative()`]
+/// to read traces until the user inputs "done"
+///
+/// `get_trace` asks for the aspect of the given kind (artist, album or song).
+/// Instead of a kind, a comma-separated list of artists can be given,
+/// which are turned into traces with `artist_trace`
+fn read_compare_traces(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    mut get_trace: impl FnMut(
+        &mut Editor<ShellHelper, FileHistory>,
+        &str,
+    ) -> Result<(Vec<TraceType>, String), UiError>,
+    artist_trace: impl Fn(&Artist) -> Vec<TraceType>,
+) -> Result<Vec<(Vec<TraceType>, String)>, UiError> {
+    let mut traces = vec![];
+
+    loop {
+        rl.helper_mut()
+            .unwrap()
+            .complete_list(string_vec(&["artist", "album", "song", "done"]));
+        eprintln!(
+            "Trace #{}: artist, album or song? Or a comma-separated list of artists (\"done\" to start plotting)",
+            traces.len() + 1
+        );
+        let usr_input = rl.readline(PROMPT_SECONDARY)?;
+        match usr_input.as_str() {
+            "done" => break,
+            list if list.contains(',') => {
+                for name in list
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                {
+                    let art = entries
+                        .find()
+                        .artist(name)
+                        .ok_or(UiError::NotFound("artist"))?;
+                    let title = art.to_string();
+                    traces.push((artist_trace(&art), title));
+                }
+            }
+            kind => traces.push(get_trace(rl, kind)?),
+        }
+    }
+
+    if traces.len() < 2 {
+        return Err(UiError::TooFewAspects("two traces"));
+    }
+
+    Ok(traces)
+}
+
+/// Plots the traces from [`read_compare_traces()`] in a single plot
+fn plot_compared(style: &plot::Style, traces: Vec<(Vec<TraceType>, String)>) {
+    let title = traces.iter().map(|(_, title)| title).join(" vs ");
+    let traces = traces
+        .into_iter()
+        .flat_map(|(traces, _)| traces)
+        .collect_vec();
+    plot::multiple(style, traces, &title, true);
+}
+
+/// Used by [`match_input()`] for `plot top` command
+fn match_plot_top(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("What do you want to plot? Top artists, albums or songs?");
+    let usr_input_asp = rl.readline(PROMPT_MAIN)?;
+    let aspect: Aspect = usr_input_asp.parse()?;
+
+    // prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many top {aspect} to plot? (recommended: ~5)");
+    let usr_input_n = rl.readline(PROMPT_SECONDARY)?;
+    let num: usize = usr_input_n.parse()?;
+
+    // prompts: cumulative or binned + rolling average
+    let options = read_trace_options(rl)?;
+
+    // TODO prompt: sum songs from different albums?
+
+    let traces = match aspect {
+        Aspect::Artists => get_traces(entries, &gather::artists(entries), num, options),
+        Aspect::Albums => get_traces(entries, &gather::albums(entries), num, options),
+        Aspect::Songs => get_traces(entries, &gather::songs(entries, true), num, options),
+    };
+
+    plot::multiple(
+        &rl.helper().unwrap().plot,
+        traces,
+        &format!("Top {aspect}"),
+        true,
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot bar` command
+fn match_plot_bar(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("What do you want to plot? Top artists, albums or songs?");
+    let usr_input_asp = rl.readline(PROMPT_MAIN)?;
+    let aspect: Aspect = usr_input_asp.parse()?;
+
+    // 2nd prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many top {aspect} to plot?");
+    let usr_input_n = rl.readline(PROMPT_SECONDARY)?;
+    let num: usize = usr_input_n.parse()?;
+
+    // 3rd prompt: plays or minutes
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["plays", "time"]));
+    eprintln!("Plays or listening time?");
+    let usr_input_rank = rl.readline(PROMPT_SECONDARY)?;
+
+    let minutes = |time: TimeDelta| usize::try_from(time.num_minutes()).unwrap_or_default();
+
+    let (bars, unit) = match (usr_input_rank.as_str(), aspect) {
+        ("plays", Aspect::Artists) => (top_bars(gather::artists(entries), num, |p| p), "plays"),
+        ("plays", Aspect::Albums) => (top_bars(gather::albums(entries), num, |p| p), "plays"),
+        ("plays", Aspect::Songs) => (top_bars(gather::songs(entries, true), num, |p| p), "plays"),
+        ("time", Aspect::Artists) => (
+            top_bars(gather::artists_listening_time(entries), num, minutes),
+            "minutes",
+        ),
+        ("time", Aspect::Albums) => (
+            top_bars(gather::albums_listening_time(entries), num, minutes),
+            "minutes",
+        ),
+        ("time", Aspect::Songs) => (
+            top_bars(gather::songs_listening_time(entries, true), num, minutes),
+            "minutes",
+        ),
+        _ => return Err(UiError::InvalidArgument("plays, time")),
+    };
+
+    plot::bar(
+        &rl.helper().unwrap().plot,
+        bars,
+        &format!("Top {aspect} by {unit}"),
+        unit,
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot share` command
+#[allow(clippy::cast_precision_loss)]
+fn match_plot_share(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: top n
+    rl.helper_mut().unwrap().reset();
+    eprintln!("How many top artists to show? The rest will be summed up as \"other\"");
+    let usr_input_n = rl.readline(PROMPT_SECONDARY)?;
+    let num: usize = usr_input_n.parse()?;
+
+    // 2nd prompt: whole dataset or date range
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["all", "date"]));
+    eprintln!("Of all plays or within a date range?");
+    let usr_input_range = rl.readline(PROMPT_SECONDARY)?;
+    let (slice, title) = match usr_input_range.as_str() {
+        "all" => (
+            &entries[..],
+            String::from("Share of listening time per artist"),
+        ),
+        "date" => {
+            // 3rd + 4th prompt: start + end date
+            let (start_date, end_date) = read_dates(rl, entries)?;
+            (
+                entries.between(&start_date, &end_date),
+                format!(
+                    "Share of listening time per artist between {} and {}",
+                    start_date.date_naive(),
+                    end_date.date_naive()
+                ),
+            )
+        }
+        _ => return Err(UiError::InvalidArgument("all, date")),
+    };
+
+    let total = gather::listening_time(slice).num_seconds();
+    if total <= 0 {
+        // every share would be NaN
+        return Err(UiError::NothingListened);
+    }
+    let total = total as f64;
+    let mut shares = top_bars(gather::artists_listening_time(slice), num, |time| time)
+        .into_iter()
+        .map(|(art, time)| (art, time.num_seconds() as f64 / total * 100.0))
+        .collect_vec();
+    let other = 100.0 - shares.iter().map(|(_, share)| share).sum::<f64>();
+    if other > 0.0 {
+        shares.push((String::from("other"), other));
+    }
+
+    plot::share(&rl.helper().unwrap().plot, shares, &title);
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot sessions` command
+fn match_plot_sessions(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: max pause within a session
+    rl.helper_mut().unwrap().reset();
+    eprintln!("After how many minutes of silence should a session end? (e.g. 30)");
+    let usr_input_gap = rl.readline(PROMPT_SECONDARY)?;
+    let gap_minutes: i64 = usr_input_gap.parse()?;
+    let max_gap = TimeDelta::try_minutes(gap_minutes).ok_or(UiError::TimeDeltaOverflow)?;
+
+    let sessions = gather::sessions(entries, max_gap);
+    let lengths = sessions
+        .iter()
+        .map(|session| gather::session_length(session).num_minutes())
+        .collect_vec();
+    let per_month = sessions
+        .iter()
+        .map(|session| session[0].timestamp.format("%Y-%m").to_string())
+        .dedup_with_count()
+        .map(|(count, month)| (month, count))
+        .collect_vec();
+
+    plot::sessions(
+        &rl.helper().unwrap().plot,
+        lengths,
+        per_month,
+        &format!("Listening sessions (max. {gap_minutes} minutes pause)"),
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot song versions` command
+fn match_plot_song_versions(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: artist name
+    let art = read_artist(rl, entries)?;
+
+    // 2nd prompt: song name
+    let versions = read_songs(rl, entries, &art)?;
+
+    let mut traces = versions
+        .iter()
+        .map(|song| trace::absolute(entries, song, None))
+        .collect_vec();
+    traces.push(trace::absolute_versions(entries, &versions));
+
+    plot::multiple(
+        &rl.helper().unwrap().plot,
+        traces,
+        &format!("{} - {} versions", art, versions[0].name),
+        true,
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot rank` command
+fn match_plot_rank(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: artist name
+    let art = read_artist(rl, entries)?;
+
+    // 2nd prompt: lifetime or monthly rank
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["lifetime", "month"]));
+    eprintln!("Rank by plays so far or by plays in each month?");
+    let usr_input_rank = rl.readline(PROMPT_SECONDARY)?;
+    let (lifetime, title) = match usr_input_rank.as_str() {
+        "lifetime" => (true, format!("{art} rank by plays so far")),
+        "month" => (false, format!("{art} rank by plays in each month")),
+        _ => return Err(UiError::InvalidArgument("lifetime, month")),
+    };
+
+    let ranks = gather::artist_ranks(entries, &art, lifetime)
+        .into_iter()
+        .map(|(month, rank)| (month.format("%Y-%m").to_string(), rank))
+        .collect_vec();
+
+    plot::rank(&rl.helper().unwrap().plot, ranks, &title);
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot discoveries` command
+///
+/// Plots how many new artists were discovered in each month,
+/// including months without any
+fn plot_discoveries(style: &plot::Style, entries: &SongEntries) {
+    let mut discoveries: HashMap<String, usize> = HashMap::new();
+    for entry in gather::artist_discoveries(entries) {
+        *discoveries
+            .entry(entry.timestamp.format("%Y-%m").to_string())
+            .or_default() += 1;
+    }
+
+    let last = entries.last_date().date_naive();
+    let mut month = entries.first_date().date_naive().with_day(1);
+    let mut per_month = vec![];
+    while let Some(date) = month.filter(|date| *date <= last) {
+        let label = date.format("%Y-%m").to_string();
+        let count = discoveries.get(&label).copied().unwrap_or_default();
+        per_month.push((label, count));
+        month = date.checked_add_months(chrono::Months::new(1));
+    }
+
+    plot::columns(style, per_month, "New artists per month", "new artists");
+}
+
+/// Used by [`match_input()`] for `plot library` command
+///
+/// Plots how many distinct artists, albums or songs had been listened to
+/// up to each date
+fn match_plot_library(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("Library growth of artists, albums or songs?");
+    let usr_input_asp = rl.readline(PROMPT_MAIN)?;
+    let aspect: Aspect = usr_input_asp.parse()?;
+
+    let title = format!("Library growth of {aspect}");
+    plot::single(
+        &rl.helper().unwrap().plot,
+        (trace::library_growth(entries, aspect), title),
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot gaps` command
+///
+/// Plots the days since the previous play of an artist at each of its plays
+fn match_plot_gaps(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: artist name
+    let art = read_artist(rl, entries)?;
+
+    let title = format!("{art} | gaps between plays");
+    plot::single(
+        &rl.helper().unwrap().plot,
+        (trace::gaps(entries, &art), title),
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot years` command
+///
+/// Overlays the plays in each month of every year of an aspect
+fn match_plot_years(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: per month or cumulative
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["month", "cumulative"]));
+    eprintln!("Plays per month or cumulative over each year?");
+    let usr_input_cumulative = rl.readline(PROMPT_SECONDARY)?;
+    let cumulative = match usr_input_cumulative.as_str() {
+        "month" => false,
+        "cumulative" => true,
+        _ => return Err(UiError::InvalidArgument("month, cumulative")),
+    };
+
+    // 2nd prompt: what to plot
+    rl.helper_mut().unwrap().complete_aspects();
+    eprintln!("What do you want to plot? artist, album or song?");
+    let usr_input_asp = rl.readline(PROMPT_SECONDARY)?;
+
+    // other prompts
+    let art = read_artist(rl, entries)?;
+    let (traces, name) = match usr_input_asp.as_str() {
+        "artist" => (trace::years(entries, &art, cumulative), art.to_string()),
+        "album" => {
+            let alb = read_album(rl, entries, &art)?;
+            (trace::years(entries, &alb, cumulative), alb.to_string())
+        }
+        "song" => {
+            let alb = read_album(rl, entries, &art)?;
+            let son = read_song(rl, entries, &alb)?;
+            (trace::years(entries, &son, cumulative), son.to_string())
+        }
+        _ => return Err(UiError::InvalidArgument("artist, album, song")),
+    };
+
+    plot::multiple(
+        &rl.helper().unwrap().plot,
+        traces,
+        &format!("{name} | year over year"),
+        // the x-axis is January to December, so no events
+        false,
+    );
+
+    Ok(())
+}
+
+/// Used by [`match_input()`] for `plot clock` command
+fn match_plot_clock(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // 1st prompt: all plays or an artist
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["all", "artist"]));
+    eprintln!("Clock of all plays or of an artist?");
+    let usr_input_of = rl.readline(PROMPT_SECONDARY)?;
+    let art = match usr_input_of.as_str() {
+        "all" => None,
+        // 2nd prompt: artist name
+        "artist" => Some(read_artist(rl, entries)?),
+        _ => return Err(UiError::InvalidArgument("all, artist")),
+    };
+
+    // last prompt: plays or minutes
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["plays", "minutes"]));
+    eprintln!("Plays or minutes?");
+    let usr_input_unit = rl.readline(PROMPT_SECONDARY)?;
+    let minutes = |hours: [TimeDelta; 24]| {
+        hours.map(|time| usize::try_from(time.num_minutes()).unwrap_or_default())
+    };
+    let hours = match (usr_input_unit.as_str(), &art) {
+        ("plays", None) => gather::plays_per_hour(entries),
+        ("plays", Some(art)) => gather::plays_per_hour_of(entries, art),
+        ("minutes", None) => minutes(gather::listening_time_per_hour(entries)),
+        ("minutes", Some(art)) => minutes(gather::listening_time_per_hour_of(entries, art)),
+        _ => return Err(UiError::InvalidArgument("plays, minutes")),
+    };
+
+    let unit = usr_input_unit.as_str();
+    let title = match art {
+        None => format!("{unit} per hour of the day"),
+        Some(art) => format!("{art} {unit} per hour of the day"),
+    };
+    plot::clock(&rl.helper().unwrap().plot, hours, &title, unit);
+
+    Ok(())
+}
+
+/// Used by [`match_plot_bar()`] and [`match_plot_share()`] to get the top `num` aspects
+/// as labels with their values (converted with `to_value`)
+fn top_bars<Asp: Music, V: Ord + Copy, T>(
+    music_map: HashMap<Asp, V>,
+    num: usize,
+    to_value: impl Fn(V) -> T,
+) -> Vec<(String, T)> {
+    music_map
+        .into_iter()
+        .sorted_unstable_by_key(|(asp, value)| (std::cmp::Reverse(*value), asp.clone()))
+        .take(num)
+        .map(|(asp, value)| (asp.to_string(), to_value(value)))
+        .collect_vec()
+}
+
+/// Used by [`match_input()`] for `plot artist albums` command
+fn match_plot_artist_albums(
+    entries: &SongEntries,
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<(), UiError> {
+    // prompt: artist name
+    let art = read_artist(rl, entries)?;
+
+    let albums_map = gather::albums_from_artist(entries, &art);
+    let albums = albums_map
+        .iter()
+        .sorted_unstable_by_key(|t| (std::cmp::Reverse(t.1), t.0))
+        .map(|(aspect, _)| aspect)
+        .collect_vec();
+
+    let mut traces = vec![];
+    for (count, alb) in albums.into_iter().enumerate() {
+        let TraceType::Absolute(trace) = trace::absolute(entries, alb, None) else {
+            unreachable!()
+        };
+
+        let trace = trace
+            .legend_group_title(art.name.to_string())
+            .name(&alb.name);
+
+        // only the traces for the 3 albums with most plays are shown by default
+        let trace = if count < 3 {
+            trace
+        } else {
+            // others are hidden and have to be enabled manually
             trace.visible(plotly::common::Visible::LegendOnly)
         };
 
@@ -728,7 +2056,7 @@ fn match_plot_artist_albums(
 
     let title = format!("{art} albums");
 
-    plot::multiple(traces, &title);
+    plot::multiple(&rl.helper().unwrap().plot, traces, &title, true);
 
     Ok(())
 }
@@ -740,39 +2068,221 @@ fn get_traces<Asp: Music>(
     entries: &SongEntries,
     music_map: &HashMap<Asp, usize>,
     num: usize,
+    options: TraceOptions,
 ) -> Vec<TraceType> {
     music_map
         .iter()
         .sorted_unstable_by_key(|t| (std::cmp::Reverse(t.1), t.0))
         .take(num)
-        .map(|(aspect, _)| trace::absolute(entries, aspect))
+        .flat_map(|(aspect, _)| absolute_traces(entries, aspect, options))
         .collect_vec()
 }
 
+/// How absolute traces are plotted, see [`read_trace_options()`]
+#[derive(Clone, Copy)]
+struct TraceOptions {
+    /// `None` for cumulative traces, otherwise the [`Bin`][series::Bin]
+    bin: Option<series::Bin>,
+    /// Whether to sum up minutes (`true`) or plays (`false`)
+    minutes: bool,
+    /// One point per play if `None`, otherwise per [`Bin`][series::Bin]
+    ///
+    /// Only used for cumulative traces
+    granularity: Option<series::Bin>,
+    /// Days of the [rolling average][trace::rolling_average] overlaid
+    /// on each trace, `None` for no overlay
+    average: Option<usize>,
+}
+
+/// Asks whether to plot cumulative (and with which granularity) or binned traces,
+/// and whether to overlay a rolling average of daily plays (or minutes)
+///
+/// Empty inputs keep the plain plot: cumulative plays with one point
+/// per play and no average
+fn read_trace_options(rl: &mut Editor<ShellHelper, FileHistory>) -> Result<TraceOptions, UiError> {
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["cumulative", "day", "week", "month"]));
+    eprintln!("Cumulative or per day, week or month? (leave empty for cumulative)");
+    let usr_input_bin = rl.readline(PROMPT_SECONDARY)?;
+    let bin = match usr_input_bin.as_str() {
+        "" | "cumulative" => None,
+        "day" => Some(series::Bin::Day),
+        "week" => Some(series::Bin::Week),
+        "month" => Some(series::Bin::Month),
+        _ => return Err(UiError::InvalidArgument("cumulative, day, week, month")),
+    };
+
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["plays", "minutes"]));
+    eprintln!("Plays or minutes? (leave empty for plays)");
+    let usr_input_unit = rl.readline(PROMPT_SECONDARY)?;
+    let minutes = match usr_input_unit.as_str() {
+        "" | "plays" => false,
+        "minutes" => true,
+        _ => return Err(UiError::InvalidArgument("plays, minutes")),
+    };
+
+    let granularity = match bin {
+        Some(_) => None,
+        None => read_granularity(rl)?,
+    };
+
+    rl.helper_mut().unwrap().complete_list(string_vec(&["30"]));
+    let unit = if minutes { "minutes" } else { "plays" };
+    eprintln!("Overlay a rolling average of daily {unit} over how many days? (e.g. 30, leave empty for none)");
+    let usr_input_average = rl.readline(PROMPT_SECONDARY)?;
+    let average = match usr_input_average.as_str() {
+        "" | "0" => None,
+        days => Some(days.parse()?),
+    };
+
+    Ok(TraceOptions {
+        bin,
+        minutes,
+        granularity,
+        average,
+    })
+}
+
+/// Creates an [absolute][trace::absolute] (or [duration][trace::absolute_duration])
+/// trace if [`TraceOptions::bin`] is `None`, otherwise a [binned][trace::binned] one
+///
+/// Followed by a [rolling average][trace::rolling_average] trace
+/// in the same unit if [`TraceOptions::average`] is set
+fn absolute_traces<Asp: Music>(
+    entries: &SongEntries,
+    aspect: &Asp,
+    options: TraceOptions,
+) -> Vec<TraceType> {
+    let mut traces = vec![match (options.bin, options.minutes) {
+        (Some(bin), minutes) => trace::binned(entries, aspect, bin, minutes),
+        (None, true) => trace::absolute_duration(entries, aspect, options.granularity),
+        (None, false) => trace::absolute(entries, aspect, options.granularity),
+    }];
+    if let Some(days) = options.average {
+        traces.push(trace::rolling_average(
+            entries,
+            aspect,
+            days,
+            options.minutes,
+        ));
+    }
+    traces
+}
+
 /// Used to get traces of absolute plots
 fn get_absolute_trace(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
     usr_input: &str,
-) -> Result<(Box<Scatter<String, usize>>, String), UiError> {
+    options: TraceOptions,
+) -> Result<(Vec<TraceType>, String), UiError> {
     match usr_input {
-        "artist" => match_plot_artist(entries, rl),
-        "album" => match_plot_album(entries, rl),
-        "song" => match_plot_song(entries, rl),
+        "artist" => match_plot_artist(entries, rl, options),
+        "album" => match_plot_album(entries, rl, options),
+        "song" => match_plot_song(entries, rl, options),
         _ => Err(UiError::InvalidArgument("artist, album, song")),
     }
 }
 
+/// Asks whether cumulative traces should have one point per play
+/// or only one per day, week or month
+///
+/// Returns `None` for the former, otherwise the [`Bin`][series::Bin]
+fn read_granularity(
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<Option<series::Bin>, UiError> {
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["play", "day", "week", "month"]));
+    eprintln!("One point per play or per day, week or month? (leave empty for per play)");
+    let usr_input_granularity = rl.readline(PROMPT_SECONDARY)?;
+    match usr_input_granularity.as_str() {
+        "" | "play" => Ok(None),
+        "day" => Ok(Some(series::Bin::Day)),
+        "week" => Ok(Some(series::Bin::Week)),
+        "month" => Ok(Some(series::Bin::Month)),
+        _ => Err(UiError::InvalidArgument("play, day, week, month")),
+    }
+}
+
+/// Asks over how many plays relative traces should be averaged
+///
+/// Returns `None` if they shouldn't be smoothed
+fn read_smoothing(
+    rl: &mut Editor<ShellHelper, FileHistory>,
+) -> Result<Option<series::Smoothing>, UiError> {
+    rl.helper_mut().unwrap().complete_list(string_vec(&["50"]));
+    eprintln!("Smooth over how many plays? (e.g. 50, leave empty for none)");
+    let usr_input_smoothing = rl.readline(PROMPT_SECONDARY)?;
+    match usr_input_smoothing.as_str() {
+        "" | "0" | "1" => Ok(None),
+        plays => Ok(Some(series::Smoothing::Window(plays.parse()?))),
+    }
+}
+
+/// What relative traces are relative to, see [`read_relative_period()`]
+#[derive(Clone, Copy)]
+enum Period {
+    /// all plays so far
+    Cumulative {
+        /// one point per play if `None`, otherwise per [`Bin`][series::Bin]
+        granularity: Option<series::Bin>,
+        /// how the jagged start of the trace is smoothed, if at all
+        smoothing: Option<series::Smoothing>,
+    },
+    /// all plays within each [`Bin`][series::Bin]
+    Each(series::Bin),
+}
+
+/// Asks whether relative traces should be relative to all plays so far
+/// (and with which granularity and smoothing) or to all plays within each day, week or month
+fn read_relative_period(rl: &mut Editor<ShellHelper, FileHistory>) -> Result<Period, UiError> {
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["cumulative", "day", "week", "month"]));
+    eprintln!("Share of all plays so far or of the plays within each day, week or month?");
+    let usr_input_period = rl.readline(PROMPT_SECONDARY)?;
+    match usr_input_period.as_str() {
+        "cumulative" => Ok(Period::Cumulative {
+            granularity: read_granularity(rl)?,
+            smoothing: read_smoothing(rl)?,
+        }),
+        "day" => Ok(Period::Each(series::Bin::Day)),
+        "week" => Ok(Period::Each(series::Bin::Week)),
+        "month" => Ok(Period::Each(series::Bin::Month)),
+        _ => Err(UiError::InvalidArgument("cumulative, day, week, month")),
+    }
+}
+
+/// Creates a trace [relative to all plays][trace::relative::to_all]
+/// or [to all plays of each period][trace::relative::to_period]
+fn relative_to_all<Asp: Music>(entries: &SongEntries, aspect: &Asp, period: Period) -> TraceType {
+    match period {
+        Period::Cumulative {
+            granularity,
+            smoothing,
+        } => trace::relative::to_all(entries, aspect, granularity, smoothing),
+        Period::Each(bin) => trace::relative::to_period(entries, aspect, bin),
+    }
+}
+
 /// Used to get traces of relative plots
+///
+/// The prompt for what the trace is relative to is skipped
+/// if `period` is [`Period::Each`], see [`read_relative_period()`]
 fn get_relative_trace(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
     usr_input: &str,
+    period: Period,
 ) -> Result<(Box<Scatter<String, f64>>, String), UiError> {
     match usr_input {
-        "artist" => match_plot_artist_relative(entries, rl),
-        "album" => match_plot_album_relative(entries, rl),
-        "song" => match_plot_song_relative(entries, rl),
+        "artist" => match_plot_artist_relative(entries, rl, period),
+        "album" => match_plot_album_relative(entries, rl, period),
+        "song" => match_plot_song_relative(entries, rl, period),
         _ => Err(UiError::InvalidArgument("artist, album, song")),
     }
 }
@@ -781,40 +2291,35 @@ fn get_relative_trace(
 fn match_plot_artist(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
-) -> Result<(Box<Scatter<String, usize>>, String), UiError> {
+    options: TraceOptions,
+) -> Result<(Vec<TraceType>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
-    if let TraceType::Absolute(trace) = trace::absolute(entries, &art) {
-        Ok((trace, art.to_string()))
-    } else {
-        unreachable!()
-    }
+    Ok((absolute_traces(entries, &art, options), art.to_string()))
 }
 
 /// Used by [`match_plot()`] for plotting absolute plays of album
 fn match_plot_album(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
-) -> Result<(Box<Scatter<String, usize>>, String), UiError> {
+    options: TraceOptions,
+) -> Result<(Vec<TraceType>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
     // 2nd prompt: album name
     let alb = read_album(rl, entries, &art)?;
 
-    if let TraceType::Absolute(trace) = trace::absolute(entries, &alb) {
-        Ok((trace, alb.to_string()))
-    } else {
-        unreachable!()
-    }
+    Ok((absolute_traces(entries, &alb, options), alb.to_string()))
 }
 
 /// Used by [`match_plot()`] for plotting absolute plays of song
 fn match_plot_song(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
-) -> Result<(Box<Scatter<String, usize>>, String), UiError> {
+    options: TraceOptions,
+) -> Result<(Vec<TraceType>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
@@ -824,22 +2329,41 @@ fn match_plot_song(
     // 3rd prompt: song name
     let son = read_song(rl, entries, &alb)?;
 
-    if let TraceType::Absolute(trace) = trace::absolute(entries, &son) {
-        Ok((trace, son.to_string()))
-    } else {
-        unreachable!()
-    }
+    Ok((absolute_traces(entries, &son, options), son.to_string()))
 }
 
 /// Used by [`match_plot_relative()`] for plotting relative plots of artist
 fn match_plot_artist_relative(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
+    period: Period,
 ) -> Result<(Box<Scatter<String, f64>>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
 
-    let trace = trace::relative::to_all(entries, &art);
+    let trace = if let Period::Cumulative {
+        granularity,
+        smoothing,
+    } = period
+    {
+        // 2nd prompt: relative to what
+        rl.helper_mut()
+            .unwrap()
+            .complete_list(string_vec(&["all", "group"]));
+        eprintln!("Relative to all or a group of artists?");
+        let usr_input_rel = rl.readline(PROMPT_SECONDARY)?;
+
+        match usr_input_rel.as_str() {
+            "all" => trace::relative::to_all(entries, &art, granularity, smoothing),
+            "group" => {
+                let group = read_artist_group(rl, entries)?;
+                trace::relative::to_aspects(entries, &art, &group, granularity, smoothing)
+            }
+            _ => return Err(UiError::InvalidArgument("all, group")),
+        }
+    } else {
+        relative_to_all(entries, &art, period)
+    };
 
     if let TraceType::Relative(trace) = trace {
         Ok((trace, art.to_string()))
@@ -852,6 +2376,7 @@ fn match_plot_artist_relative(
 fn match_plot_album_relative(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
+    period: Period,
 ) -> Result<(Box<Scatter<String, f64>>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
@@ -859,17 +2384,29 @@ fn match_plot_album_relative(
     // 2nd prompt: album name
     let alb = read_album(rl, entries, &art)?;
 
-    // 3rd prompt: relative to what
-    rl.helper_mut()
-        .unwrap()
-        .complete_list(string_vec(&["all", "artist"]));
-    println!("Relative to all or artist?");
-    let usr_input_rel = rl.readline(PROMPT_SECONDARY)?;
-
-    let trace = match usr_input_rel.as_str() {
-        "all" => trace::relative::to_all(entries, &alb),
-        "artist" => trace::relative::to_artist(entries, &alb),
-        _ => return Err(UiError::InvalidArgument("all, artist")),
+    let trace = if let Period::Cumulative {
+        granularity,
+        smoothing,
+    } = period
+    {
+        // 3rd prompt: relative to what
+        rl.helper_mut()
+            .unwrap()
+            .complete_list(string_vec(&["all", "artist", "group"]));
+        eprintln!("Relative to all, artist or a group of artists?");
+        let usr_input_rel = rl.readline(PROMPT_SECONDARY)?;
+
+        match usr_input_rel.as_str() {
+            "all" => trace::relative::to_all(entries, &alb, granularity, smoothing),
+            "artist" => trace::relative::to_artist(entries, &alb, granularity, smoothing),
+            "group" => {
+                let group = read_artist_group(rl, entries)?;
+                trace::relative::to_aspects(entries, &alb, &group, granularity, smoothing)
+            }
+            _ => return Err(UiError::InvalidArgument("all, artist, group")),
+        }
+    } else {
+        relative_to_all(entries, &alb, period)
     };
 
     if let TraceType::Relative(trace) = trace {
@@ -883,6 +2420,7 @@ fn match_plot_album_relative(
 fn match_plot_song_relative(
     entries: &SongEntries,
     rl: &mut Editor<ShellHelper, FileHistory>,
+    period: Period,
 ) -> Result<(Box<Scatter<String, f64>>, String), UiError> {
     // 1st prompt: artist name
     let art = read_artist(rl, entries)?;
@@ -893,19 +2431,31 @@ fn match_plot_song_relative(
     // 3rd prompt: song name
     let son = read_song(rl, entries, &alb)?;
 
-    // 4th prompt: relative to what
-    rl.helper_mut()
-        .unwrap()
-        .complete_list(string_vec(&["all", "artist", "album"]));
-
-    println!("Relative to all, artist or album?");
-    let usr_input_rel = rl.readline(PROMPT_SECONDARY)?;
-
-    let trace = match usr_input_rel.as_str() {
-        "all" => trace::relative::to_all(entries, &son),
-        "artist" => trace::relative::to_artist(entries, &son),
-        "album" => trace::relative::to_album(entries, &son),
-        _ => return Err(UiError::InvalidArgument("all, artist, album")),
+    let trace = if let Period::Cumulative {
+        granularity,
+        smoothing,
+    } = period
+    {
+        // 4th prompt: relative to what
+        rl.helper_mut()
+            .unwrap()
+            .complete_list(string_vec(&["all", "artist", "album", "group"]));
+
+        eprintln!("Relative to all, artist, album or a group of artists?");
+        let usr_input_rel = rl.readline(PROMPT_SECONDARY)?;
+
+        match usr_input_rel.as_str() {
+            "all" => trace::relative::to_all(entries, &son, granularity, smoothing),
+            "artist" => trace::relative::to_artist(entries, &son, granularity, smoothing),
+            "album" => trace::relative::to_album(entries, &son, granularity, smoothing),
+            "group" => {
+                let group = read_artist_group(rl, entries)?;
+                trace::relative::to_aspects(entries, &son, &group, granularity, smoothing)
+            }
+            _ => return Err(UiError::InvalidArgument("all, artist, album, group")),
+        }
+    } else {
+        relative_to_all(entries, &son, period)
     };
 
     if let TraceType::Relative(trace) = trace {
@@ -915,22 +2465,51 @@ fn match_plot_song_relative(
     }
 }
 
+/// Used by relative plots for reading a group of artists
+/// the trace is relative to, see [`trace::relative::to_aspects`]
+fn read_artist_group(
+    rl: &mut Editor<ShellHelper, FileHistory>,
+    entries: &SongEntries,
+) -> Result<Vec<Artist>, UiError> {
+    rl.helper_mut().unwrap().complete_list(entries.artists());
+    eprintln!("Comma-separated list of the artists in the group?");
+    let usr_input_group = rl.readline(PROMPT_MAIN)?;
+
+    let group = usr_input_group
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            entries
+                .find()
+                .artist(name)
+                .ok_or(UiError::NotFound("artist"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if group.is_empty() {
+        return Err(UiError::TooFewAspects("one artist"));
+    }
+
+    Ok(group)
+}
+
 /// Used by `*_date` functions for reading start and end dates from user
 ///
 /// Returns `(start_date, end_date)`
 fn read_dates(
     rl: &mut Editor<ShellHelper, FileHistory>,
+    entries: &SongEntries,
 ) -> Result<(DateTime<Local>, DateTime<Local>), UiError> {
-    // make sure no wrong autocompletes appear
-    rl.helper_mut().unwrap().reset();
-
     // 1st prompt: start date
-    println!("Start date? YYYY-MM-DD or 'start'");
+    rl.helper_mut().unwrap().complete_dates(entries, "start");
+    eprintln!("Start date? YYYY-MM-DD or 'start'");
     let usr_input_start_date = rl.readline(PROMPT_SECONDARY)?;
     let start_date = parse_date(&usr_input_start_date)?;
 
     // 2nd prompt: end date
-    println!("End date? YYYY-MM-DD or 'now'");
+    rl.helper_mut().unwrap().complete_dates(entries, "now");
+    eprintln!("End date? YYYY-MM-DD or 'now'");
     let usr_input_end_date = rl.readline(PROMPT_SECONDARY)?;
     let end_date = parse_date(&usr_input_end_date)?;
 
@@ -940,6 +2519,32 @@ fn read_dates(
     Ok((start_date, end_date))
 }
 
+/// Used by `match_*` functions for asking whether the plays
+/// of songs from different albums should be summed up
+fn read_sum_songs(rl: &mut Editor<ShellHelper, FileHistory>) -> Result<bool, UiError> {
+    read_yes_no(rl, "Do you want to sum songs from different albums?")
+}
+
+/// Used by `match_*` functions for asking a yes/no `question`
+///
+/// Invalid input is treated as 'no'
+fn read_yes_no(rl: &mut Editor<ShellHelper, FileHistory>, question: &str) -> Result<bool, UiError> {
+    rl.helper_mut()
+        .unwrap()
+        .complete_list(string_vec(&["yes", "y", "no", "n"]));
+    eprintln!("{question} (y/n)");
+    let usr_input_b = rl.readline(PROMPT_SECONDARY)?;
+    let answer = match usr_input_b.as_str() {
+        "yes" | "y" => true,
+        "no" | "n" => false,
+        _ => {
+            eprintln!("Invalid input. Assuming 'no'.");
+            false
+        }
+    };
+    Ok(answer)
+}
+
 /// Used by `match_*` functions for finding [`Artist`] from user input
 fn read_artist(
     rl: &mut Editor<ShellHelper, FileHistory>,
@@ -947,7 +2552,20 @@ fn read_artist(
 ) -> Result<Artist, UiError> {
     // prompt: artist name
     rl.helper_mut().unwrap().complete_list(entries.artists());
-    println!("Artist name?");
+    eprintln!("Artist name?");
+    let usr_input_art = rl.readline(PROMPT_MAIN)?;
+    if let Some(art) = entries.find().artist(&usr_input_art) {
+        return Ok(art);
+    }
+
+    // fallback: offer similarly named artists (e.g. in case of a typo)
+    let suggestions = entries.find().artists_fuzzy(&usr_input_art, 3);
+    if suggestions.is_empty() {
+        return Err(UiError::NotFound("artist"));
+    }
+    let names = suggestions.into_iter().map(|art| art.name).collect_vec();
+    eprintln!("Did you mean: {}?", names.join(", "));
+    rl.helper_mut().unwrap().complete_list(names);
     let usr_input_art = rl.readline(PROMPT_MAIN)?;
     entries
         .find()
@@ -963,7 +2581,7 @@ fn read_album(
 ) -> Result<Album, UiError> {
     // prompt: album name
     rl.helper_mut().unwrap().complete_list(entries.albums(art));
-    println!("Album name?");
+    eprintln!("Album name?");
     let usr_input_alb = rl.readline(PROMPT_MAIN)?;
     entries
         .find()
@@ -979,7 +2597,7 @@ fn read_song(
 ) -> Result<Song, UiError> {
     // prompt: song name
     rl.helper_mut().unwrap().complete_list(entries.songs(alb));
-    println!("Song name?");
+    eprintln!("Song name?");
     let usr_input_son = rl.readline(PROMPT_MAIN)?;
     entries
         .find()
@@ -995,7 +2613,7 @@ fn read_songs(
 ) -> Result<Vec<Song>, UiError> {
     // prompt: song name
     rl.helper_mut().unwrap().complete_list(entries.songs(art));
-    println!("Song name?");
+    eprintln!("Song name?");
     let usr_input_son = rl.readline(PROMPT_MAIN)?;
     entries
         .find()
@@ -49,7 +49,7 @@ use std::cmp::Ordering;
 use std::fmt::Display;
 use std::rc::Rc;
 
-use crate::entry::SongEntry;
+use crate::entry::{is_folded, SongEntry};
 
 /// Used for functions that accept either
 /// a [`Song`], [`Album`] or [`Artist`] struct
@@ -59,7 +59,8 @@ pub trait Music: Display + Clone + Eq + Ord {
 
     /// Checks if a [`SongEntry`] is a [`Music`] but case insensitive
     ///
-    /// Performs `.to_lowercase()` on both `entry` and on [`self`].
+    /// Compares the case-folded names the `entry` got while parsing
+    /// with the lowercase names of [`self`] without allocating
     fn is_entry_lowercase(&self, entry: &SongEntry) -> bool;
 }
 
@@ -143,7 +144,7 @@ impl Music for Artist {
         entry.artist == self.name
     }
     fn is_entry_lowercase(&self, entry: &SongEntry) -> bool {
-        entry.artist.to_lowercase() == self.name.to_lowercase()
+        is_folded(&entry.folded_artist, &self.name)
     }
 }
 impl HasSongs for Artist {}
@@ -243,8 +244,8 @@ impl Music for Album {
         entry.artist == self.artist.name && entry.album == self.name
     }
     fn is_entry_lowercase(&self, entry: &SongEntry) -> bool {
-        entry.artist.to_lowercase() == self.artist.name.to_lowercase()
-            && entry.album.to_lowercase() == self.name.to_lowercase()
+        is_folded(&entry.folded_artist, &self.artist.name)
+            && is_folded(&entry.folded_album, &self.name)
     }
 }
 impl HasSongs for Album {}
@@ -353,9 +354,9 @@ impl Music for Song {
             && entry.track == self.name
     }
     fn is_entry_lowercase(&self, entry: &SongEntry) -> bool {
-        entry.artist.to_lowercase() == self.album.artist.name.to_lowercase()
-            && entry.album.to_lowercase() == self.album.name.to_lowercase()
-            && entry.track.to_lowercase() == self.name.to_lowercase()
+        is_folded(&entry.folded_artist, &self.album.artist.name)
+            && is_folded(&entry.folded_album, &self.album.name)
+            && is_folded(&entry.folded_track, &self.name)
     }
 }
 
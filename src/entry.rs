@@ -15,7 +15,8 @@
 //!     .filter(30, TimeDelta::seconds(10));
 //! ```
 
-use std::collections::HashMap;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::rc::Rc;
 
@@ -24,12 +25,13 @@ use itertools::Itertools;
 use tracing::info;
 
 use crate::aspect;
+use crate::export;
 use crate::find;
 use crate::gather;
 use crate::parse;
 
 use aspect::{Album, Artist, HasSongs, Music, Song};
-use parse::{parse, ParseError};
+use parse::{parse, parse_readers, ParseError};
 
 /// A representation of a single song stream in endsong.json
 /// utilized by many functions here.
@@ -52,6 +54,19 @@ pub struct SongEntry {
     pub artist: Rc<str>,
     /// Spotify URI
     pub id: String,
+    /// class of the device the song has been played on
+    pub platform: Platform,
+    /// case-folded `track`, see [`fold_case`]
+    ///
+    /// Like the names, each one is only stored once and shared by all entries
+    /// with it (and with the name itself if it's already lowercase).
+    /// Changing the names through [`DerefMut`][std::ops::DerefMut] of
+    /// [`SongEntries`] doesn't update it
+    pub(crate) folded_track: Rc<str>,
+    /// case-folded `album`, see [`fold_case`]
+    pub(crate) folded_album: Rc<str>,
+    /// case-folded `artist`, see [`fold_case`]
+    pub(crate) folded_artist: Rc<str>,
 }
 /// Equal if `artist`, `album` and `track` name are the same
 impl PartialEq for SongEntry {
@@ -89,6 +104,115 @@ impl PartialOrd for SongEntry {
     }
 }
 
+/// Returns the case-folded (lowercase) version of `name`
+///
+/// Used for the case-folded names of a [`SongEntry`], which are created
+/// once per name while parsing, and for the queries compared with them
+pub(crate) fn fold_case(name: &str) -> String {
+    name.chars().flat_map(char::to_lowercase).collect()
+}
+
+/// Returns whether `name` folded with [`fold_case`] is `folded`
+/// without allocating a new string
+pub(crate) fn is_folded(folded: &str, name: &str) -> bool {
+    folded.chars().eq(name.chars().flat_map(char::to_lowercase))
+}
+
+/// Class of the device a [`SongEntry`] has been played on
+///
+/// Derived from the `platform` field in endsong.json, which
+/// contains e.g. `"Android OS 10 API 29 (Xiaomi, POCOPHONE F1)"`
+/// or `"web_player windows 10;chrome 88.0.4324.150;desktop"`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Platform {
+    /// Android phones and tablets
+    Android,
+    /// iPhones and iPads
+    Ios,
+    /// Windows, macOS and Linux desktop apps
+    Desktop,
+    /// Spotify web player in the browser
+    WebPlayer,
+    /// Sonos, Chromecast, Amazon Echo and similar
+    SmartSpeaker,
+    /// everything else (TVs, cars, game consoles...)
+    Other,
+}
+impl Platform {
+    /// Determines the device class from the raw `platform`
+    /// string of an endsong.json entry
+    ///
+    /// Matches whole words, so that e.g. `"bios"` isn't seen as iOS
+    /// or `"podcast"` as a Chromecast
+    #[must_use]
+    pub fn from_raw(raw: &str) -> Platform {
+        let raw = raw.to_lowercase();
+        // "iOS 14.2 (iPad11,6)" -> ["ios", "ipad"]
+        let words = raw
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|word| !word.is_empty())
+            .collect_vec();
+        let contains_any = |patterns: &[&str]| words.iter().any(|word| patterns.contains(word));
+        let contains_pair = |first: &str, second: &str| {
+            words
+                .windows(2)
+                .any(|pair| pair[0] == first && pair[1] == second)
+        };
+
+        // web player first, because it also contains the OS it ran on
+        if raw.starts_with("web_player") {
+            Platform::WebPlayer
+        } else if contains_any(&["sonos", "cast", "chromecast", "echo", "alexa", "speaker"])
+            || contains_pair("google", "home")
+        {
+            Platform::SmartSpeaker
+        } else if contains_any(&["android"]) {
+            Platform::Android
+        } else if contains_any(&["ios", "iphone", "ipad"]) {
+            Platform::Ios
+        } else if contains_any(&["windows", "osx", "macos", "linux"]) || contains_pair("os", "x") {
+            Platform::Desktop
+        } else {
+            Platform::Other
+        }
+    }
+}
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Platform::Android => write!(f, "Android"),
+            Platform::Ios => write!(f, "iOS"),
+            Platform::Desktop => write!(f, "Desktop"),
+            Platform::WebPlayer => write!(f, "Web Player"),
+            Platform::SmartSpeaker => write!(f, "Smart Speaker"),
+            Platform::Other => write!(f, "Other"),
+        }
+    }
+}
+
+/// Approximate memory usage of [`SongEntries`] in bytes
+///
+/// Created with [`SongEntries::memory_stats`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// the [`SongEntry`]s themselves, including their Spotify URIs
+    pub entries: usize,
+    /// the artist, album and song names and their case-folded versions -
+    /// each name is only stored once and shared by all entries with it
+    pub names: usize,
+    /// the [song durations][SongEntries::durations] (`0` if they haven't been used yet)
+    pub durations: usize,
+    /// the indices used by [`Find`] (`0` if they haven't been used yet)
+    pub indices: usize,
+}
+impl MemoryStats {
+    /// Returns the sum of all parts
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.entries + self.names + self.durations + self.indices
+    }
+}
+
 /// Struct containing a vector of [`SongEntry`]s and a map of [`Song`]s with their [`TimeDelta`]s
 ///
 /// Fundamental for the use of this program
@@ -106,15 +230,23 @@ impl PartialOrd for SongEntry {
 ///     println!("{entry:?}");
 /// }
 ///
-/// // entries.durations is a HashMap<Song, TimeDelta>
+/// // entries.durations() is a &HashMap<Song, TimeDelta>
 /// let song = Song::new("STYX HELIX", "eYe's", "MYTH & ROID");
-/// let duration: TimeDelta = entries.durations.get(&song)?;
+/// let duration: TimeDelta = entries.durations().get(&song)?;
 /// ```
 pub struct SongEntries {
     /// Vector of [`SongEntry`]s
     entries: Vec<SongEntry>,
-    /// Map of [`Song`]s with their [durations][TimeDelta]
-    pub durations: HashMap<Song, TimeDelta>,
+    /// Map of [`Song`]s with their [durations][TimeDelta],
+    /// computed on first access in [`SongEntries::durations`]
+    durations: OnceCell<SongDurations>,
+    /// Map of [`Song`]s with durations from an external source
+    /// (see [`SongEntries::with_durations`]) which aren't recalculated -
+    /// outside of [`SongEntries::durations`] so that changing the entries keeps them
+    known: HashMap<Song, TimeDelta>,
+    /// Case-folded name indices used by [`Find`],
+    /// computed on first access in [`SongEntries::index`]
+    index: OnceCell<find::Index>,
 }
 impl SongEntries {
     /// Creates an instance of [`SongEntries`]
@@ -122,16 +254,44 @@ impl SongEntries {
     /// # Arguments
     ///
     /// * `paths` - a slice of [`Paths`][`Path`] to each `endsong.json` file.
-    /// Those can be [`Strings`][String], [`strs`][str], [`PathBufs`][std::path::PathBuf]
-    /// or whatever implements [`AsRef<Path>`]
+    ///   Those can be [`Strings`][String], [`strs`][str], [`PathBufs`][std::path::PathBuf]
+    ///   or whatever implements [`AsRef<Path>`]
     ///
     /// # Errors
     ///
     /// Will return an error if any of the files can't be opened or read
     pub fn new<P: AsRef<Path> + std::fmt::Debug>(paths: &[P]) -> Result<SongEntries, ParseError> {
         let entries = parse(paths)?;
-        let durations = song_durations(&entries);
-        Ok(SongEntries { entries, durations })
+        Ok(SongEntries {
+            entries,
+            durations: OnceCell::new(),
+            known: HashMap::new(),
+            index: OnceCell::new(),
+        })
+    }
+
+    /// Creates an instance of [`SongEntries`] from the contents
+    /// of `endsong.json` files, e.g. uploaded ones or from [`std::io::stdin`]
+    ///
+    /// # Arguments
+    ///
+    /// * `readers` - the contents of each `endsong.json` file,
+    ///   e.g. [`Files`][std::fs::File] or [`&[u8]`][slice]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if any of the readers can't be read
+    /// or doesn't contain a valid `endsong.json`
+    pub fn from_readers<R: std::io::Read>(
+        readers: impl IntoIterator<Item = R>,
+    ) -> Result<SongEntries, ParseError> {
+        let entries = parse_readers(readers)?;
+        Ok(SongEntries {
+            entries,
+            durations: OnceCell::new(),
+            known: HashMap::new(),
+            index: OnceCell::new(),
+        })
     }
 
     /// Sometimes an artist changes the capitalization of their album
@@ -155,21 +315,21 @@ impl SongEntries {
         // 1st: Albums
         // if it's from the same artist and has the same name
         // but different capitalization it's the same album
-        let albums = self.iter().map(Album::from).unique().collect_vec();
-
-        // key: (artist, lowercase album name), value: all album names
-        let mut album_versions: HashMap<(Artist, String), Vec<Rc<str>>> = HashMap::new();
+        // with the case-folded album name the entry got while parsing
+        let albums = self
+            .iter()
+            .unique_by(|entry| Album::from(*entry))
+            .map(|entry| (Album::from(entry), Rc::clone(&entry.folded_album)))
+            .collect_vec();
 
-        for alb in &albums {
-            let lowercase = alb.name.to_lowercase();
-            let artist = Artist::from(alb);
+        // key: (artist, case-folded album name), value: all album names
+        let mut album_versions: HashMap<(Artist, Rc<str>), Vec<Rc<str>>> = HashMap::new();
 
-            match album_versions.get_mut(&(artist.clone(), lowercase.clone())) {
-                Some(vec) => vec.push(Rc::clone(&alb.name)),
-                None => {
-                    album_versions.insert((artist, lowercase), vec![Rc::clone(&alb.name)]);
-                }
-            }
+        for (alb, folded) in &albums {
+            album_versions
+                .entry((Artist::from(alb), Rc::clone(folded)))
+                .or_default()
+                .push(Rc::clone(&alb.name));
         }
 
         // the last album in the vector is the one that will be kept
@@ -177,11 +337,9 @@ impl SongEntries {
         // key: albym, value: newest album name
         let mut album_mappings: HashMap<Album, Rc<str>> = HashMap::new();
 
-        for alb in albums {
+        for (alb, folded) in albums {
             let artist = Artist::from(&alb);
-            let versions = album_versions
-                .get(&(artist, alb.name.to_lowercase()))
-                .unwrap();
+            let versions = album_versions.get(&(artist, folded)).unwrap();
 
             if versions.len() < 2 {
                 continue;
@@ -201,21 +359,20 @@ impl SongEntries {
         // if it's from the same artist, has the same album and has the same name
         // but different capitalization it's the same song
         // !! doing this after the iteration of changing album names !!
-        let songs = self.iter().map(Song::from).unique().collect_vec();
-
-        // key: (album, lowercase song name), value: all song names
-        let mut song_versions: HashMap<(Album, String), Vec<Rc<str>>> = HashMap::new();
+        let songs = self
+            .iter()
+            .unique_by(|entry| Song::from(*entry))
+            .map(|entry| (Song::from(entry), Rc::clone(&entry.folded_track)))
+            .collect_vec();
 
-        for song in &songs {
-            let lowercase = song.name.to_lowercase();
-            let album = Album::from(song);
+        // key: (album, case-folded song name), value: all song names
+        let mut song_versions: HashMap<(Album, Rc<str>), Vec<Rc<str>>> = HashMap::new();
 
-            match song_versions.get_mut(&(album.clone(), lowercase.clone())) {
-                Some(vec) => vec.push(Rc::clone(&song.name)),
-                None => {
-                    song_versions.insert((album, lowercase), vec![Rc::clone(&song.name)]);
-                }
-            }
+        for (song, folded) in &songs {
+            song_versions
+                .entry((Album::from(song), Rc::clone(folded)))
+                .or_default()
+                .push(Rc::clone(&song.name));
         }
 
         // the last songs in the vector is the one that will be kept
@@ -223,11 +380,9 @@ impl SongEntries {
         // key: song, value: newest song name
         let mut song_mappings: HashMap<Song, Rc<str>> = HashMap::new();
 
-        for song in songs {
+        for (song, folded) in songs {
             let album = Album::from(&song);
-            let versions = song_versions
-                .get(&(album, song.name.to_lowercase()))
-                .unwrap();
+            let versions = song_versions.get(&(album, folded)).unwrap();
 
             if versions.len() < 2 {
                 continue;
@@ -244,7 +399,8 @@ impl SongEntries {
         }
 
         // has to be done because some songs change album capitalization
-        self.durations = song_durations(&self);
+        self.durations.take();
+        self.index.take();
 
         self
     }
@@ -262,6 +418,9 @@ impl SongEntries {
     /// `absolute_threshold` - all songs below this [`TimeDelta`]
     /// will be filtered out; a good default is `TimeDelta::seconds(10)`
     ///
+    /// Afterwards [`SongEntries::durations`] only take the remaining entries
+    /// into account (updated only for the songs that had entries filtered out)
+    ///
     /// # Panics
     ///
     /// Will panic if `threshhold` is below 0 or above 100
@@ -274,21 +433,100 @@ impl SongEntries {
             "Threshold has to be between 0 and 100"
         );
 
+        let entries = std::mem::take(&mut self.entries);
+        let durations = &self
+            .durations
+            .get_or_init(|| SongDurations::new(&entries, &self.known))
+            .durations;
+
         // discards every entry whose time_played is below the
         // threshhold percentage of its duration
-        self.entries.retain(|entry| {
-            // retain is supposed to preserve the order so I don't have to sort again?
-            let song = Song::from(entry);
-            let duration = *self.durations.get(&song).unwrap();
+        // partition preserves the order so I don't have to sort again
+        let (kept, removed): (Vec<SongEntry>, Vec<SongEntry>) =
+            entries.into_iter().partition(|entry| {
+                let song = Song::from(entry);
+                let duration = *durations.get(&song).unwrap();
 
-            entry.time_played >= (duration * percent_threshold) / 100
-                && entry.time_played >= absolute_threshold
-        });
+                entry.time_played >= (duration * percent_threshold) / 100
+                    && entry.time_played >= absolute_threshold
+            });
+        self.entries = kept;
 
-        info!(
-            "{} song entries have been filtered out!",
-            length - self.len()
-        );
+        info!("{} song entries have been filtered out!", removed.len());
+
+        // only the durations of the filtered out songs have to be updated
+        if let Some(durations) = self.durations.get_mut() {
+            durations.remove(&removed, &self.known);
+        }
+        // some aspects may not be in the dataset anymore
+        self.index.take();
+
+        self
+    }
+
+    /// Replaces the calculated [durations][SongEntries::durations]
+    /// of the given songs with known ones, e.g. from the Spotify API
+    /// (see the `spotify` feature)
+    ///
+    /// Songs not in the dataset are ignored. Use it after
+    /// [`SongEntries::sum_different_capitalization`] since that
+    /// may change the names of the songs
+    #[must_use]
+    pub fn with_durations(
+        mut self,
+        durations: impl IntoIterator<Item = (Song, TimeDelta)>,
+    ) -> Self {
+        // so that the calculated ones exist to be replaced
+        let _ = self.durations();
+
+        if let Some(song_durations) = self.durations.get_mut() {
+            for (song, duration) in durations {
+                if let Some(old) = song_durations.durations.get_mut(&song) {
+                    *old = duration;
+                    self.known.insert(song, duration);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Replaces the calculated durations with the real ones from Spotify
+    ///
+    /// Shorthand for [`Spotify::durations`][crate::spotify::Spotify::durations]
+    /// and [`SongEntries::with_durations`]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    #[cfg(feature = "spotify")]
+    pub fn with_spotify_durations(
+        self,
+        spotify: &mut crate::spotify::Spotify,
+    ) -> Result<Self, crate::spotify::SpotifyError> {
+        let durations = spotify.durations(&self)?;
+        Ok(self.with_durations(durations))
+    }
+
+    /// Adds the given entries to the dataset, e.g. from a newer
+    /// Spotify export which doesn't contain the older entries
+    ///
+    /// Only the durations of the songs of the added entries are updated
+    /// instead of recalculating all of them.
+    /// Doesn't check for duplicate entries!
+    #[must_use]
+    pub fn add_entries(mut self, entries: Vec<SongEntry>) -> Self {
+        info!("Adding {} song entries...", entries.len());
+
+        if let Some(durations) = self.durations.get_mut() {
+            durations.add(&entries, &self.known);
+        }
+        // there may be new aspects
+        self.index.take();
+
+        self.entries.extend(entries);
+        // stable sort so entries from the same time stay in order
+        self.entries.sort();
 
         self
     }
@@ -331,6 +569,71 @@ impl SongEntries {
         &self[begin..=stop]
     }
 
+    /// Returns a map of [`Song`]s with their [durations][TimeDelta]
+    ///
+    /// Computed on first access and cached, so the first call
+    /// takes a while with a big dataset
+    #[must_use]
+    pub fn durations(&self) -> &HashMap<Song, TimeDelta> {
+        &self
+            .durations
+            .get_or_init(|| SongDurations::new(self, &self.known))
+            .durations
+    }
+
+    /// Returns the approximate memory usage of the dataset
+    ///
+    /// Only counts the heap allocations and sizes of the values themselves,
+    /// so the real usage is a bit higher (allocator overhead, [`HashMap`] metadata etc.)
+    #[must_use]
+    pub fn memory_stats(&self) -> MemoryStats {
+        use std::mem::size_of;
+
+        let entries = self.entries.capacity() * size_of::<SongEntry>()
+            + self.iter().map(|entry| entry.id.capacity()).sum::<usize>();
+
+        // names are shared between entries (see parse.rs), so each one is counted once
+        // Rc<str> allocation = strong and weak count + the string itself
+        let mut seen = HashSet::new();
+        let names = self
+            .iter()
+            .flat_map(|entry| {
+                [
+                    &entry.artist,
+                    &entry.album,
+                    &entry.track,
+                    &entry.folded_artist,
+                    &entry.folded_album,
+                    &entry.folded_track,
+                ]
+            })
+            .filter(|name| seen.insert(name.as_ptr()))
+            .map(|name| 2 * size_of::<usize>() + name.len())
+            .sum();
+
+        let durations = self.durations.get().map_or(0, |durations| {
+            map_bytes(&durations.durations)
+                + map_bytes(&durations.plays)
+                + durations.plays.values().map(map_bytes).sum::<usize>()
+        }) + map_bytes(&self.known);
+
+        let indices = self.index.get().map_or(0, find::Index::bytes);
+
+        MemoryStats {
+            entries,
+            names,
+            durations,
+            indices,
+        }
+    }
+
+    /// Returns the case-folded name indices used by [`Find`]
+    ///
+    /// Computed on first access and cached
+    fn index(&self) -> &find::Index {
+        self.index.get_or_init(|| find::Index::new(self))
+    }
+
     /// Returns the date of the first (time-wise) occurrence of any [`SongEntry`]
     ///
     /// # Panics
@@ -443,12 +746,56 @@ impl SongEntries {
         gather::plays_of_many(self, aspects)
     }
 
+    /// Writes the entries as a CSV file of scrobbles for Last.fm import tools
+    ///
+    /// See [`export::lastfm_csv`] for the columns
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if writing to `writer` fails
+    pub fn export_lastfm_csv<W: std::io::Write>(
+        &self,
+        writer: W,
+    ) -> Result<(), export::ExportError> {
+        export::lastfm_csv(self, self.durations(), writer)
+    }
+
+    /// Writes the entries in the `ListenBrainz` submission format
+    ///
+    /// See [`export::listenbrainz`]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if writing to `writer` fails
+    pub fn export_listenbrainz<W: std::io::Write>(
+        &self,
+        writer: W,
+    ) -> Result<(), export::ExportError> {
+        export::listenbrainz(self, self.durations(), writer)
+    }
+
+    /// Writes the entries into a new `SQLite` database at `path`
+    ///
+    /// See [`export::sqlite`] for the schema
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the database can't be created,
+    /// e.g. because there already is one with these tables at `path`
+    #[cfg(feature = "sqlite")]
+    pub fn export_sqlite<P: AsRef<Path>>(&self, path: P) -> Result<(), export::ExportError> {
+        export::sqlite(self, path)
+    }
+
     /// Adds search capability
     ///
     /// Use with methods from [`Find`]: [`.artist()`][Find::artist()], [`.album()`][Find::album()],
     /// [`.song_from_album()`][Find::song_from_album()] and [`.song()`][Find::song()]
+    ///
+    /// Those use sorted indices built on the first search
+    /// (and again after the entries have been changed)
     #[must_use]
-    pub fn find(&self) -> Find {
+    pub fn find(&self) -> Find<'_> {
         Find(self)
     }
 }
@@ -460,8 +807,12 @@ impl std::ops::Deref for SongEntries {
         &self.entries
     }
 }
+// the entries may be changed, so the cached durations and indices
+// are computed again on the next access (with the known durations kept)
 impl std::ops::DerefMut for SongEntries {
     fn deref_mut(&mut self) -> &mut Vec<SongEntry> {
+        self.durations.take();
+        self.index.take();
         &mut self.entries
     }
 }
@@ -478,51 +829,129 @@ impl<P: AsRef<Path> + std::fmt::Debug> TryFrom<&[P]> for SongEntries {
     }
 }
 
-/// Returns a [`HashMap`] with the [`Songs`][Song] as keys and
-/// their [durations][TimeDelta]s as values
-fn song_durations(entries: &Vec<SongEntry>) -> HashMap<Song, TimeDelta> {
-    info!("Calculating song durations...");
-    // 10k is just a guess for amount of unique songs
-    let mut big_boy: HashMap<Song, HashMap<TimeDelta, usize>> = HashMap::with_capacity(10_000);
+/// Returns the approximate number of bytes used by the entries of `map`
+fn map_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    // +1 for the control byte of each bucket
+    map.capacity() * (std::mem::size_of::<(K, V)>() + 1)
+}
 
-    for entry in entries {
-        let song = Song::from(entry);
-        let duration = entry.time_played;
+/// [Durations][TimeDelta] of all [`Songs`][Song] together with how often
+/// they've been played for how long
+///
+/// Keeping the latter allows updating the durations of only the affected songs
+/// when entries are added or removed instead of recalculating all of them
+struct SongDurations {
+    /// Map of [`Song`]s with their [durations][TimeDelta]
+    durations: HashMap<Song, TimeDelta>,
+    /// Map of [`Song`]s with a map of how long they've been played
+    /// and how often they've been played for that long
+    plays: HashMap<Song, HashMap<TimeDelta, usize>>,
+}
+impl SongDurations {
+    /// Calculates the durations of all songs in `entries`
+    ///
+    /// `known` - durations from an external source used instead of the calculated ones
+    fn new(entries: &[SongEntry], known: &HashMap<Song, TimeDelta>) -> Self {
+        info!("Calculating song durations...");
+        let mut song_durations = SongDurations {
+            durations: HashMap::new(),
+            // 10k is just a guess for amount of unique songs
+            plays: HashMap::with_capacity(10_000),
+        };
+        song_durations.add(entries, known);
+        song_durations
+    }
 
-        if let Some(durations) = big_boy.get_mut(&song) {
-            *durations.entry(duration).or_insert(0) += 1;
-        } else {
-            big_boy.insert(song, HashMap::from([(duration, 1)]));
+    /// Updates the durations of the songs of the added `entries`
+    fn add<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = &'a SongEntry>,
+        known: &HashMap<Song, TimeDelta>,
+    ) {
+        let mut changed = HashSet::new();
+
+        for entry in entries {
+            let song = Song::from(entry);
+            *self
+                .plays
+                .entry(song.clone())
+                .or_default()
+                .entry(entry.time_played)
+                .or_insert(0) += 1;
+            changed.insert(song);
         }
+
+        self.update(changed, known);
     }
 
-    big_boy
-        .into_iter()
-        .map(|(song, durations)| {
-            // because the longest duration is not necessarily the correct one
-            // e.g. if you skip through the song `ms_played` will be longer than the actual song length
-            // so we take the most common duration
-            let max_occurrence = durations.iter().max_by_key(|(_, count)| *count).unwrap().1;
-
-            let duration = *durations
-                .iter()
-                // but multiple durations can have the same maximum occurrence
-                .filter(|(_, count)| *count == max_occurrence)
-                // so we then take the longest maximum duration
-                .max_by_key(|(dur, _)| *dur)
-                .unwrap()
-                .0;
-
-            (song, duration)
-        })
-        .collect()
+    /// Updates the durations of the songs of the removed `entries`
+    ///
+    /// Songs without any plays left are removed
+    fn remove<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = &'a SongEntry>,
+        known: &HashMap<Song, TimeDelta>,
+    ) {
+        let mut changed = HashSet::new();
+
+        for entry in entries {
+            let song = Song::from(entry);
+            let Some(plays) = self.plays.get_mut(&song) else {
+                continue;
+            };
+            if let Some(count) = plays.get_mut(&entry.time_played) {
+                *count -= 1;
+                if *count == 0 {
+                    plays.remove(&entry.time_played);
+                }
+            }
+            changed.insert(song);
+        }
+
+        self.update(changed, known);
+    }
+
+    /// Recalculates the durations of the `songs`
+    /// unless they're `known`
+    fn update(&mut self, songs: HashSet<Song>, known: &HashMap<Song, TimeDelta>) {
+        for song in songs {
+            let Some(duration) = self.plays.get(&song).and_then(most_common_duration) else {
+                self.plays.remove(&song);
+                self.durations.remove(&song);
+                continue;
+            };
+
+            let duration = known.get(&song).copied().unwrap_or(duration);
+            self.durations.insert(song, duration);
+        }
+    }
+}
+
+/// Returns the duration a song has been played for most often
+/// or [`None`] if it hasn't been played
+///
+/// `plays` - map of how long a song has been played
+/// and how often it's been played for that long
+fn most_common_duration(plays: &HashMap<TimeDelta, usize>) -> Option<TimeDelta> {
+    // because the longest duration is not necessarily the correct one
+    // e.g. if you skip through the song `ms_played` will be longer than the actual song length
+    // so we take the most common duration
+    let max_occurrence = plays.values().max()?;
+
+    plays
+        .iter()
+        // but multiple durations can have the same maximum occurrence
+        .filter(|(_, count)| *count == max_occurrence)
+        // so we then take the longest maximum duration
+        .map(|(dur, _)| *dur)
+        .max()
 }
 
 /// Used by [`SongEntries`] as a wrapper for [`find`] methods
 ///
 /// Created with [`SongEntries::find`]
 pub struct Find<'a>(&'a SongEntries);
-impl<'a> Find<'a> {
+impl Find<'_> {
     /// Searches the entries for if the given artist exists in the dataset
     ///
     /// Case-insensitive and returns the [`Artist`] with proper capitalization
@@ -531,7 +960,36 @@ impl<'a> Find<'a> {
     /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
     #[must_use]
     pub fn artist(&self, artist_name: &str) -> Option<Artist> {
-        find::artist(self.0, artist_name)
+        self.0.index().artist(artist_name)
+    }
+
+    /// Searches the dataset for artists with a name similar to `artist_name`
+    ///
+    /// Case-insensitive and returns at most `num` [`Artists`][Artist]
+    /// sorted by similarity (most similar first)
+    #[must_use]
+    pub fn artists_fuzzy(&self, artist_name: &str, num: usize) -> Vec<Artist> {
+        find::artists_fuzzy(self.0, artist_name, num)
+    }
+
+    /// Searches the dataset for albums with a name similar to `album_name`
+    /// regardless of their artist
+    ///
+    /// Case-insensitive and returns at most `num` [`Albums`][Album]
+    /// sorted by similarity (most similar first)
+    #[must_use]
+    pub fn albums_fuzzy(&self, album_name: &str, num: usize) -> Vec<Album> {
+        find::albums_fuzzy(self.0, album_name, num)
+    }
+
+    /// Searches the dataset for songs with a name similar to `song_name`
+    /// regardless of their album and artist
+    ///
+    /// Case-insensitive and returns at most `num` [`Songs`][Song]
+    /// sorted by similarity (most similar first)
+    #[must_use]
+    pub fn songs_fuzzy(&self, song_name: &str, num: usize) -> Vec<Song> {
+        find::songs_fuzzy(self.0, song_name, num)
     }
 
     /// Searches the entries for if the given album exists in the dataset
@@ -542,7 +1000,7 @@ impl<'a> Find<'a> {
     /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
     #[must_use]
     pub fn album(&self, album_name: &str, artist_name: &str) -> Option<Album> {
-        find::album(self.0, album_name, artist_name)
+        self.0.index().album(album_name, artist_name)
     }
 
     /// Searches the entries for if the given song (in that specific album)
@@ -559,7 +1017,9 @@ impl<'a> Find<'a> {
         album_name: &str,
         artist_name: &str,
     ) -> Option<Song> {
-        find::song_from_album(self.0, song_name, album_name, artist_name)
+        self.0
+            .index()
+            .song_from_album(song_name, album_name, artist_name)
     }
 
     /// Searches the dataset for multiple versions of a song
@@ -570,7 +1030,7 @@ impl<'a> Find<'a> {
     /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
     #[must_use]
     pub fn song(&self, song_name: &str, artist_name: &str) -> Option<Vec<Song>> {
-        find::song(self.0, song_name, artist_name)
+        self.0.index().song(song_name, artist_name)
     }
 
     /// Returns a [`Vec<Song>`] with all the songs in the given album
@@ -583,3 +1043,190 @@ impl<'a> Find<'a> {
         find::songs_from_album(self.0, album)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads the example `endsong.json` from `stuff/example_endsong`
+    fn example_entries() -> SongEntries {
+        let paths = [format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        )];
+        SongEntries::new(&paths).unwrap()
+    }
+
+    #[test]
+    fn from_readers() {
+        let path = format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        );
+        let from_paths = SongEntries::new(&[&path]).unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        // the same file twice shouldn't result in duplicate entries
+        let from_readers = SongEntries::from_readers([&contents[..], &contents[..]]).unwrap();
+        assert_eq!(from_readers.entries, from_paths.entries);
+        assert_eq!(from_readers.durations(), from_paths.durations());
+
+        assert!(matches!(
+            SongEntries::from_readers([&contents[..], &b"not json"[..]]),
+            Err(ParseError::ReaderSerde(_, 2))
+        ));
+        assert!(matches!(
+            SongEntries::new(&["does_not_exist.json"]),
+            Err(ParseError::Io(..))
+        ));
+    }
+
+    #[test]
+    fn incremental_durations() {
+        let all = example_entries();
+
+        let (older, newer) = all.split_at(all.len() / 2);
+        let mut part = SongEntries::from_readers(Vec::<&[u8]>::new()).unwrap();
+        part.entries = older.to_vec();
+        // force the calculation so that it's updated incrementally
+        let _ = part.durations();
+        let part = part.add_entries(newer.to_vec());
+        assert_eq!(part.entries, all.entries);
+        assert_eq!(part.durations(), all.durations());
+
+        let filtered = example_entries().filter(100, TimeDelta::try_seconds(10).unwrap());
+        assert!(filtered.len() < all.len());
+        assert_eq!(
+            filtered.durations(),
+            &SongDurations::new(&filtered, &filtered.known).durations
+        );
+    }
+
+    #[test]
+    fn deref_mut_clears_caches() {
+        let mut entries = example_entries();
+        let artist = Artist::from(&entries[0]);
+        assert!(entries.find().artist(&artist.name).is_some());
+        assert!(entries.durations().contains_key(&Song::from(&entries[0])));
+
+        let song = Song::from(&entries[0]);
+        entries.retain(|entry| entry.artist != artist.name);
+        assert!(entries.find().artist(&artist.name).is_none());
+        assert!(!entries.durations().contains_key(&song));
+    }
+
+    #[test]
+    fn with_durations() {
+        let song = Song::from(&example_entries()[0]);
+        let unknown = Song::new("Unknown", "Unknown", "Unknown");
+        let minute = TimeDelta::try_minutes(1).unwrap();
+
+        let entries =
+            example_entries().with_durations([(song.clone(), minute), (unknown.clone(), minute)]);
+        assert_eq!(entries.durations().get(&song), Some(&minute));
+        assert!(!entries.durations().contains_key(&unknown));
+
+        // known durations aren't recalculated when the song's entries change
+        let mut replay = entries[0].clone();
+        replay.time_played = minute * 3;
+        let mut entries = entries.add_entries(vec![replay.clone(), replay]);
+        assert_eq!(entries.durations().get(&song), Some(&minute));
+
+        // nor lost when the entries are changed directly
+        entries.retain(|entry| entry.time_played != minute * 3);
+        assert_eq!(entries.durations().get(&song), Some(&minute));
+    }
+
+    #[test]
+    fn memory_stats() {
+        let entries = example_entries();
+
+        let before = entries.memory_stats();
+        assert!(before.entries >= entries.len() * std::mem::size_of::<SongEntry>());
+        assert!(before.names > 0);
+        // computed lazily
+        assert_eq!((before.durations, before.indices), (0, 0));
+
+        let _ = entries.durations();
+        let _ = entries.find().artist("Theocracy");
+        let after = entries.memory_stats();
+        assert!(after.durations > 0 && after.indices > 0);
+        assert!(after.total() > before.total());
+    }
+
+    #[test]
+    fn folded_names() {
+        let entries = example_entries();
+
+        for entry in entries.iter() {
+            assert_eq!(*entry.folded_artist, fold_case(&entry.artist));
+            assert_eq!(*entry.folded_album, fold_case(&entry.album));
+            assert_eq!(*entry.folded_track, fold_case(&entry.track));
+        }
+
+        let entry = &entries[0];
+        let song = Song::from(entry);
+        let shouting = Song::new(
+            entry.track.to_uppercase(),
+            entry.album.to_uppercase(),
+            entry.artist.to_uppercase(),
+        );
+        assert!(shouting.is_entry_lowercase(entry));
+        assert!(!shouting.is_entry(entry));
+        assert!(Album::from(&song).is_entry_lowercase(entry));
+        assert!(!Artist::new("Powerwolf").is_entry_lowercase(entry));
+
+        // all entries of a name share its case-folded version
+        let theocracy = entries
+            .iter()
+            .filter(|entry| &*entry.artist == "Theocracy")
+            .map(|entry| Rc::as_ptr(&entry.folded_artist))
+            .unique()
+            .count();
+        assert_eq!(theocracy, 1);
+    }
+
+    #[test]
+    fn platform_from_raw() {
+        assert_eq!(
+            Platform::from_raw("Android OS 10 API 29 (Xiaomi, POCOPHONE F1)"),
+            Platform::Android
+        );
+        assert_eq!(Platform::from_raw("android"), Platform::Android);
+        assert_eq!(Platform::from_raw("iOS 14.2 (iPad11,6)"), Platform::Ios);
+        assert_eq!(Platform::from_raw("ios"), Platform::Ios);
+        assert_eq!(
+            Platform::from_raw("Windows 10 (10.0.19042; x64)"),
+            Platform::Desktop
+        );
+        assert_eq!(Platform::from_raw("Linux [x86-64 0]"), Platform::Desktop);
+        assert_eq!(
+            Platform::from_raw("OS X 10.15.7 [x86 8]"),
+            Platform::Desktop
+        );
+        assert_eq!(
+            Platform::from_raw("web_player windows 10;chrome 88.0.4324.150;desktop"),
+            Platform::WebPlayer
+        );
+        assert_eq!(
+            Platform::from_raw("Partner sonos_ppc Sonos;Play:1;"),
+            Platform::SmartSpeaker
+        );
+        assert_eq!(
+            Platform::from_raw("Partner google cast_audio;Google_Home"),
+            Platform::SmartSpeaker
+        );
+        assert_eq!(Platform::from_raw("not_applicable"), Platform::Other);
+        // only whole words count
+        assert_eq!(Platform::from_raw("BIOS 2.1"), Platform::Other);
+        assert_eq!(Platform::from_raw("Partner podcast_app"), Platform::Other);
+        assert_eq!(Platform::from_raw("Echoes TV"), Platform::Other);
+        assert_eq!(Platform::from_raw("Partner home_theater"), Platform::Other);
+        assert_eq!(Platform::from_raw("Boxer 9 (x86)"), Platform::Other);
+        assert_eq!(
+            Platform::from_raw("Partner roku_web_player"),
+            Platform::Other
+        );
+        assert_eq!(Platform::from_raw(""), Platform::Other);
+    }
+}
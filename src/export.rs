@@ -0,0 +1,404 @@
+//! Module responsible for exporting the dataset into other formats
+//!
+//! [`lastfm_csv`] and [`listenbrainz`] write the entries as scrobbles,
+//! so that a Spotify history can be imported into Last.fm or `ListenBrainz`.
+//! Use [`SongEntries::filter`][crate::entry::SongEntries::filter] beforehand
+//! so that skipped songs aren't exported as scrobbles.
+//!
+//! With the `sqlite` feature enabled, [`sqlite`] writes the entries into
+//! an `SQLite` database with a normalized schema for ad-hoc SQL queries:
+//!
+//! ```sql
+//! SELECT artists.name, COUNT(*) AS plays
+//! FROM plays
+//! JOIN songs ON songs.id = plays.song_id
+//! JOIN albums ON albums.id = songs.album_id
+//! JOIN artists ON artists.id = albums.artist_id
+//! GROUP BY artists.id
+//! ORDER BY plays DESC
+//! LIMIT 10;
+//! ```
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::io::Write;
+
+use chrono::{DateTime, Local, SecondsFormat, TimeDelta};
+use serde::Serialize;
+use thiserror::Error;
+
+#[cfg(feature = "sqlite")]
+use std::path::Path;
+
+use crate::aspect::Song;
+#[cfg(feature = "sqlite")]
+use crate::aspect::{Album, Artist};
+use crate::entry::SongEntry;
+
+/// Maximum number of listens in a single `ListenBrainz` submission
+const LISTENBRAINZ_MAX_LISTENS: usize = 1000;
+
+/// Errors that can occur when exporting the dataset
+#[derive(Error, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ExportError {
+    /// Used when writing fails
+    #[error("Error while writing: {0}")]
+    Io(#[from] std::io::Error),
+    /// Used when serializing to JSON fails
+    #[error("Error while serializing: {0}")]
+    Serde(#[from] serde_json::Error),
+    /// Used when creating or writing the `SQLite` database fails
+    #[cfg(feature = "sqlite")]
+    #[error("Error while writing the database: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// Returns when the playback of `entry` started
+///
+/// [`SongEntry::timestamp`] is when the playback ended,
+/// but scrobbles are timestamped with the start
+fn started_at(entry: &SongEntry) -> DateTime<Local> {
+    entry.timestamp - entry.time_played
+}
+
+/// Writes the entries as a CSV file of scrobbles
+///
+/// Uses the columns of common Last.fm import tools
+/// (e.g. Universal Scrobbler or Open Scrobbler):
+/// `Artist,Track,Album,Timestamp,Album Artist,Duration`
+/// with the start of the playback in UTC (RFC 3339) and the length
+/// of the song from `durations` in seconds (empty if it's not in there),
+/// e.g. [`SongEntries::durations`][crate::entry::SongEntries::durations]
+///
+/// # Errors
+///
+/// Will return an error if writing to `writer` fails
+pub fn lastfm_csv<W: Write, S: BuildHasher>(
+    entries: &[SongEntry],
+    durations: &HashMap<Song, TimeDelta, S>,
+    mut writer: W,
+) -> Result<(), ExportError> {
+    writeln!(writer, "Artist,Track,Album,Timestamp,Album Artist,Duration")?;
+
+    for entry in entries {
+        let duration = durations
+            .get(&Song::from(entry))
+            .map(|duration| duration.num_seconds().to_string())
+            .unwrap_or_default();
+
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            csv_field(&entry.artist),
+            csv_field(&entry.track),
+            csv_field(&entry.album),
+            started_at(entry)
+                .naive_utc()
+                .and_utc()
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            // Spotify doesn't know the album artist
+            csv_field(&entry.artist),
+            duration
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Quotes `field` if it contains a comma, quote or line break
+fn csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+/// A [ListenBrainz submission](https://listenbrainz.readthedocs.io/en/latest/users/json.html)
+#[derive(Serialize)]
+struct Submission<'a> {
+    /// always `import` since the listens are from the past
+    listen_type: &'static str,
+    /// the listens
+    payload: Vec<Listen<'a>>,
+}
+
+/// A single listen of a [`Submission`]
+#[derive(Serialize)]
+struct Listen<'a> {
+    /// Unix timestamp of the start of the listen
+    listened_at: i64,
+    /// metadata of the listened song
+    track_metadata: TrackMetadata<'a>,
+}
+
+/// Metadata of a [`Listen`]
+#[derive(Serialize)]
+struct TrackMetadata<'a> {
+    /// name of the artist
+    artist_name: &'a str,
+    /// name of the song
+    track_name: &'a str,
+    /// name of the album
+    release_name: &'a str,
+    /// optional metadata
+    additional_info: AdditionalInfo<'a>,
+}
+
+/// Optional metadata of a [`Listen`]
+#[derive(Serialize)]
+struct AdditionalInfo<'a> {
+    /// length of the song
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<i64>,
+    /// link to the song on Spotify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spotify_id: Option<String>,
+    /// always `Spotify`
+    music_service_name: &'static str,
+    /// always `endsong`
+    submission_client: &'a str,
+}
+
+/// Writes the entries in the `ListenBrainz` submission format
+///
+/// Every line is a JSON submission of at most 1000 listens
+/// (the limit of the `ListenBrainz` API), which can be sent to
+/// `/1/submit-listens` as it is. The listens are timestamped with
+/// the start of the playback and the length of the song is taken
+/// from `durations` (left out if it's not in there),
+/// e.g. [`SongEntries::durations`][crate::entry::SongEntries::durations]
+///
+/// # Errors
+///
+/// Will return an error if writing to `writer` fails
+pub fn listenbrainz<W: Write, S: BuildHasher>(
+    entries: &[SongEntry],
+    durations: &HashMap<Song, TimeDelta, S>,
+    mut writer: W,
+) -> Result<(), ExportError> {
+    for chunk in entries.chunks(LISTENBRAINZ_MAX_LISTENS) {
+        let payload = chunk
+            .iter()
+            .map(|entry| Listen {
+                listened_at: started_at(entry).timestamp(),
+                track_metadata: TrackMetadata {
+                    artist_name: &entry.artist,
+                    track_name: &entry.track,
+                    release_name: &entry.album,
+                    additional_info: AdditionalInfo {
+                        duration_ms: durations
+                            .get(&Song::from(entry))
+                            .map(TimeDelta::num_milliseconds),
+                        // spotify:track:<id> -> https://open.spotify.com/track/<id>
+                        spotify_id: entry
+                            .id
+                            .strip_prefix("spotify:track:")
+                            .map(|id| format!("https://open.spotify.com/track/{id}")),
+                        music_service_name: "Spotify",
+                        submission_client: env!("CARGO_PKG_NAME"),
+                    },
+                },
+            })
+            .collect();
+
+        let submission = Submission {
+            listen_type: "import",
+            payload,
+        };
+        serde_json::to_writer(&mut writer, &submission)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Schema of the database created by [`sqlite`]
+#[cfg(feature = "sqlite")]
+const SCHEMA: &str = "
+CREATE TABLE artists (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE albums (
+    id INTEGER PRIMARY KEY,
+    artist_id INTEGER NOT NULL REFERENCES artists(id),
+    name TEXT NOT NULL,
+    UNIQUE (artist_id, name)
+);
+CREATE TABLE songs (
+    id INTEGER PRIMARY KEY,
+    album_id INTEGER NOT NULL REFERENCES albums(id),
+    name TEXT NOT NULL,
+    UNIQUE (album_id, name)
+);
+CREATE TABLE plays (
+    id INTEGER PRIMARY KEY,
+    song_id INTEGER NOT NULL REFERENCES songs(id),
+    timestamp TEXT NOT NULL,
+    ms_played INTEGER NOT NULL,
+    platform TEXT NOT NULL,
+    spotify_uri TEXT NOT NULL
+);
+CREATE INDEX plays_song_id ON plays(song_id);
+CREATE INDEX plays_timestamp ON plays(timestamp);
+";
+
+/// Writes the entries into a new `SQLite` database at `path`
+///
+/// The tables are `artists`, `albums`, `songs` and `plays`
+/// (see the [module documentation][self] for an example query).
+/// Timestamps are stored as RFC 3339 strings in the local time zone,
+/// so `SQLite`'s date functions work on them.
+///
+/// # Errors
+///
+/// Will return an error if the database can't be created,
+/// e.g. because there already is one with these tables at `path`
+#[cfg(feature = "sqlite")]
+pub fn sqlite<P: AsRef<Path>>(entries: &[SongEntry], path: P) -> Result<(), ExportError> {
+    tracing::info!("Exporting {} entries to SQLite...", entries.len());
+
+    let mut connection = rusqlite::Connection::open(path)?;
+    // one transaction instead of one per insert
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+
+    {
+        let mut insert_artist = transaction.prepare("INSERT INTO artists (name) VALUES (?1)")?;
+        let mut insert_album =
+            transaction.prepare("INSERT INTO albums (artist_id, name) VALUES (?1, ?2)")?;
+        let mut insert_song =
+            transaction.prepare("INSERT INTO songs (album_id, name) VALUES (?1, ?2)")?;
+        let mut insert_play = transaction.prepare(
+            "INSERT INTO plays (song_id, timestamp, ms_played, platform, spotify_uri)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+
+        let mut artist_ids: HashMap<Artist, i64> = HashMap::new();
+        let mut album_ids: HashMap<Album, i64> = HashMap::new();
+        let mut song_ids: HashMap<Song, i64> = HashMap::new();
+
+        for entry in entries {
+            let artist = Artist::from(entry);
+            let artist_id = if let Some(&id) = artist_ids.get(&artist) {
+                id
+            } else {
+                let id = insert_artist.insert((&*entry.artist,))?;
+                artist_ids.insert(artist, id);
+                id
+            };
+
+            let album = Album::from(entry);
+            let album_id = if let Some(&id) = album_ids.get(&album) {
+                id
+            } else {
+                let id = insert_album.insert((artist_id, &*entry.album))?;
+                album_ids.insert(album, id);
+                id
+            };
+
+            let song = Song::from(entry);
+            let song_id = if let Some(&id) = song_ids.get(&song) {
+                id
+            } else {
+                let id = insert_song.insert((album_id, &*entry.track))?;
+                song_ids.insert(song, id);
+                id
+            };
+
+            insert_play.execute((
+                song_id,
+                entry.timestamp.to_rfc3339(),
+                entry.time_played.num_milliseconds(),
+                entry.platform.to_string(),
+                &entry.id,
+            ))?;
+        }
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads the example `endsong.json` from `stuff/example_endsong`
+    fn example_entries() -> crate::entry::SongEntries {
+        let paths = [format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        )];
+        crate::entry::SongEntries::new(&paths).unwrap()
+    }
+
+    #[test]
+    fn scrobbles() {
+        let entries = example_entries();
+
+        let mut csv = Vec::new();
+        lastfm_csv(&entries, entries.durations(), &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), entries.len() + 1);
+        // start of the playback, not the end
+        let start = entries[0].timestamp - entries[0].time_played;
+        let first = csv.lines().nth(1).unwrap();
+        assert!(first.contains(
+            &start
+                .naive_utc()
+                .and_utc()
+                .to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+        let duration = entries.durations()[&Song::from(&entries[0])];
+        assert!(first.ends_with(&format!(",{}", duration.num_seconds())));
+        assert_eq!(csv_field("Hello, World"), "\"Hello, World\"");
+        assert_eq!(csv_field("12\" Mix"), "\"12\"\" Mix\"");
+        assert_eq!(csv_field("Plain"), "Plain");
+
+        let mut json = Vec::new();
+        listenbrainz(&entries, entries.durations(), &mut json).unwrap();
+        let submission: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(submission["listen_type"], "import");
+        let listens = submission["payload"].as_array().unwrap();
+        assert_eq!(listens.len(), entries.len());
+        assert_eq!(listens[0]["listened_at"], start.timestamp());
+        assert_ne!(listens[0]["listened_at"], entries[0].timestamp.timestamp());
+        assert_eq!(
+            listens[0]["track_metadata"]["additional_info"]["duration_ms"],
+            duration.num_milliseconds()
+        );
+        assert_eq!(
+            listens[0]["track_metadata"]["artist_name"],
+            &*entries[0].artist
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn sqlite() {
+        let entries = example_entries();
+
+        let path = std::env::temp_dir().join(format!("endsong_{}.sqlite", std::process::id()));
+        super::sqlite(&entries, &path).unwrap();
+        // tables already exist
+        assert!(super::sqlite(&entries, &path).is_err());
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let count = |table: &str| -> usize {
+            connection
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), (), |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+        assert_eq!(count("plays"), entries.len());
+        assert_eq!(count("artists"), crate::gather::artists(&entries).len());
+        assert_eq!(count("albums"), crate::gather::albums(&entries).len());
+        assert_eq!(count("songs"), crate::gather::songs(&entries, false).len());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
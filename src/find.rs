@@ -1,9 +1,15 @@
 //! Module responsible for finding artists, albums and songs in the dataset
 
+use std::rc::Rc;
+
 use itertools::Itertools;
 
 use crate::aspect::{Album, Artist, Music, Song};
-use crate::entry::SongEntry;
+use crate::entry::{fold_case, SongEntry};
+
+/// Minimum [Jaro-Winkler similarity][strsim::jaro_winkler] for a name
+/// to be considered a match by [`artists_fuzzy`], [`albums_fuzzy`] and [`songs_fuzzy`]
+const FUZZY_THRESHOLD: f64 = 0.75;
 
 /// Searches the entries for if the given artist exists in the dataset
 ///
@@ -12,14 +18,78 @@ use crate::entry::SongEntry;
 ///
 /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
 pub fn artist(entries: &[SongEntry], artist_name: &str) -> Option<Artist> {
-    let usr_artist = Artist::new(artist_name);
+    let artist_name = fold_case(artist_name);
 
     entries
         .iter()
-        .find(|entry| usr_artist.is_entry_lowercase(entry))
+        .find(|entry| *entry.folded_artist == *artist_name)
         .map(Artist::from)
 }
 
+/// Searches the dataset for artists with a name similar to `artist_name`
+///
+/// Case-insensitive and returns at most `num` [`Artists`][Artist]
+/// sorted by similarity (most similar first)
+///
+/// Useful as a fallback when [`artist`] doesn't find anything,
+/// e.g. because of a typo
+#[must_use]
+pub fn artists_fuzzy(entries: &[SongEntry], artist_name: &str, num: usize) -> Vec<Artist> {
+    fuzzy(entries, artist_name, num, |art: &Artist| &art.name)
+}
+
+/// Searches the dataset for albums with a name similar to `album_name`
+/// regardless of their artist
+///
+/// Case-insensitive and returns at most `num` [`Albums`][Album]
+/// sorted by similarity (most similar first)
+#[must_use]
+pub fn albums_fuzzy(entries: &[SongEntry], album_name: &str, num: usize) -> Vec<Album> {
+    fuzzy(entries, album_name, num, |alb: &Album| &alb.name)
+}
+
+/// Searches the dataset for songs with a name similar to `song_name`
+/// regardless of their album and artist
+///
+/// Case-insensitive and returns at most `num` [`Songs`][Song]
+/// sorted by similarity (most similar first) - every album version
+/// of a song is a separate [`Song`]
+#[must_use]
+pub fn songs_fuzzy(entries: &[SongEntry], song_name: &str, num: usize) -> Vec<Song> {
+    fuzzy(entries, song_name, num, |song: &Song| &song.name)
+}
+
+/// Returns at most `num` aspects whose `name` is similar to `query`
+///
+/// Used by [`artists_fuzzy`], [`albums_fuzzy`] and [`songs_fuzzy`]
+fn fuzzy<'a, Asp>(
+    entries: &'a [SongEntry],
+    query: &str,
+    num: usize,
+    name: impl Fn(&Asp) -> &str,
+) -> Vec<Asp>
+where
+    Asp: Music + std::hash::Hash + From<&'a SongEntry>,
+{
+    let query = query.to_lowercase();
+
+    entries
+        .iter()
+        .map(Asp::from)
+        .unique()
+        .filter_map(|asp| {
+            let similarity = strsim::jaro_winkler(&query, &name(&asp).to_lowercase());
+            (similarity >= FUZZY_THRESHOLD).then_some((asp, similarity))
+        })
+        // most similar first, alphabetically if equally similar
+        .sorted_unstable_by(|(asp_a, sim_a), (asp_b, sim_b)| {
+            sim_b.total_cmp(sim_a).then_with(|| asp_a.cmp(asp_b))
+        })
+        .take(num)
+        .map(|(asp, _)| asp)
+        .collect_vec()
+}
+
 /// Searches the entries for if the given album exists in the dataset
 ///
 /// Case-insensitive and returns the [`Album`] with proper capitalization
@@ -27,11 +97,11 @@ pub fn artist(entries: &[SongEntry], artist_name: &str) -> Option<Artist> {
 ///
 /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
 pub fn album(entries: &[SongEntry], album_name: &str, artist_name: &str) -> Option<Album> {
-    let usr_album = Album::new(album_name, artist_name);
+    let (album_name, artist_name) = (fold_case(album_name), fold_case(artist_name));
 
     entries
         .iter()
-        .find(|entry| usr_album.is_entry_lowercase(entry))
+        .find(|entry| *entry.folded_artist == *artist_name && *entry.folded_album == *album_name)
         .map(Album::from)
 }
 
@@ -48,11 +118,17 @@ pub fn song_from_album(
     album_name: &str,
     artist_name: &str,
 ) -> Option<Song> {
-    let usr_song = Song::new(song_name, album_name, artist_name);
+    let song_name = fold_case(song_name);
+    let album_name = fold_case(album_name);
+    let artist_name = fold_case(artist_name);
 
     entries
         .iter()
-        .find(|entry| usr_song.is_entry_lowercase(entry))
+        .find(|entry| {
+            *entry.folded_artist == *artist_name
+                && *entry.folded_album == *album_name
+                && *entry.folded_track == *song_name
+        })
         .map(Song::from)
 }
 
@@ -63,13 +139,11 @@ pub fn song_from_album(
 ///
 /// See #2 <https://github.com/fsktom/rusty-endsong-parser/issues/2>
 pub fn song(entries: &[SongEntry], song_name: &str, artist_name: &str) -> Option<Vec<Song>> {
-    let (song_name, artist_name) = (song_name.to_lowercase(), artist_name.to_lowercase());
+    let (song_name, artist_name) = (fold_case(song_name), fold_case(artist_name));
 
     let song_versions = entries
         .iter()
-        .filter(|entry| {
-            entry.track.to_lowercase() == song_name && entry.artist.to_lowercase() == artist_name
-        })
+        .filter(|entry| *entry.folded_artist == *artist_name && *entry.folded_track == *song_name)
         .unique()
         .map(Song::from)
         .collect_vec();
@@ -95,23 +169,218 @@ pub fn songs_from_album(entries: &[SongEntry], album: &Album) -> Vec<Song> {
         .collect_vec()
 }
 
+/// Case-folded (artist, album) names of an album in the [`Index`]
+type AlbumKey = (Rc<str>, Rc<str>);
+
+/// Case-folded (artist, song, album) names of a song in the [`Index`]
+type SongKey = (Rc<str>, Rc<str>, Rc<str>);
+
+/// Case-folded sorted indices of the names of all artists, albums and songs
+/// in a dataset
+///
+/// Built once by [`SongEntries`][crate::entry::SongEntries] so that
+/// the lookups of [`Find`][crate::entry::Find] are binary searches
+/// instead of linear scans. The case-folded names are the ones
+/// the entries got while parsing, so nothing is lowercased here
+///
+/// Returns the same results as [`artist`], [`album`], [`song_from_album`]
+/// and [`song`] (i.e. with the capitalization of the first entry found)
+pub(crate) struct Index {
+    /// Case-folded artist names with their first occurrence,
+    /// sorted by the case-folded name
+    artists: Vec<(Rc<str>, Artist)>,
+    /// Case-folded (artist, album) names with their first occurrence,
+    /// sorted by the case-folded names
+    albums: Vec<(AlbumKey, Album)>,
+    /// Case-folded (artist, song, album) names with every distinct
+    /// capitalization of the song, sorted by the case-folded artist and song names
+    /// and then by first occurrence
+    songs: Vec<(SongKey, Song)>,
+}
+impl Index {
+    /// Builds the indices from the given entries
+    pub(crate) fn new(entries: &[SongEntry]) -> Self {
+        let mut artists = Vec::new();
+        let mut albums = Vec::new();
+        let mut songs = Vec::new();
+
+        // unique_by() keeps the first occurrence of every capitalization
+        for entry in entries.iter().unique_by(|entry| Song::from(*entry)) {
+            let artist = Rc::clone(&entry.folded_artist);
+            let album = Rc::clone(&entry.folded_album);
+
+            artists.push((Rc::clone(&artist), Artist::from(entry)));
+            albums.push(((Rc::clone(&artist), Rc::clone(&album)), Album::from(entry)));
+            songs.push((
+                (artist, Rc::clone(&entry.folded_track), album),
+                Song::from(entry),
+            ));
+        }
+
+        // stable sorts -> the first occurrence stays first among equal names
+        artists.sort_by(|(a, _), (b, _)| a.cmp(b));
+        artists.dedup_by(|(a, _), (b, _)| a == b);
+        albums.sort_by(|(a, _), (b, _)| a.cmp(b));
+        albums.dedup_by(|(a, _), (b, _)| a == b);
+        songs.sort_by(|((art_a, song_a, _), _), ((art_b, song_b, _), _)| {
+            (art_a, song_a).cmp(&(art_b, song_b))
+        });
+
+        Index {
+            artists,
+            albums,
+            songs,
+        }
+    }
+
+    /// Returns the approximate number of bytes used by the indices
+    ///
+    /// The aspects and the case-folded names are shared with the entries,
+    /// so only the indices themselves are counted
+    pub(crate) fn bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        self.artists.capacity() * size_of::<(Rc<str>, Artist)>()
+            + self.albums.capacity() * size_of::<(AlbumKey, Album)>()
+            + self.songs.capacity() * size_of::<(SongKey, Song)>()
+    }
+
+    /// Indexed version of [`artist`]
+    pub(crate) fn artist(&self, artist_name: &str) -> Option<Artist> {
+        let artist_name = fold_case(artist_name);
+
+        self.artists
+            .binary_search_by(|(art, _)| art.as_ref().cmp(artist_name.as_str()))
+            .ok()
+            .map(|i| self.artists[i].1.clone())
+    }
+
+    /// Indexed version of [`album`]
+    pub(crate) fn album(&self, album_name: &str, artist_name: &str) -> Option<Album> {
+        let key = (fold_case(artist_name), fold_case(album_name));
+
+        self.albums
+            .binary_search_by(|((art, alb), _)| {
+                (art.as_ref(), alb.as_ref()).cmp(&(key.0.as_str(), key.1.as_str()))
+            })
+            .ok()
+            .map(|i| self.albums[i].1.clone())
+    }
+
+    /// Indexed version of [`song_from_album`]
+    pub(crate) fn song_from_album(
+        &self,
+        song_name: &str,
+        album_name: &str,
+        artist_name: &str,
+    ) -> Option<Song> {
+        let album_name = fold_case(album_name);
+
+        self.versions(song_name, artist_name)
+            .iter()
+            .find(|((_, _, alb), _)| **alb == *album_name)
+            .map(|(_, song)| song.clone())
+    }
+
+    /// Indexed version of [`song`]
+    pub(crate) fn song(&self, song_name: &str, artist_name: &str) -> Option<Vec<Song>> {
+        let versions = self.versions(song_name, artist_name);
+
+        if versions.is_empty() {
+            return None;
+        }
+
+        Some(versions.iter().map(|(_, song)| song.clone()).collect_vec())
+    }
+
+    /// Returns the slice of the song index with every version of the song
+    /// in the order of their first occurrence
+    fn versions(&self, song_name: &str, artist_name: &str) -> &[(SongKey, Song)] {
+        let key = (fold_case(artist_name), fold_case(song_name));
+        let key = (key.0.as_str(), key.1.as_str());
+
+        let start = self
+            .songs
+            .partition_point(|((art, song, _), _)| (art.as_ref(), song.as_ref()) < key);
+        let end = self
+            .songs
+            .partition_point(|((art, song, _), _)| (art.as_ref(), song.as_ref()) <= key);
+
+        &self.songs[start..end]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn find_aspect() {
+    /// Loads the example `endsong.json` from `stuff/example_endsong`
+    fn example_entries() -> crate::entry::SongEntries {
         // MAYBE RATHER INTEGRATION TEST THAN UNIT TEST?!
-        let paths = vec![format!(
+        let paths = [format!(
             "{}/stuff/example_endsong/endsong_0.json",
             std::env::current_dir().unwrap().display()
         )];
-        let entries = crate::entry::SongEntries::new(&paths).unwrap();
+        crate::entry::SongEntries::new(&paths).unwrap()
+    }
+
+    #[test]
+    fn find_aspect() {
+        let entries = example_entries();
 
         assert_eq!(
             artist(&entries, "Theocracy").unwrap(),
             Artist::new("Theocracy")
         );
         assert!(entries.find().artist("Powerwolf").is_none());
+
+        assert_eq!(
+            artists_fuzzy(&entries, "theokracy", 3),
+            vec![Artist::new("Theocracy")]
+        );
+        assert!(entries.find().artists_fuzzy("Powerwolf", 3).is_empty());
+
+        assert_eq!(
+            albums_fuzzy(&entries, "waking the falen", 3),
+            vec![Album::new("Waking The Fallen", "Avenged Sevenfold")]
+        );
+        assert_eq!(
+            songs_fuzzy(&entries, "radiant eclipse", 1),
+            vec![Song::new(
+                "Radiant Eclipse",
+                "Waking The Fallen",
+                "Avenged Sevenfold"
+            )]
+        );
+        assert!(entries
+            .find()
+            .songs_fuzzy("Blood of the Saints", 3)
+            .is_empty());
+    }
+
+    #[test]
+    fn index_matches_linear_search() {
+        let entries = example_entries();
+        let index = Index::new(&entries);
+
+        for entry in entries.iter() {
+            let (art, alb, song) = (
+                entry.artist.to_uppercase(),
+                entry.album.to_uppercase(),
+                entry.track.to_uppercase(),
+            );
+
+            assert_eq!(index.artist(&art), artist(&entries, &art));
+            assert_eq!(index.album(&alb, &art), album(&entries, &alb, &art));
+            assert_eq!(
+                index.song_from_album(&song, &alb, &art),
+                song_from_album(&entries, &song, &alb, &art)
+            );
+            assert_eq!(index.song(&song, &art), super::song(&entries, &song, &art));
+        }
+
+        assert!(index.artist("Powerwolf").is_none());
+        assert!(index.album("Blessed & Possessed", "Theocracy").is_none());
+        assert!(index.song("Blood of the Saints", "Theocracy").is_none());
     }
 }
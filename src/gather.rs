@@ -30,14 +30,15 @@
 //! let _ = gather::albums_from_artist(entries.between(&start_date, &end_date), &artist);
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::rc::Rc;
 
-use chrono::TimeDelta;
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeDelta, Timelike};
 use itertools::Itertools;
 
 use crate::aspect::{Album, Artist, HasSongs, Music, Song};
-use crate::entry::SongEntry;
+use crate::entry::{Platform, SongEntry};
 
 /// Returns a map with all [`Songs`][Song] and their playcount
 ///
@@ -58,13 +59,60 @@ pub fn songs(entries: &[SongEntry], sum_songs_from_different_albums: bool) -> Ha
         return songs;
     }
 
+    sum_song_versions(songs)
+}
+
+/// Returns a map with all [`Songs`][Song] and their listening time
+///
+/// `sum_songs_from_different_albums` - see [`songs`]
+#[must_use]
+pub fn songs_listening_time(
+    entries: &[SongEntry],
+    sum_songs_from_different_albums: bool,
+) -> HashMap<Song, TimeDelta> {
+    let songs = listening_time_by::<Song>(entries);
+    if !sum_songs_from_different_albums {
+        return songs;
+    }
+
+    sum_song_versions(songs)
+}
+
+/// Returns a map with all [`Songs`][Song] from `artist` and their playcount
+///
+/// `sum_songs_from_different_albums` - see [`songs`]
+#[must_use]
+pub fn songs_from_artist(
+    entries: &[SongEntry],
+    artist: &Artist,
+    sum_songs_from_different_albums: bool,
+) -> HashMap<Song, usize> {
+    let songs = songs_from(entries, artist);
+    if !sum_songs_from_different_albums {
+        return songs;
+    }
+
+    sum_song_versions(songs)
+}
+
+/// Sums up the plays (or listening time) of songs with the same name and artist
+/// but from different albums
+///
+/// Used by [`songs`], [`songs_from_artist`] and [`songs_listening_time`]
+///
+/// # Panics
+///
+/// Uses .`unwrap()` but it should never panic
+fn sum_song_versions<V: Copy + Ord + std::iter::Sum<V>>(
+    songs: HashMap<Song, V>,
+) -> HashMap<Song, V> {
     // to know which album the song had highest amount of plays from
     // that album will be then displayed in () after the song name
     // but the number of plays that will be displayed will be a sum of
     // the plays from all albums
     // key: (song name, artist)
     // value: HashMap of albums with number of plays of the song in that album
-    let mut songs_albums: HashMap<(Rc<str>, Artist), HashMap<Album, usize>> =
+    let mut songs_albums: HashMap<(Rc<str>, Artist), HashMap<Album, V>> =
         HashMap::with_capacity(songs.len());
     for (song, plays_song) in songs {
         let song_just_artist = (song.name, song.album.artist.clone());
@@ -76,11 +124,11 @@ pub fn songs(entries: &[SongEntry], sum_songs_from_different_albums: bool) -> Ha
     }
 
     // required because only one version (i.e. album) of the song should be saved
-    let mut songs: HashMap<Song, usize> = HashMap::with_capacity(songs_albums.len());
+    let mut songs: HashMap<Song, V> = HashMap::with_capacity(songs_albums.len());
 
     for ((song_name, _), albs) in songs_albums {
         // number of plays of the song across all albums
-        let total = albs.values().sum();
+        let total = albs.values().copied().sum();
         // album with the highest number of plays
         let highest = albs
             .into_iter()
@@ -113,6 +161,36 @@ pub fn songs_from<Asp: HasSongs>(entries: &[SongEntry], aspect: &Asp) -> HashMap
         .counts()
 }
 
+/// Returns a map with the [`Albums`][Album] from `track_counts`
+/// and the share (`0.0` to `1.0`) of their tracks that have been played at least once
+///
+/// `track_counts` - number of tracks on each album, e.g. from the Spotify API
+/// (see the `spotify` feature); albums not in it are skipped.
+/// Capped at `1.0` since some songs may be on other versions of the album
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn album_completion<S: std::hash::BuildHasher>(
+    entries: &[SongEntry],
+    track_counts: &HashMap<Album, usize, S>,
+) -> HashMap<Album, f64> {
+    let mut played: HashMap<Album, HashSet<&str>> = HashMap::new();
+    for entry in entries {
+        let album = Album::from(entry);
+        if track_counts.contains_key(&album) {
+            played.entry(album).or_default().insert(&entry.track);
+        }
+    }
+
+    played
+        .into_iter()
+        .filter(|(album, _)| track_counts[album] != 0)
+        .map(|(album, songs)| {
+            let completion = (songs.len() as f64 / track_counts[&album] as f64).min(1.0);
+            (album, completion)
+        })
+        .collect()
+}
+
 /// Returns a map with all [`Albums`][Album] and their playcount
 #[must_use]
 pub fn albums(entries: &[SongEntry]) -> HashMap<Album, usize> {
@@ -141,6 +219,34 @@ pub fn artists(entries: &[SongEntry]) -> HashMap<Artist, usize> {
     entries.iter().map(Artist::from).counts()
 }
 
+/// Returns a map with all [`Albums`][Album] and their listening time
+#[must_use]
+pub fn albums_listening_time(entries: &[SongEntry]) -> HashMap<Album, TimeDelta> {
+    listening_time_by::<Album>(entries)
+}
+
+/// Returns a map with all [`Artists`][Artist] and their listening time
+#[must_use]
+pub fn artists_listening_time(entries: &[SongEntry]) -> HashMap<Artist, TimeDelta> {
+    listening_time_by::<Artist>(entries)
+}
+
+/// Sums up the listening time of entries grouped by the aspect
+///
+/// Used by the `*_listening_time` functions
+fn listening_time_by<Asp>(entries: &[SongEntry]) -> HashMap<Asp, TimeDelta>
+where
+    Asp: Music + Hash + for<'a> From<&'a SongEntry>,
+{
+    let mut times: HashMap<Asp, TimeDelta> = HashMap::new();
+    for entry in entries {
+        *times
+            .entry(Asp::from(entry))
+            .or_insert_with(TimeDelta::zero) += entry.time_played;
+    }
+    times
+}
+
 /// Counts up the plays of an [`Artist`], [`Album`] or [`Song`]
 #[must_use]
 pub fn plays<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> usize {
@@ -173,3 +279,585 @@ pub fn all_plays(entries: &[SongEntry]) -> usize {
 pub fn listening_time(entries: &[SongEntry]) -> TimeDelta {
     entries.iter().map(|entry| entry.time_played).sum()
 }
+
+/// Returns the time listened to an [`Artist`], [`Album`] or [`Song`]
+#[must_use]
+pub fn listening_time_of<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> TimeDelta {
+    entries
+        .iter()
+        .filter(|entry| aspect.is_entry(entry))
+        .map(|entry| entry.time_played)
+        .sum()
+}
+
+/// Returns the date of the first play of an [`Artist`], [`Album`] or [`Song`]
+///
+/// Returns [`None`] if `aspect` is not in `entries`
+#[must_use]
+pub fn first_play<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> Option<DateTime<Local>> {
+    entries
+        .iter()
+        .find(|entry| aspect.is_entry(entry))
+        .map(|entry| entry.timestamp)
+}
+
+/// Returns the first play of every [`Artist`], i.e. when it was discovered
+///
+/// The entries are in chronological order
+#[must_use]
+pub fn artist_discoveries(entries: &[SongEntry]) -> Vec<&SongEntry> {
+    let mut known: HashSet<&str> = HashSet::new();
+    entries
+        .iter()
+        .filter(|entry| known.insert(&entry.artist))
+        .collect()
+}
+
+/// Returns the artists where a single song accounts for more than
+/// `percent_threshold`% of their plays, i.e. "one-hit wonders"
+///
+/// `percent_threshold` - a value between 0 and 100 (%)
+///
+/// `min_plays` - artists with less plays are skipped, since an artist
+/// with a single play would always be a one-hit wonder
+///
+/// Plays of a song on different albums are summed up (see [`songs`]).
+/// Returns the song together with its playcount and the playcount
+/// of its artist, sorted by the song's share of the artist's plays descending
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn one_hit_wonders(
+    entries: &[SongEntry],
+    percent_threshold: f64,
+    min_plays: usize,
+) -> Vec<(Song, usize, usize)> {
+    let artists = artists(entries);
+    let share =
+        |song_plays: usize, artist_plays: usize| 100.0 * (song_plays as f64 / artist_plays as f64);
+
+    songs(entries, true)
+        .into_iter()
+        .filter_map(|(song, song_plays)| {
+            let artist_plays = *artists.get(&song.album.artist)?;
+            (artist_plays >= min_plays && share(song_plays, artist_plays) > percent_threshold)
+                .then_some((song, song_plays, artist_plays))
+        })
+        // highest share first, alphabetically if equal
+        .sorted_unstable_by(|(a, a_plays, a_all), (b, b_plays, b_all)| {
+            share(*b_plays, *b_all)
+                .total_cmp(&share(*a_plays, *a_all))
+                .then_with(|| a.cmp(b))
+        })
+        .collect()
+}
+
+/// Returns how many distinct [`Artists`][Artist], [`Albums`][Album]
+/// or [`Songs`][Song] had been listened to up to each first play of a new one,
+/// i.e. the growth of the library
+///
+/// The same song on different albums counts as different songs.
+/// The entries are in chronological order
+#[must_use]
+pub fn library_growth<Asp>(entries: &[SongEntry]) -> Vec<(DateTime<Local>, usize)>
+where
+    Asp: Music + Hash + for<'a> From<&'a SongEntry>,
+{
+    let mut known: HashSet<Asp> = HashSet::new();
+    entries
+        .iter()
+        .filter(|entry| known.insert(Asp::from(entry)))
+        .zip(1..)
+        .map(|(entry, count)| (entry.timestamp, count))
+        .collect()
+}
+
+/// Returns the time since the previous play of an [`Artist`], [`Album`] or [`Song`]
+/// for each of its plays but the first one, e.g. to see when it was
+/// listened to less often and when it came back
+///
+/// The gaps are in chronological order, dated at the play ending them
+#[must_use]
+pub fn listening_gaps<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+) -> Vec<(DateTime<Local>, TimeDelta)> {
+    entries
+        .iter()
+        .filter(|entry| aspect.is_entry(entry))
+        .tuple_windows()
+        .map(|(previous, next)| (next.timestamp, next.timestamp - previous.timestamp))
+        .collect()
+}
+
+/// Returns the first and last day of every streak of consecutive days
+/// with at least one play in `entries` in chronological order
+///
+/// Use [`longest_streak`] and [`current_streak`] for the streaks of an aspect
+#[must_use]
+pub fn streaks(entries: &[SongEntry]) -> Vec<(NaiveDate, NaiveDate)> {
+    day_streaks(entries.iter())
+}
+
+/// Returns the first and last day of the longest streak of consecutive days
+/// with at least one play of an [`Artist`], [`Album`] or [`Song`]
+///
+/// The earliest one is returned if several streaks are equally long.
+/// Returns [`None`] if `aspect` is not in `entries`
+#[must_use]
+pub fn longest_streak<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+) -> Option<(NaiveDate, NaiveDate)> {
+    day_streaks(entries.iter().filter(|entry| aspect.is_entry(entry)))
+        .into_iter()
+        .reduce(|longest, streak| {
+            if streak.1 - streak.0 > longest.1 - longest.0 {
+                streak
+            } else {
+                longest
+            }
+        })
+}
+
+/// Returns the first and last day of the ongoing streak of consecutive days
+/// with at least one play of an [`Artist`], [`Album`] or [`Song`],
+/// i.e. the streak that includes the day of the last entry
+///
+/// Returns [`None`] if `aspect` wasn't played on the day of the last entry
+#[must_use]
+pub fn current_streak<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let last_day = entries.last()?.timestamp.date_naive();
+    day_streaks(entries.iter().filter(|entry| aspect.is_entry(entry)))
+        .pop()
+        .filter(|(_, end)| *end == last_day)
+}
+
+/// Returns the first and last day of every streak of consecutive days
+/// of the chronologically sorted `entries`
+///
+/// Used by [`streaks`], [`longest_streak`] and [`current_streak`]
+fn day_streaks<'a>(entries: impl Iterator<Item = &'a SongEntry>) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut streaks: Vec<(NaiveDate, NaiveDate)> = vec![];
+    for day in entries.map(|entry| entry.timestamp.date_naive()).dedup() {
+        match streaks.last_mut() {
+            Some((_, end)) if end.succ_opt() == Some(day) => *end = day,
+            _ => streaks.push((day, day)),
+        }
+    }
+    streaks
+}
+
+/// Returns the plays of an [`Artist`], [`Album`] or [`Song`]
+/// in each month (January to December) of each year,
+/// e.g. to compare the years with each other
+///
+/// Covers every year from the first to the last play of `aspect`
+/// in chronological order, so years without plays are all zeros.
+/// Returns an empty [`Vec`] if `aspect` is not in `entries`
+///
+/// # Panics
+///
+/// Unwraps used on the conversions between year differences and [`usize`],
+/// but won't panic since entries are in chronological order
+#[must_use]
+pub fn monthly_plays_by_year<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+) -> Vec<(i32, [usize; 12])> {
+    let mut years: Vec<(i32, [usize; 12])> = vec![];
+
+    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
+        let year = entry.timestamp.year();
+        let first = *years.first().map_or(&year, |(first, _)| first);
+        let index = usize::try_from(year - first).unwrap();
+        while years.len() <= index {
+            let next = first + i32::try_from(years.len()).unwrap();
+            years.push((next, [0; 12]));
+        }
+        years[index].1[entry.timestamp.month0() as usize] += 1;
+    }
+
+    years
+}
+
+/// Returns the `n`th play of an [`Artist`], [`Album`] or [`Song`]
+///
+/// Counting starts at 1, i.e. `n = 1` returns the very first play.
+/// Returns [`None`] if `n` is 0 or if `aspect` has less than `n` plays
+#[must_use]
+pub fn nth_play<'a, Asp: Music>(
+    entries: &'a [SongEntry],
+    aspect: &Asp,
+    n: usize,
+) -> Option<&'a SongEntry> {
+    entries
+        .iter()
+        .filter(|entry| aspect.is_entry(entry))
+        .nth(n.checked_sub(1)?)
+}
+
+/// Returns the amount of plays for each hour of the day
+///
+/// Index `0` contains the plays between 00:00 and 00:59, index `1`
+/// the plays between 01:00 and 01:59 and so on (in local time)
+#[must_use]
+pub fn plays_per_hour(entries: &[SongEntry]) -> [usize; 24] {
+    let mut hours = [0; 24];
+    for entry in entries {
+        hours[entry.timestamp.hour() as usize] += 1;
+    }
+    hours
+}
+
+/// Returns the amount of plays of an [`Artist`], [`Album`] or [`Song`]
+/// for each hour of the day
+///
+/// See [`plays_per_hour`]
+#[must_use]
+pub fn plays_per_hour_of<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> [usize; 24] {
+    let mut hours = [0; 24];
+    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
+        hours[entry.timestamp.hour() as usize] += 1;
+    }
+    hours
+}
+
+/// Returns the time listened for each hour of the day
+///
+/// See [`plays_per_hour`]
+#[must_use]
+pub fn listening_time_per_hour(entries: &[SongEntry]) -> [TimeDelta; 24] {
+    let mut hours = [TimeDelta::zero(); 24];
+    for entry in entries {
+        hours[entry.timestamp.hour() as usize] += entry.time_played;
+    }
+    hours
+}
+
+/// Returns the time listened to an [`Artist`], [`Album`] or [`Song`]
+/// for each hour of the day
+///
+/// See [`plays_per_hour`]
+#[must_use]
+pub fn listening_time_per_hour_of<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+) -> [TimeDelta; 24] {
+    let mut hours = [TimeDelta::zero(); 24];
+    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
+        hours[entry.timestamp.hour() as usize] += entry.time_played;
+    }
+    hours
+}
+
+/// Returns a map with all [`Platforms`][Platform] and their playcount
+#[must_use]
+pub fn platforms(entries: &[SongEntry]) -> HashMap<Platform, usize> {
+    entries.iter().map(|entry| entry.platform).counts()
+}
+
+/// Returns a map with all [`Platforms`][Platform] and the time listened on them
+#[must_use]
+pub fn platforms_listening_time(entries: &[SongEntry]) -> HashMap<Platform, TimeDelta> {
+    let mut platforms: HashMap<Platform, TimeDelta> = HashMap::new();
+    for entry in entries {
+        *platforms
+            .entry(entry.platform)
+            .or_insert_with(TimeDelta::zero) += entry.time_played;
+    }
+    platforms
+}
+
+/// Splits the entries into listening sessions
+///
+/// A session ends when the pause between the end of one play
+/// and the start of the next one is longer than `max_gap`
+///
+/// Returns the sessions as slices of `entries` in chronological order
+#[must_use]
+pub fn sessions(entries: &[SongEntry], max_gap: TimeDelta) -> Vec<&[SongEntry]> {
+    let mut sessions = vec![];
+    let mut start = 0;
+
+    for (i, pair) in entries.windows(2).enumerate() {
+        // the timestamp of an entry is when it stopped playing
+        let start_of_next = pair[1].timestamp - pair[1].time_played;
+        if start_of_next - pair[0].timestamp > max_gap {
+            sessions.push(&entries[start..=i]);
+            start = i + 1;
+        }
+    }
+    if start < entries.len() {
+        sessions.push(&entries[start..]);
+    }
+
+    sessions
+}
+
+/// Returns how long a session (see [`sessions`]) lasted,
+/// i.e. the time from the start of its first play to the end of its last play
+///
+/// Returns [`TimeDelta::zero`] if `session` is empty
+#[must_use]
+pub fn session_length(session: &[SongEntry]) -> TimeDelta {
+    match (session.first(), session.last()) {
+        (Some(first), Some(last)) => last.timestamp - (first.timestamp - first.time_played),
+        _ => TimeDelta::zero(),
+    }
+}
+
+/// Returns the rank of `artist` among all artists at the end of each month
+/// together with the first day of that month
+///
+/// The rank is 1 for the most played artist, tied artists share a rank.
+/// If `lifetime` is true, artists are ranked by their plays up to and including
+/// that month, otherwise only by their plays within that month.
+///
+/// Months in which `artist` has no rank (i.e. no plays yet
+/// or no plays in that month respectively) are left out
+///
+/// # Panics
+///
+/// Unwrap used on [`NaiveDate::with_day`], but won't panic since
+/// every month has a first day
+#[must_use]
+pub fn artist_ranks(
+    entries: &[SongEntry],
+    artist: &Artist,
+    lifetime: bool,
+) -> Vec<(NaiveDate, usize)> {
+    let mut ranks = vec![];
+    let mut plays: HashMap<&str, usize> = HashMap::new();
+
+    let same_month = |a: &SongEntry, b: &SongEntry| {
+        a.timestamp.year() == b.timestamp.year() && a.timestamp.month() == b.timestamp.month()
+    };
+    for month in entries.chunk_by(same_month) {
+        if !lifetime {
+            plays.clear();
+        }
+        for entry in month {
+            *plays.entry(&entry.artist).or_default() += 1;
+        }
+
+        if let Some(&artist_plays) = plays.get(artist.name.as_ref()) {
+            let rank = 1 + plays.values().filter(|&&p| p > artist_plays).count();
+            let first_day = month[0].timestamp.date_naive().with_day(1).unwrap();
+            ranks.push((first_day, rank));
+        }
+    }
+
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads the example `endsong.json` from `stuff/example_endsong`
+    fn example_entries() -> crate::entry::SongEntries {
+        let paths = [format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        )];
+        crate::entry::SongEntries::new(&paths).unwrap()
+    }
+
+    #[test]
+    fn nth_play() {
+        let entries = example_entries();
+
+        let first = entries.first().unwrap();
+        let artist = Artist::from(first);
+        assert_eq!(super::nth_play(&entries, &artist, 1), Some(first));
+        assert_eq!(super::nth_play(&entries, &artist, 0), None);
+
+        let plays = plays(&entries, &artist);
+        assert!(super::nth_play(&entries, &artist, plays).is_some());
+        assert!(super::nth_play(&entries, &artist, plays + 1).is_none());
+    }
+
+    #[test]
+    fn album_completion() {
+        let entries = example_entries();
+
+        let album = Album::from(&entries[0]);
+        let played = songs_from(&entries, &album).len();
+        let unknown = Album::new("Unknown", "Unknown");
+        let track_counts = HashMap::from([(album.clone(), played * 2), (unknown.clone(), 10)]);
+
+        let completion = super::album_completion(&entries, &track_counts);
+        assert_eq!(completion.get(&album), Some(&0.5));
+        // never played
+        assert!(!completion.contains_key(&unknown));
+
+        // capped at 1.0
+        let track_counts = HashMap::from([(album.clone(), 1)]);
+        let completion = super::album_completion(&entries, &track_counts)[&album];
+        assert!((completion - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn one_hit_wonders() {
+        let entries = example_entries();
+
+        let wonders = super::one_hit_wonders(&entries, 50.0, 2);
+        // SID has 3 plays, 2 of them of the same song
+        let (song, song_plays, artist_plays) = &wonders[0];
+        assert_eq!(song.album.artist, Artist::new("SID"));
+        assert_eq!((*song_plays, *artist_plays), (2, 3));
+        // artists with a single play are skipped
+        assert_eq!(wonders.len(), 1);
+
+        // every artist with a single play is a one-hit wonder
+        assert_eq!(
+            super::one_hit_wonders(&entries, 99.0, 1).len(),
+            artists(&entries)
+                .values()
+                .filter(|&&plays| plays == 1)
+                .count()
+        );
+        assert!(super::one_hit_wonders(&entries, 100.0, 1).is_empty());
+    }
+
+    #[test]
+    fn longest_streak() {
+        let entries = example_entries();
+
+        // Sabaton has a single play
+        let sabaton = Artist::new("Sabaton");
+        let day = first_play(&entries, &sabaton).unwrap().date_naive();
+        assert_eq!(super::longest_streak(&entries, &sabaton), Some((day, day)));
+
+        let (start, end) = super::longest_streak(&entries, &Artist::new("SID")).unwrap();
+        assert!(start <= end);
+
+        assert!(super::longest_streak(&entries, &Artist::new("Powerwolf")).is_none());
+
+        // every day with a play is in exactly one streak
+        let all = super::streaks(&entries);
+        let days = entries
+            .iter()
+            .map(|entry| entry.timestamp.date_naive())
+            .unique()
+            .count();
+        assert_eq!(
+            all.iter()
+                .map(|(start, end)| (*end - *start).num_days() + 1)
+                .sum::<i64>(),
+            i64::try_from(days).unwrap()
+        );
+        assert!(all.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        // the artist of the last entry has an ongoing streak
+        let last = Artist::from(entries.last().unwrap());
+        let (_, end) = current_streak(&entries, &last).unwrap();
+        assert_eq!(end, entries.last_date().date_naive());
+        assert!(current_streak(&entries, &sabaton).is_none());
+    }
+
+    #[test]
+    fn sessions() {
+        let entries = example_entries();
+
+        let sessions = super::sessions(&entries, TimeDelta::try_minutes(30).unwrap());
+        // every entry is in exactly one session
+        assert_eq!(
+            sessions.iter().map(|s| s.len()).sum::<usize>(),
+            entries.len()
+        );
+        assert!(sessions
+            .iter()
+            .all(|s| session_length(s) > TimeDelta::zero()));
+
+        // with an absurdly long gap everything is a single session
+        let all = super::sessions(&entries, TimeDelta::try_weeks(52 * 100).unwrap());
+        assert_eq!(all.len(), 1);
+
+        assert!(super::sessions(&[], TimeDelta::zero()).is_empty());
+    }
+
+    #[test]
+    fn artist_ranks() {
+        let entries = example_entries();
+
+        let artist = Artist::from(entries.last().unwrap());
+        let ranks = super::artist_ranks(&entries, &artist, true);
+        assert!(ranks.iter().all(|(_, rank)| *rank >= 1));
+
+        // the last lifetime rank is the rank among all plays
+        let all = artists(&entries);
+        let artist_plays = all[&artist];
+        let expected = 1 + all.values().filter(|&&p| p > artist_plays).count();
+        assert_eq!(ranks.last().unwrap().1, expected);
+
+        // the artist was played in the last month
+        let monthly = super::artist_ranks(&entries, &artist, false);
+        assert_eq!(monthly.last().unwrap().0, ranks.last().unwrap().0);
+    }
+
+    #[test]
+    fn artist_discoveries() {
+        let entries = example_entries();
+
+        let discoveries = super::artist_discoveries(&entries);
+        assert_eq!(discoveries.len(), artists(&entries).len());
+        for entry in discoveries {
+            let artist = Artist::from(entry);
+            assert_eq!(first_play(&entries, &artist), Some(entry.timestamp));
+        }
+    }
+
+    #[test]
+    fn library_growth() {
+        let entries = example_entries();
+
+        let growth = super::library_growth::<Artist>(&entries);
+        assert_eq!(growth.last().unwrap().1, artists(&entries).len());
+        // the same as the discoveries
+        let discoveries = super::artist_discoveries(&entries);
+        assert!(growth
+            .iter()
+            .zip(discoveries)
+            .all(|((date, _), entry)| *date == entry.timestamp));
+
+        let growth = super::library_growth::<Album>(&entries);
+        assert_eq!(growth.last().unwrap().1, albums(&entries).len());
+        let growth = super::library_growth::<Song>(&entries);
+        assert_eq!(growth.last().unwrap().1, songs(&entries, false).len());
+    }
+
+    #[test]
+    fn listening_gaps() {
+        let entries = example_entries();
+        let artist = Artist::new("SID");
+
+        let gaps = super::listening_gaps(&entries, &artist);
+        assert_eq!(gaps.len(), plays(&entries, &artist) - 1);
+        assert!(gaps.iter().all(|(_, gap)| *gap >= TimeDelta::zero()));
+
+        // the gaps add up to the time between the first and the last play
+        let first = first_play(&entries, &artist).unwrap();
+        let total: TimeDelta = gaps.iter().map(|(_, gap)| *gap).sum();
+        assert_eq!(first + total, gaps.last().unwrap().0);
+    }
+
+    #[test]
+    fn monthly_plays_by_year() {
+        let entries = example_entries();
+        let artist = Artist::new("SID");
+
+        let years = super::monthly_plays_by_year(&entries, &artist);
+        let sum: usize = years.iter().flat_map(|(_, months)| months).sum();
+        assert_eq!(sum, plays(&entries, &artist));
+        assert_eq!(
+            years.first().unwrap().0,
+            first_play(&entries, &artist).unwrap().year()
+        );
+
+        assert!(super::monthly_plays_by_year(&entries, &Artist::new("not an artist")).is_empty());
+    }
+}
@@ -16,17 +16,23 @@
 
 pub mod aspect;
 pub mod entry;
+pub mod export;
 pub mod find;
 pub mod gather;
+#[cfg(feature = "musicbrainz")]
+pub mod musicbrainz;
+pub mod series;
+#[cfg(feature = "spotify")]
+pub mod spotify;
 
 mod parse;
 
 /// Re-exports the most commonly used items from this crate
 /// and its dependencies.
 pub mod prelude {
-    pub use crate::{find, gather};
+    pub use crate::{find, gather, series};
 
-    pub use crate::entry::{SongEntries, SongEntry};
+    pub use crate::entry::{Platform, SongEntries, SongEntry};
 
     pub use crate::aspect::{Album, Artist, Song};
     pub use crate::aspect::{HasSongs, Music};
@@ -0,0 +1,404 @@
+//! Module for enriching artists and albums with data from [MusicBrainz](https://musicbrainz.org)
+//!
+//! Only available with the `musicbrainz` feature
+//!
+//! Resolves [`Artists`][Artist] and [`Albums`][Album] to their `MusicBrainz` entries
+//! to get their canonical names, genres and release years. Every answer (including
+//! "not found") is cached on disk, so each aspect is only looked up once,
+//! and requests are limited to one per second as required by `MusicBrainz`.
+//!
+//! ```no_run
+//! use endsong::prelude::*;
+//! use endsong::musicbrainz::MusicBrainz;
+//!
+//! let mut musicbrainz = MusicBrainz::new("musicbrainz_cache.json")?;
+//!
+//! if let Some(info) = musicbrainz.artist(&Artist::new("Sabaton"))? {
+//!     println!("{}: {}", info.name, info.genres.join(", "));
+//! }
+//! if let Some(info) = musicbrainz.album(&Album::new("Coat of Arms", "Sabaton"))? {
+//!     println!("{} ({:?})", info.title, info.year);
+//! }
+//!
+//! // writes the cache to disk
+//! musicbrainz.save()?;
+//! # Ok::<(), endsong::musicbrainz::MusicBrainzError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use crate::aspect::{Album, Artist};
+
+/// Base URL of the `MusicBrainz` API
+const API: &str = "https://musicbrainz.org/ws/2";
+
+/// Minimum time between two requests
+/// (see <https://musicbrainz.org/doc/MusicBrainz_API/Rate_Limiting>)
+const RATE_LIMIT: Duration = Duration::from_secs(1);
+
+/// Minimum search score (0-100) for a search result to be accepted
+const MIN_SCORE: u8 = 90;
+
+/// Errors that can occur when querying `MusicBrainz`
+#[derive(Error, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum MusicBrainzError {
+    /// Used when the request fails
+    #[error("Error while querying MusicBrainz: {0}")]
+    Http(#[from] Box<ureq::Error>),
+    /// Used when reading the response or the cache fails
+    #[error("Error while reading/writing: {0}")]
+    Io(#[from] std::io::Error),
+    /// Used when the cache can't be (de)serialized
+    #[error("Error while (de)serializing the cache: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Information about an [`Artist`] from `MusicBrainz`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtistInfo {
+    /// `MusicBrainz` ID of the artist
+    pub mbid: String,
+    /// canonical name of the artist
+    pub name: String,
+    /// genres of the artist, most voted first
+    pub genres: Vec<String>,
+}
+
+/// Information about an [`Album`] from `MusicBrainz` (a release group)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlbumInfo {
+    /// `MusicBrainz` ID of the release group
+    pub mbid: String,
+    /// canonical title of the album
+    pub title: String,
+    /// year of the first release
+    pub year: Option<i32>,
+    /// genres of the album, most voted first
+    pub genres: Vec<String>,
+}
+
+/// On-disk cache of the lookups, [`None`] if `MusicBrainz` didn't find anything
+#[derive(Default, Serialize, Deserialize)]
+struct Cache {
+    /// key: artist name
+    artists: HashMap<String, Option<ArtistInfo>>,
+    /// key: "artist name - album name"
+    albums: HashMap<String, Option<AlbumInfo>>,
+}
+
+/// Search result of an artist or release group
+#[derive(Deserialize)]
+struct SearchResult {
+    /// `MusicBrainz` ID
+    id: String,
+    /// how well the result matches the query (0-100)
+    score: u8,
+}
+
+/// Response of an artist search
+#[derive(Deserialize)]
+struct ArtistSearch {
+    /// the results, best match first
+    artists: Vec<SearchResult>,
+}
+
+/// Response of a release group search
+#[derive(Deserialize)]
+struct ReleaseGroupSearch {
+    /// the results, best match first
+    #[serde(rename = "release-groups")]
+    release_groups: Vec<SearchResult>,
+}
+
+/// Genre of an artist or release group lookup
+#[derive(Deserialize)]
+struct Genre {
+    /// name of the genre
+    name: String,
+    /// number of votes
+    count: u32,
+}
+
+/// Response of an artist lookup with genres
+#[derive(Deserialize)]
+struct ArtistLookup {
+    /// canonical name
+    name: String,
+    /// genres of the artist
+    #[serde(default)]
+    genres: Vec<Genre>,
+}
+
+/// Response of a release group lookup with genres
+#[derive(Deserialize)]
+struct ReleaseGroupLookup {
+    /// canonical title
+    title: String,
+    /// `YYYY-MM-DD`, `YYYY-MM` or `YYYY` (or empty)
+    #[serde(rename = "first-release-date", default)]
+    first_release_date: String,
+    /// genres of the release group
+    #[serde(default)]
+    genres: Vec<Genre>,
+}
+
+/// Client for looking up artists and albums on `MusicBrainz`
+///
+/// See the [module documentation][self] for an example
+pub struct MusicBrainz {
+    /// where the cache is saved
+    cache_path: PathBuf,
+    /// answers of previous lookups
+    cache: Cache,
+    /// HTTP client
+    agent: ureq::Agent,
+    /// time of the last request, for rate limiting
+    last_request: Option<Instant>,
+}
+impl MusicBrainz {
+    /// Creates a new client using (and later saving) the cache at `cache_path`
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cache file exists but can't be read
+    pub fn new<P: AsRef<Path>>(cache_path: P) -> Result<Self, MusicBrainzError> {
+        let cache_path = cache_path.as_ref().to_path_buf();
+        let cache = match std::fs::read(&cache_path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Cache::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let agent = ureq::AgentBuilder::new()
+            // required by MusicBrainz
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION"),
+                " ( https://github.com/fsktom/rusty-endsong-parser )"
+            ))
+            .build();
+
+        Ok(MusicBrainz {
+            cache_path,
+            cache,
+            agent,
+            last_request: None,
+        })
+    }
+
+    /// Writes the cache to disk
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cache can't be written
+    pub fn save(&self) -> Result<(), MusicBrainzError> {
+        let contents = serde_json::to_vec(&self.cache)?;
+        std::fs::write(&self.cache_path, contents)?;
+        Ok(())
+    }
+
+    /// Looks up the artist on `MusicBrainz`
+    ///
+    /// Returns [`None`] if there's no artist matching the name well enough
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn artist(&mut self, artist: &Artist) -> Result<Option<ArtistInfo>, MusicBrainzError> {
+        if let Some(info) = self.cache.artists.get(&*artist.name) {
+            return Ok(info.clone());
+        }
+
+        info!("Looking up {artist} on MusicBrainz...");
+        let query = format!("artist:\"{}\"", escape(&artist.name));
+        let search: ArtistSearch = self.get("artist", &[("query", &query), ("limit", "1")])?;
+
+        let info = match best_match(search.artists) {
+            Some(id) => {
+                let lookup: ArtistLookup =
+                    self.get(&format!("artist/{id}"), &[("inc", "genres")])?;
+                Some(ArtistInfo {
+                    mbid: id,
+                    name: lookup.name,
+                    genres: genre_names(lookup.genres),
+                })
+            }
+            None => None,
+        };
+
+        self.cache
+            .artists
+            .insert(artist.name.to_string(), info.clone());
+        Ok(info)
+    }
+
+    /// Looks up the album on `MusicBrainz`
+    ///
+    /// Returns [`None`] if there's no release group by the artist
+    /// matching the name well enough
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn album(&mut self, album: &Album) -> Result<Option<AlbumInfo>, MusicBrainzError> {
+        let key = album.to_string();
+        if let Some(info) = self.cache.albums.get(&key) {
+            return Ok(info.clone());
+        }
+
+        info!("Looking up {album} on MusicBrainz...");
+        let query = format!(
+            "releasegroup:\"{}\" AND artist:\"{}\"",
+            escape(&album.name),
+            escape(&album.artist.name)
+        );
+        let search: ReleaseGroupSearch =
+            self.get("release-group", &[("query", &query), ("limit", "1")])?;
+
+        let info = match best_match(search.release_groups) {
+            Some(id) => {
+                let lookup: ReleaseGroupLookup =
+                    self.get(&format!("release-group/{id}"), &[("inc", "genres")])?;
+                Some(AlbumInfo {
+                    mbid: id,
+                    title: lookup.title,
+                    year: lookup
+                        .first_release_date
+                        .get(..4)
+                        .and_then(|year| year.parse().ok()),
+                    genres: genre_names(lookup.genres),
+                })
+            }
+            None => None,
+        };
+
+        self.cache.albums.insert(key, info.clone());
+        Ok(info)
+    }
+
+    /// Sends a GET request to the given API endpoint
+    /// with the given query parameters, respecting the rate limit
+    fn get<T: serde::de::DeserializeOwned>(
+        &mut self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T, MusicBrainzError> {
+        if let Some(remaining) = self
+            .last_request
+            .and_then(|last| RATE_LIMIT.checked_sub(last.elapsed()))
+        {
+            sleep(remaining);
+        }
+        self.last_request = Some(Instant::now());
+
+        let response = self
+            .agent
+            .get(&format!("{API}/{endpoint}"))
+            .query_pairs(params.iter().copied())
+            .query("fmt", "json")
+            .call()
+            .map_err(Box::new)?;
+
+        Ok(response.into_json()?)
+    }
+}
+
+/// Returns the ID of the first search result if it matches well enough
+fn best_match(results: Vec<SearchResult>) -> Option<String> {
+    results
+        .into_iter()
+        .next()
+        .filter(|result| result.score >= MIN_SCORE)
+        .map(|result| result.id)
+}
+
+/// Returns the names of the genres, most voted first
+fn genre_names(mut genres: Vec<Genre>) -> Vec<String> {
+    genres.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    genres.into_iter().map(|genre| genre.name).collect()
+}
+
+/// Escapes `"` and `\` so that `term` can be used as a quoted phrase
+/// in a (Lucene) search query
+fn escape(term: &str) -> String {
+    term.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_lookups() {
+        let path = std::env::temp_dir().join(format!("endsong_mb_{}.json", std::process::id()));
+        let sabaton = ArtistInfo {
+            mbid: String::from("39a31de6-763d-48b8-a7ba-cf7ed2b4b8a1"),
+            name: String::from("Sabaton"),
+            genres: vec![String::from("power metal")],
+        };
+
+        let mut musicbrainz = MusicBrainz::new(&path).unwrap();
+        musicbrainz
+            .cache
+            .artists
+            .insert(String::from("Sabaton"), Some(sabaton.clone()));
+        musicbrainz
+            .cache
+            .albums
+            .insert(String::from("Sabaton - Unknown"), None);
+        musicbrainz.save().unwrap();
+
+        // answered from the cache, no requests
+        let mut musicbrainz = MusicBrainz::new(&path).unwrap();
+        assert_eq!(
+            musicbrainz.artist(&Artist::new("Sabaton")).unwrap(),
+            Some(sabaton)
+        );
+        assert_eq!(
+            musicbrainz
+                .album(&Album::new("Unknown", "Sabaton"))
+                .unwrap(),
+            None
+        );
+        assert!(musicbrainz.last_request.is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn query_helpers() {
+        assert_eq!(escape(r#"12" Mix \ Remaster"#), r#"12\" Mix \\ Remaster"#);
+
+        let results = |score| {
+            vec![SearchResult {
+                id: String::from("id"),
+                score,
+            }]
+        };
+        assert_eq!(best_match(results(100)), Some(String::from("id")));
+        assert_eq!(best_match(results(50)), None);
+        assert_eq!(best_match(Vec::new()), None);
+
+        let genre = |name: &str, count| Genre {
+            name: name.to_string(),
+            count,
+        };
+        assert_eq!(
+            genre_names(vec![
+                genre("rock", 1),
+                genre("metal", 3),
+                genre("heavy metal", 3)
+            ]),
+            vec!["heavy metal", "metal", "rock"]
+        );
+    }
+}
@@ -12,9 +12,9 @@ use chrono::{DateTime, Local, TimeDelta, TimeZone};
 use itertools::Itertools;
 use serde::Deserialize;
 use thiserror::Error;
-use tracing::{error, info, info_span};
+use tracing::{error, info};
 
-use crate::entry::SongEntry;
+use crate::entry::{fold_case, Platform, SongEntry};
 
 /// Errors that can occur when parsing an endsong.json file
 #[derive(Error, Debug)]
@@ -37,6 +37,13 @@ pub enum ParseError {
     /// Used when reading the file fails
     #[error("Error while opening {1}: {0}")]
     Io(std::io::Error, Box<Path>),
+    /// Used when serde deserialization of the n-th reader
+    /// (starting at 1) of [`parse_readers`] fails
+    #[error("Error while parsing reader #{1}: {0}")]
+    ReaderSerde(serde_json::Error, usize),
+    /// Used when reading the n-th reader (starting at 1) of [`parse_readers`] fails
+    #[error("Error while reading reader #{1}: {0}")]
+    ReaderIo(std::io::Error, usize),
 }
 
 // https://stackoverflow.com/questions/44205435/how-to-deserialize-a-json-file-which-contains-null-values-using-serde
@@ -55,9 +62,10 @@ struct Entry {
     /// Skipped
     #[serde(skip_deserializing)]
     _username: (),
-    /// Skipped for now: maybe use it for sth
-    #[serde(skip_deserializing)]
-    _platform: String,
+    /// Device the song has been played on
+    ///
+    /// Option because it's not guaranteed to be there
+    platform: Option<String>,
     /// Miliseconds the song has been played for
     ms_played: i64,
     /// Skipped
@@ -116,6 +124,10 @@ struct Entry {
     _incognito_mode: (),
 }
 
+/// An interned name together with its interned case-folded version,
+/// see [`map_rc_name`]
+type Names = (Rc<str>, Rc<str>);
+
 /// Main parsing function that parses many `endsong.json` files
 ///
 /// Returns a vector of [`SongEntry`]s sorted by timestamp
@@ -125,38 +137,86 @@ struct Entry {
 /// Will return an error if any of the files can't be opened or read
 pub fn parse<P: AsRef<Path> + std::fmt::Debug>(paths: &[P]) -> Result<Vec<SongEntry>, ParseError> {
     info!("Parsing {} files", paths.len());
-    // at least for me: about 15.8k-15.95k entries per file
-    // to prevent reallocations?
-    let mut song_entries: Vec<SongEntry> = Vec::with_capacity(16_000 * paths.len());
-
-    let mut song_names: HashMap<String, Rc<str>> = HashMap::with_capacity(10_000);
-    let mut album_names: HashMap<String, Rc<str>> = HashMap::with_capacity(10_000);
-    let mut artist_names: HashMap<String, Rc<str>> = HashMap::with_capacity(5_000);
-
-    let mut timestamps: HashSet<DateTime<Local>> = HashSet::with_capacity(16_000 * paths.len());
-
-    for path in paths {
-        let p = path.as_ref();
-        let span = info_span!("file", path = ?p);
-        let _guard = span.enter();
-        info!("currently parsing");
-        let mut one = match parse_single(
-            path,
-            &mut song_names,
-            &mut album_names,
-            &mut artist_names,
-            &mut timestamps,
-        ) {
-            Ok(parsed) => parsed,
-            Err(SingleParseError::Io(e)) => {
-                error!("failed to open");
-                return Err(ParseError::Io(e, p.into()));
+
+    // files are only opened once the previous one has been parsed
+    let files = paths.iter().map(|path| {
+        info!(path = ?path.as_ref(), "currently parsing");
+        File::open(path)
+    });
+
+    parse_many(files).map_err(|(e, i)| {
+        let p: Box<Path> = paths[i].as_ref().into();
+        match e {
+            SingleParseError::Io(e) => {
+                error!(path = ?p, "failed to open");
+                ParseError::Io(e, p)
             }
-            Err(SingleParseError::Serde(e)) => {
-                error!("failed to parse");
-                return Err(ParseError::Serde(e, p.into()));
+            SingleParseError::Serde(e) => {
+                error!(path = ?p, "failed to parse");
+                ParseError::Serde(e, p)
             }
-        };
+        }
+    })
+}
+
+/// Parses the contents of many `endsong.json` files from `readers`
+/// (e.g. uploaded files) instead of from paths like [`parse`]
+///
+/// Returns a vector of [`SongEntry`]s sorted by timestamp
+///
+/// # Errors
+///
+/// Will return an error if any of the readers can't be read
+/// or doesn't contain a valid `endsong.json`
+pub fn parse_readers<R: Read>(
+    readers: impl IntoIterator<Item = R>,
+) -> Result<Vec<SongEntry>, ParseError> {
+    let readers = readers.into_iter().enumerate().map(|(i, reader)| {
+        info!("Parsing reader #{}", i + 1);
+        Ok(reader)
+    });
+
+    parse_many(readers).map_err(|(e, i)| match e {
+        SingleParseError::Io(e) => ParseError::ReaderIo(e, i + 1),
+        SingleParseError::Serde(e) => ParseError::ReaderSerde(e, i + 1),
+    })
+}
+
+/// Parses every reader with [`parse_single`] into one vector
+/// of [`SongEntry`]s sorted by timestamp
+///
+/// Returns the first error together with the index of its reader
+fn parse_many<R: Read>(
+    readers: impl IntoIterator<Item = std::io::Result<R>>,
+) -> Result<Vec<SongEntry>, (SingleParseError, usize)> {
+    let readers = readers.into_iter();
+    let files = readers.size_hint().0.max(1);
+
+    // at least for me: about 15.8k-15.95k entries per file
+    // to prevent reallocations?
+    let mut song_entries: Vec<SongEntry> = Vec::with_capacity(16_000 * files);
+
+    let mut song_names: HashMap<String, Names> = HashMap::with_capacity(10_000);
+    let mut album_names: HashMap<String, Names> = HashMap::with_capacity(10_000);
+    let mut artist_names: HashMap<String, Names> = HashMap::with_capacity(5_000);
+    let mut folded_names: HashMap<String, Rc<str>> = HashMap::with_capacity(20_000);
+
+    let mut timestamps: HashSet<DateTime<Local>> = HashSet::with_capacity(16_000 * files);
+
+    for (i, reader) in readers.enumerate() {
+        let mut one = reader
+            .map_err(SingleParseError::from)
+            .and_then(|reader| {
+                parse_single(
+                    reader,
+                    &mut song_names,
+                    &mut album_names,
+                    &mut artist_names,
+                    &mut folded_names,
+                    &mut timestamps,
+                )
+            })
+            .map_err(|e| (e, i))?;
         song_entries.append(&mut one);
     }
 
@@ -168,25 +228,34 @@ pub fn parse<P: AsRef<Path> + std::fmt::Debug>(paths: &[P]) -> Result<Vec<SongEn
     Ok(song_entries)
 }
 
-/// Responsible for parsing the a single `endsong.json` file into a vector of [`SongEntry`]
-#[instrument]
-fn parse_single<P: AsRef<Path> + std::fmt::Debug>(
-    path: P,
-    song_names: &mut HashMap<String, Rc<str>>,
-    album_names: &mut HashMap<String, Rc<str>>,
-    artist_names: &mut HashMap<String, Rc<str>>,
+/// Responsible for parsing the contents of a single `endsong.json` file into a vector of [`SongEntry`]
+#[instrument(skip_all)]
+fn parse_single<R: Read>(
+    mut reader: R,
+    song_names: &mut HashMap<String, Names>,
+    album_names: &mut HashMap<String, Names>,
+    artist_names: &mut HashMap<String, Names>,
+    folded_names: &mut HashMap<String, Rc<str>>,
     timestamps: &mut HashSet<DateTime<Local>>,
 ) -> Result<Vec<SongEntry>, SingleParseError> {
     // https://github.com/serde-rs/json/issues/160#issuecomment-253446892
+    // reading it all at once is a lot faster than serde_json::from_reader
     let mut file_contents = String::new();
-    File::open(path)?.read_to_string(&mut file_contents)?;
+    reader.read_to_string(&mut file_contents)?;
     let full_entries: Vec<Entry> = serde_json::from_str(&file_contents)?;
 
     // convert each Entry to a SongEntry (ignoring podcast streams)
     let song_entries = full_entries
         .into_iter()
         .filter_map(|entry| {
-            entry_to_songentry(entry, song_names, album_names, artist_names, timestamps)
+            entry_to_songentry(
+                entry,
+                song_names,
+                album_names,
+                artist_names,
+                folded_names,
+                timestamps,
+            )
         })
         .collect_vec();
 
@@ -196,9 +265,10 @@ fn parse_single<P: AsRef<Path> + std::fmt::Debug>(
 /// Converts the genral [`Entry`] to a more specific [`SongEntry`]
 fn entry_to_songentry(
     entry: Entry,
-    song_names: &mut HashMap<String, Rc<str>>,
-    album_names: &mut HashMap<String, Rc<str>>,
-    artist_names: &mut HashMap<String, Rc<str>>,
+    song_names: &mut HashMap<String, Names>,
+    album_names: &mut HashMap<String, Names>,
+    artist_names: &mut HashMap<String, Names>,
+    folded_names: &mut HashMap<String, Rc<str>>,
     timestamps: &mut HashSet<DateTime<Local>>,
 ) -> Option<SongEntry> {
     let timestamp = parse_date(&entry.ts);
@@ -211,9 +281,18 @@ fn entry_to_songentry(
     // ? to remove podcast entries
     // if the track is None, so are album and artist
 
-    let track = map_rc_name(song_names, &entry.master_metadata_track_name?);
-    let album = map_rc_name(album_names, &entry.master_metadata_album_album_name?);
-    let artist = map_rc_name(artist_names, &entry.master_metadata_album_artist_name?);
+    let (track, folded_track) =
+        map_rc_name(song_names, folded_names, &entry.master_metadata_track_name?);
+    let (album, folded_album) = map_rc_name(
+        album_names,
+        folded_names,
+        &entry.master_metadata_album_album_name?,
+    );
+    let (artist, folded_artist) = map_rc_name(
+        artist_names,
+        folded_names,
+        &entry.master_metadata_album_artist_name?,
+    );
 
     Some(SongEntry {
         timestamp,
@@ -223,20 +302,48 @@ fn entry_to_songentry(
         album,
         artist,
         id: entry.spotify_track_uri?,
+        platform: Platform::from_raw(entry.platform.as_deref().unwrap_or_default()),
+        folded_track,
+        folded_album,
+        folded_artist,
     })
 }
 
 /// Checks if the given `name` is in the `map` and does [`Rc::clone`] on it
+/// and its case-folded version
 ///
 /// If it's not in the map, it clones the String value into an
-/// [`Rc`] and inserts it into the map
-fn map_rc_name(map: &mut HashMap<String, Rc<str>>, name: &str) -> Rc<str> {
-    if let Some(name_rc) = map.get(name) {
-        Rc::clone(name_rc)
-    } else {
-        map.insert(name.to_string(), Rc::from(name));
-        Rc::clone(map.get(name).unwrap())
+/// [`Rc`] and inserts it into the map together with its case-folded version,
+/// which is shared with other names folded to the same one (see `folded_names`)
+/// or with the name itself if it's already lowercase
+fn map_rc_name(
+    map: &mut HashMap<String, Names>,
+    folded_names: &mut HashMap<String, Rc<str>>,
+    name: &str,
+) -> Names {
+    if let Some((name_rc, folded_rc)) = map.get(name) {
+        return (Rc::clone(name_rc), Rc::clone(folded_rc));
     }
+
+    let name_rc: Rc<str> = Rc::from(name);
+    let folded = fold_case(name);
+    let folded_rc = match folded_names.get(&folded) {
+        Some(folded_rc) => Rc::clone(folded_rc),
+        None if folded == name => {
+            folded_names.insert(folded, Rc::clone(&name_rc));
+            Rc::clone(&name_rc)
+        }
+        None => {
+            let folded_rc: Rc<str> = Rc::from(folded.as_str());
+            folded_names.insert(folded, Rc::clone(&folded_rc));
+            folded_rc
+        }
+    };
+    map.insert(
+        name.to_string(),
+        (Rc::clone(&name_rc), Rc::clone(&folded_rc)),
+    );
+    (name_rc, folded_rc)
 }
 
 /// Used by [`entry_to_songentry()`]
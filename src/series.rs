@@ -0,0 +1,579 @@
+//! Module for time series of the plays of artists, albums and songs
+//!
+//! Each [`Series`] is a list of points in time with a value, independent
+//! of any charting library, e.g. to feed other charting backends
+//! or for numeric analysis.
+//!
+//! # Examples
+//! ```rust
+//! use endsong::prelude::*;
+//!
+//! // create SongEntries from a single file
+//! let paths = vec![format!(
+//!     "{}/stuff/example_endsong/endsong_0.json",
+//!     std::env::current_dir().unwrap().display()
+//! )];
+//! let entries = SongEntries::new(&paths).unwrap();
+//!
+//! let artist = Artist::new("Sabaton");
+//!
+//! // cumulative plays after each play
+//! let _ = series::absolute(&entries, &artist);
+//!
+//! // plays within each month
+//! let _ = series::binned(&entries, &artist, series::Bin::Month, false);
+//!
+//! // share of all plays so far in %
+//! let _ = series::relative::to_all(&entries, &artist);
+//!
+//! // cumulative plays at the end of each week
+//! let _ = series::coarsen(series::absolute(&entries, &artist), series::Bin::Week);
+//! ```
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeDelta, TimeZone};
+
+use crate::aspect::Music;
+use crate::entry::SongEntry;
+
+/// Points in time with a value, in chronological order
+pub type Series = Vec<(DateTime<Local>, f64)>;
+
+/// Time span over which plays are summed up in a [`binned`] series
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bin {
+    /// a single day
+    Day,
+    /// a week starting on Monday
+    Week,
+    /// a calendar month
+    Month,
+}
+impl Bin {
+    /// Returns the first day of the bin `date` is in
+    ///
+    /// # Panics
+    ///
+    /// Unwraps used on [`TimeDelta::try_days`] and [`NaiveDate::with_day`],
+    /// but won't panic since only durations of less than a week
+    /// and the first day of a month are created
+    #[must_use]
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Bin::Day => date,
+            Bin::Week => {
+                date - TimeDelta::try_days(date.weekday().num_days_from_monday().into()).unwrap()
+            }
+            Bin::Month => date.with_day(1).unwrap(),
+        }
+    }
+}
+impl Display for Bin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bin::Day => write!(f, "day"),
+            Bin::Week => write!(f, "week"),
+            Bin::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// Returns the start of the day in the [`Local`] timezone
+fn start_of_day(date: NaiveDate) -> DateTime<Local> {
+    // midnight doesn't exist on some days with DST changes, then 01:00 is used
+    let at = |hour| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+    };
+    at(0).or_else(|| at(1)).unwrap()
+}
+
+/// Returns the absolute amount of plays of an [`Artist`][crate::aspect::Artist],
+/// [`Album`][crate::aspect::Album] or [`Song`][crate::aspect::Song]
+/// after each of its plays
+///
+/// Returns an empty series if `aspect` is not in `entries`
+#[must_use]
+pub fn absolute<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> Series {
+    // since each date represents a single listen, we can just count up
+    entries
+        .iter()
+        .filter(|entry| aspect.is_entry(entry))
+        .zip(1..)
+        .map(|(entry, plays)| (entry.timestamp, f64::from(plays)))
+        .collect()
+}
+
+/// Returns the minutes listened to an [`Artist`][crate::aspect::Artist],
+/// [`Album`][crate::aspect::Album] or [`Song`][crate::aspect::Song]
+/// in total after each of its plays
+///
+/// Like [`absolute`], but weighs each play by how long it was listened to
+///
+/// Returns an empty series if `aspect` is not in `entries`
+#[must_use]
+pub fn absolute_duration<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> Series {
+    let mut minutes = 0.0;
+    entries
+        .iter()
+        .filter(|entry| aspect.is_entry(entry))
+        .map(|entry| {
+            minutes += minutes_played(entry);
+            (entry.timestamp, minutes)
+        })
+        .collect()
+}
+
+/// Returns the time listened of an entry in (fractional) minutes
+#[allow(clippy::cast_precision_loss)]
+fn minutes_played(entry: &SongEntry) -> f64 {
+    entry.time_played.num_milliseconds() as f64 / 60_000.0
+}
+
+/// Keeps only the last point of each [`Bin`]
+///
+/// Shrinks e.g. an [`absolute`] series with one point per play
+/// to one point per week. The kept points retain their own dates,
+/// so each one is the value at the end of its bin.
+#[must_use]
+pub fn coarsen(series: Series, bin: Bin) -> Series {
+    let mut coarse = Series::new();
+
+    for (date, value) in series {
+        let start = bin.start(date.date_naive());
+        match coarse.last_mut() {
+            // series are chronological, so a later point of the same bin replaces it
+            Some(last) if bin.start(last.0.date_naive()) == start => *last = (date, value),
+            _ => coarse.push((date, value)),
+        }
+    }
+
+    coarse
+}
+
+/// How a [`Series`] is smoothed, see [`smooth`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing {
+    /// exponential moving average with the given factor between 0 and 1 -
+    /// the higher, the more weight the latest point has
+    Exponential(f64),
+    /// average of the given amount of last points
+    Window(usize),
+}
+
+/// Smooths the values of a series, e.g. to keep the noise
+/// at the start of [relative] series from dominating them
+///
+/// A [`Smoothing::Exponential`] factor outside of 0 to 1 is clamped to that range,
+/// a [`Smoothing::Window`] of 0 points is treated like a window of 1 point
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn smooth(series: Series, smoothing: Smoothing) -> Series {
+    match smoothing {
+        Smoothing::Exponential(factor) => {
+            let factor = factor.clamp(0.0, 1.0);
+            let mut average: Option<f64> = None;
+            series
+                .into_iter()
+                .map(|(date, value)| {
+                    let smoothed = match average {
+                        Some(average) => factor * value + (1.0 - factor) * average,
+                        None => value,
+                    };
+                    average = Some(smoothed);
+                    (date, smoothed)
+                })
+                .collect()
+        }
+        Smoothing::Window(points) => {
+            let points = points.max(1);
+            let values = series.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+            let mut window_sum = 0.0;
+            series
+                .into_iter()
+                .enumerate()
+                .map(|(index, (date, value))| {
+                    window_sum += value;
+                    if index >= points {
+                        window_sum -= values[index - points];
+                    }
+                    let window_points = (index + 1).min(points);
+                    (date, window_sum / window_points as f64)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Returns the amount of plays (or minutes if `minutes` is true)
+/// of an aspect within each [`Bin`], dated at the start of the bin
+///
+/// Unlike [`absolute`], this shows when the aspect wasn't listened to.
+/// Bins without plays are left out.
+///
+/// Returns an empty series if `aspect` is not in `entries`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn binned<Asp: Music>(entries: &[SongEntry], aspect: &Asp, bin: Bin, minutes: bool) -> Series {
+    // first day of each bin with the plays and (fractional) minutes listened in it
+    let mut bins = Vec::<(NaiveDate, usize, f64)>::new();
+
+    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
+        let start = bin.start(entry.timestamp.date_naive());
+        match bins.last_mut() {
+            // entries are chronological, so a bin can't appear again later
+            Some((last, plays, time)) if *last == start => {
+                *plays += 1;
+                *time += minutes_played(entry);
+            }
+            _ => bins.push((start, 1, minutes_played(entry))),
+        }
+    }
+
+    bins.into_iter()
+        .map(|(start, plays, time)| {
+            let value = if minutes { time } else { plays as f64 };
+            (start_of_day(start), value)
+        })
+        .collect()
+}
+
+/// Returns the average of daily plays (or minutes listened if `minutes` is `true`)
+/// of an aspect over the last `days` days, dated at the start of each day
+///
+/// Covers every day from the first to the last play of `aspect`,
+/// so days without plays lower the average.
+/// The first `days - 1` days are averaged over the days so far.
+///
+/// Returns an empty series if `aspect` is not in `entries`
+///
+/// # Panics
+///
+/// Panics if `days` is 0
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn rolling_average<Asp: Music>(
+    entries: &[SongEntry],
+    aspect: &Asp,
+    days: usize,
+    minutes: bool,
+) -> Series {
+    assert!(days > 0, "a rolling average needs at least one day");
+
+    // plays or minutes of each day from the first to the last play
+    let mut first_day: Option<NaiveDate> = None;
+    let mut daily = Vec::<f64>::new();
+
+    for entry in entries.iter().filter(|entry| aspect.is_entry(entry)) {
+        let day = entry.timestamp.date_naive();
+        let first = *first_day.get_or_insert(day);
+        let index = usize::try_from((day - first).num_days()).unwrap();
+        if index >= daily.len() {
+            daily.resize(index + 1, 0.0);
+        }
+        daily[index] += if minutes { minutes_played(entry) } else { 1.0 };
+    }
+
+    let Some(first) = first_day else {
+        return vec![];
+    };
+
+    let mut averages = Series::with_capacity(daily.len());
+    let mut window = 0.0;
+
+    for (index, (day, value)) in first.iter_days().zip(&daily).enumerate() {
+        window += value;
+        if index >= days {
+            window -= daily[index - days];
+        }
+        let window_days = (index + 1).min(days);
+
+        averages.push((start_of_day(day), window / window_days as f64));
+    }
+
+    averages
+}
+
+/// Module for relative series
+///
+/// Either to all plays, the artist, the album or all plays of each period.
+/// The values are percentages, i.e. between 0 and 100.
+///
+/// The `duration_*` functions are shares of the time listened instead of plays.
+pub mod relative {
+    use chrono::NaiveDate;
+
+    use super::{minutes_played, start_of_day, Bin, Series};
+    use crate::aspect::{Album, Music, Song};
+    use crate::entry::SongEntry;
+
+    /// Returns the share of plays of an aspect
+    /// among all plays so far after each entry
+    ///
+    /// Starts at the first play of `aspect`,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    pub fn to_all<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> Series {
+        relative_to(entries.iter(), aspect, |_| 1.0)
+    }
+
+    /// Returns the share of the time listened to an aspect
+    /// among the time listened to anything so far after each entry
+    ///
+    /// Starts at the first play of `aspect`,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    pub fn duration_to_all<Asp: Music>(entries: &[SongEntry], aspect: &Asp) -> Series {
+        relative_to(entries.iter(), aspect, minutes_played)
+    }
+
+    /// Returns the share of plays of an [`Album`] or [`Song`]
+    /// among all plays of the corresponding artist so far after each of them
+    ///
+    /// Starts at the first play of `aspect`,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    pub fn to_artist<Asp: AsRef<Album> + Music>(entries: &[SongEntry], aspect: &Asp) -> Series {
+        let artist = &aspect.as_ref().artist;
+        relative_to(
+            entries.iter().filter(|entry| artist.is_entry(entry)),
+            aspect,
+            |_| 1.0,
+        )
+    }
+
+    /// Returns the share of the time listened to an [`Album`] or [`Song`]
+    /// among the time listened to the corresponding artist so far after each of them
+    ///
+    /// Starts at the first play of `aspect`,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    pub fn duration_to_artist<Asp: AsRef<Album> + Music>(
+        entries: &[SongEntry],
+        aspect: &Asp,
+    ) -> Series {
+        let artist = &aspect.as_ref().artist;
+        relative_to(
+            entries.iter().filter(|entry| artist.is_entry(entry)),
+            aspect,
+            minutes_played,
+        )
+    }
+
+    /// Returns the share of plays of a [`Song`]
+    /// among all plays of the corresponding album so far after each of them
+    ///
+    /// Starts at the first play of `song`,
+    /// returns an empty series if `song` is not in `entries`
+    #[must_use]
+    pub fn to_album(entries: &[SongEntry], song: &Song) -> Series {
+        let album = &song.album;
+        relative_to(
+            entries.iter().filter(|entry| album.is_entry(entry)),
+            song,
+            |_| 1.0,
+        )
+    }
+
+    /// Returns the share of the time listened to a [`Song`]
+    /// among the time listened to the corresponding album so far after each of them
+    ///
+    /// Starts at the first play of `song`,
+    /// returns an empty series if `song` is not in `entries`
+    #[must_use]
+    pub fn duration_to_album(entries: &[SongEntry], song: &Song) -> Series {
+        let album = &song.album;
+        relative_to(
+            entries.iter().filter(|entry| album.is_entry(entry)),
+            song,
+            minutes_played,
+        )
+    }
+
+    /// Returns the share of plays of an aspect among all plays
+    /// of a custom group of aspects so far after each of them,
+    /// e.g. a song relative to all power metal artists
+    ///
+    /// Plays of `aspect` itself always count towards the group,
+    /// even if it isn't part of any aspect in `group`
+    ///
+    /// Starts at the first play of `aspect`,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    pub fn to_aspects<Asp: Music, Grp: Music>(
+        entries: &[SongEntry],
+        aspect: &Asp,
+        group: &[Grp],
+    ) -> Series {
+        relative_to(
+            entries.iter().filter(|entry| {
+                aspect.is_entry(entry) || group.iter().any(|member| member.is_entry(entry))
+            }),
+            aspect,
+            |_| 1.0,
+        )
+    }
+
+    /// Returns the share of plays of an aspect
+    /// among all plays within each [`Bin`], dated at the start of the bin
+    ///
+    /// Unlike [`to_all`], each point only takes the plays of its own
+    /// week, month etc. into account, so that recent shifts in taste are visible
+    ///
+    /// Starts at the first bin `aspect` is played in,
+    /// returns an empty series if `aspect` is not in `entries`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_period<Asp: Music>(entries: &[SongEntry], aspect: &Asp, bin: Bin) -> Series {
+        // first day of each bin with the aspect plays and all plays in it
+        let mut bins = Vec::<(NaiveDate, usize, usize)>::new();
+
+        for entry in entries {
+            let start = bin.start(entry.timestamp.date_naive());
+            let is_aspect = usize::from(aspect.is_entry(entry));
+            match bins.last_mut() {
+                Some((last, aspect_plays, all_plays)) if *last == start => {
+                    *aspect_plays += is_aspect;
+                    *all_plays += 1;
+                }
+                _ => bins.push((start, is_aspect, 1)),
+            }
+        }
+
+        bins.into_iter()
+            .skip_while(|(_, aspect_plays, _)| *aspect_plays == 0)
+            .map(|(start, aspect_plays, all_plays)| {
+                (
+                    start_of_day(start),
+                    100.0 * (aspect_plays as f64 / all_plays as f64),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the share of `aspect` among `entries` after each entry,
+    /// starting at the first play of `aspect`
+    ///
+    /// Each entry counts as much as `weight` returns for it,
+    /// e.g. 1 for plays or the minutes listened for durations
+    fn relative_to<'a, Asp: Music>(
+        entries: impl Iterator<Item = &'a SongEntry>,
+        aspect: &Asp,
+        weight: fn(&SongEntry) -> f64,
+    ) -> Series {
+        let mut series = Series::new();
+
+        let mut aspect_plays = 0.0;
+        let mut all_plays = 0.0;
+
+        // the series should start at the first time the aspect is played
+        let mut aspect_found = false;
+
+        for entry in entries {
+            let weight = weight(entry);
+            all_plays += weight;
+
+            if aspect.is_entry(entry) {
+                aspect_found = true;
+                aspect_plays += weight;
+            }
+            if aspect_found {
+                // *100 so that the percentage is easier to read...
+                series.push((entry.timestamp, 100.0 * (aspect_plays / all_plays)));
+            }
+        }
+
+        series
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aspect::Artist;
+
+    /// Loads the example `endsong.json` from `stuff/example_endsong`
+    fn example_entries() -> crate::entry::SongEntries {
+        let paths = [format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        )];
+        crate::entry::SongEntries::new(&paths).unwrap()
+    }
+
+    #[test]
+    // whole numbers are represented exactly in f64
+    #[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+    fn series() {
+        let entries = example_entries();
+        let artist = Artist::from(entries.first().unwrap());
+        let plays = crate::gather::plays(&entries, &artist) as f64;
+
+        let cumulative = absolute(&entries, &artist);
+        assert_eq!(cumulative.len() as f64, plays);
+        assert_eq!(cumulative.last().unwrap().1, plays);
+
+        // every play is in exactly one bin
+        for bin in [Bin::Day, Bin::Week, Bin::Month] {
+            let bins = binned(&entries, &artist, bin, false);
+            assert_eq!(bins.iter().map(|(_, p)| p).sum::<f64>(), plays);
+        }
+
+        // one point per month with the plays at its end
+        let monthly = coarsen(cumulative, Bin::Month);
+        assert_eq!(
+            monthly.len(),
+            binned(&entries, &artist, Bin::Month, false).len()
+        );
+        assert_eq!(monthly.last().unwrap().1, plays);
+
+        let relative = relative::to_all(&entries, &artist);
+        assert!(relative.iter().all(|(_, p)| (0.0..=100.0).contains(p)));
+        // starts at the first play
+        assert_eq!(relative[0], (entries[0].timestamp, 100.0));
+
+        // minutes listened add up to the total listening time
+        let minutes = absolute_duration(&entries, &artist).last().unwrap().1;
+        let total = crate::gather::listening_time_of(&entries, &artist);
+        assert!((minutes - total.num_milliseconds() as f64 / 60_000.0).abs() < 1e-6);
+        // bars and lines agree
+        let binned_minutes = binned(&entries, &artist, Bin::Month, true)
+            .iter()
+            .map(|(_, m)| m)
+            .sum::<f64>();
+        assert!((binned_minutes - minutes).abs() < 1e-6);
+        assert!(relative::duration_to_all(&entries, &artist)
+            .iter()
+            .all(|(_, p)| (0.0..=100.0).contains(p)));
+
+        // smoothing keeps the amount of points and stays within the values
+        for smoothing in [Smoothing::Exponential(0.1), Smoothing::Window(50)] {
+            let smoothed = smooth(relative.clone(), smoothing);
+            assert_eq!(smoothed.len(), relative.len());
+            assert!(smoothed.iter().all(|(_, p)| (0.0..=100.0).contains(p)));
+        }
+        assert_eq!(smooth(relative.clone(), Smoothing::Window(1)), relative);
+
+        // relative to itself it's always 100%
+        assert!(
+            relative::to_aspects(&entries, &artist, std::slice::from_ref(&artist))
+                .iter()
+                .all(|(_, p)| *p == 100.0)
+        );
+        assert_eq!(
+            relative::to_aspects(&entries, &artist, &Vec::<Artist>::new()),
+            relative::to_aspects(&entries, &artist, std::slice::from_ref(&artist))
+        );
+
+        assert!(absolute(&entries, &Artist::new("not an artist")).is_empty());
+        assert!(rolling_average(&entries, &Artist::new("not an artist"), 30, false).is_empty());
+
+        // a one-day average is just the plays or minutes of each day
+        let daily_plays = rolling_average(&entries, &artist, 1, false);
+        assert_eq!(daily_plays.iter().map(|(_, p)| p).sum::<f64>(), plays);
+        let daily_minutes = rolling_average(&entries, &artist, 1, true);
+        assert!((daily_minutes.iter().map(|(_, m)| m).sum::<f64>() - minutes).abs() < 1e-6);
+    }
+}
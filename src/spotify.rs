@@ -0,0 +1,485 @@
+//! Module for enriching the dataset with data from the [Spotify Web API](https://developer.spotify.com/documentation/web-api)
+//!
+//! Only available with the `spotify` feature
+//!
+//! Uses the client credentials flow, so only a client ID and secret of
+//! an app created in the Spotify developer dashboard are needed. Fetches the
+//! real durations of songs (to replace the [calculated ones][crate::entry::SongEntries::durations]),
+//! the number of tracks on albums (for [`gather::album_completion`][crate::gather::album_completion])
+//! and the genres of artists. Every answer is cached on disk, so each track
+//! and artist is only requested once.
+//!
+//! ```no_run
+//! use endsong::prelude::*;
+//! use endsong::spotify::Spotify;
+//!
+//! # let paths = ["endsong_0.json"];
+//! let entries = SongEntries::new(&paths)?.sum_different_capitalization();
+//!
+//! let mut spotify = Spotify::new("client id", "client secret", "spotify_cache.json")?;
+//! let durations = spotify.durations(&entries)?;
+//! let entries = entries.with_durations(durations);
+//!
+//! let track_counts = spotify.album_track_counts(&entries)?;
+//! let completion = gather::album_completion(&entries, &track_counts);
+//!
+//! // writes the cache to disk
+//! spotify.save()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use chrono::TimeDelta;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use crate::aspect::{Album, Artist, Song};
+use crate::entry::SongEntry;
+
+/// Base URL of the Spotify Web API
+const API: &str = "https://api.spotify.com/v1";
+
+/// URL for requesting an access token
+const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+
+/// Maximum number of IDs in a single request to `/tracks` or `/artists`
+const MAX_IDS: usize = 50;
+
+/// How often a request is retried after being rate limited
+const MAX_RETRIES: usize = 3;
+
+/// Errors that can occur when querying the Spotify API
+#[derive(Error, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum SpotifyError {
+    /// Used when a request fails
+    #[error("Error while querying Spotify: {0}")]
+    Http(#[from] Box<ureq::Error>),
+    /// Used when reading a response or the cache fails
+    #[error("Error while reading/writing: {0}")]
+    Io(#[from] std::io::Error),
+    /// Used when the cache can't be (de)serialized
+    #[error("Error while (de)serializing the cache: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Information about a track from the Spotify API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackInfo {
+    /// duration of the track in milliseconds
+    pub duration_ms: i64,
+    /// number of tracks on the album of the track
+    pub album_tracks: usize,
+    /// Spotify IDs and names of the artists of the track
+    pub artists: Vec<(String, String)>,
+}
+
+/// On-disk cache of the requests, [`None`] if Spotify didn't find anything
+#[derive(Default, Serialize, Deserialize)]
+struct Cache {
+    /// key: Spotify track ID
+    tracks: HashMap<String, Option<TrackInfo>>,
+    /// key: Spotify artist ID, value: genres
+    artists: HashMap<String, Option<Vec<String>>>,
+}
+
+/// Response of a token request
+#[derive(Deserialize)]
+struct Token {
+    /// the token
+    access_token: String,
+    /// seconds until the token expires
+    expires_in: u64,
+}
+
+/// Response of `/tracks`
+#[derive(Deserialize)]
+struct Tracks {
+    /// the tracks in the order of the IDs, [`None`] for unknown IDs
+    tracks: Vec<Option<Track>>,
+}
+
+/// A track of [`Tracks`]
+#[derive(Deserialize)]
+struct Track {
+    /// duration in milliseconds
+    duration_ms: i64,
+    /// album of the track
+    album: TrackAlbum,
+    /// artists of the track
+    artists: Vec<TrackArtist>,
+}
+
+/// Album of a [`Track`]
+#[derive(Deserialize)]
+struct TrackAlbum {
+    /// number of tracks on the album
+    total_tracks: usize,
+}
+
+/// Artist of a [`Track`]
+#[derive(Deserialize)]
+struct TrackArtist {
+    /// Spotify ID
+    id: String,
+    /// name
+    name: String,
+}
+
+/// Response of `/artists`
+#[derive(Deserialize)]
+struct Artists {
+    /// the artists in the order of the IDs, [`None`] for unknown IDs
+    artists: Vec<Option<ArtistGenres>>,
+}
+
+/// An artist of [`Artists`]
+#[derive(Deserialize)]
+struct ArtistGenres {
+    /// genres of the artist
+    genres: Vec<String>,
+}
+
+/// Client for the Spotify Web API
+///
+/// See the [module documentation][self] for an example
+pub struct Spotify {
+    /// client ID of the Spotify app
+    client_id: String,
+    /// client secret of the Spotify app
+    client_secret: String,
+    /// where the cache is saved
+    cache_path: PathBuf,
+    /// answers of previous requests
+    cache: Cache,
+    /// HTTP client
+    agent: ureq::Agent,
+    /// current access token and when it expires
+    token: Option<(String, Instant)>,
+}
+impl Spotify {
+    /// Creates a new client with the credentials of a Spotify app
+    /// using (and later saving) the cache at `cache_path`
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cache file exists but can't be read
+    pub fn new<P: AsRef<Path>>(
+        client_id: &str,
+        client_secret: &str,
+        cache_path: P,
+    ) -> Result<Self, SpotifyError> {
+        let cache_path = cache_path.as_ref().to_path_buf();
+        let cache = match std::fs::read(&cache_path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Cache::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Spotify {
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            cache_path,
+            cache,
+            agent: ureq::Agent::new(),
+            token: None,
+        })
+    }
+
+    /// Writes the cache to disk
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cache can't be written
+    pub fn save(&self) -> Result<(), SpotifyError> {
+        let contents = serde_json::to_vec(&self.cache)?;
+        std::fs::write(&self.cache_path, contents)?;
+        Ok(())
+    }
+
+    /// Returns the information about every song in `entries`
+    /// Spotify knows about
+    ///
+    /// If a song has multiple Spotify IDs, the first known one is used
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn tracks(
+        &mut self,
+        entries: &[SongEntry],
+    ) -> Result<HashMap<Song, TrackInfo>, SpotifyError> {
+        // spotify:track:<id> -> <id>
+        let ids = entries
+            .iter()
+            .filter_map(|entry| Some((Song::from(entry), entry.id.strip_prefix("spotify:track:")?)))
+            .unique()
+            .collect_vec();
+
+        let missing = ids
+            .iter()
+            .map(|(_, id)| *id)
+            .filter(|id| !self.cache.tracks.contains_key(*id))
+            .unique()
+            .collect_vec();
+        if !missing.is_empty() {
+            info!("Requesting {} tracks from Spotify...", missing.len());
+        }
+        for chunk in missing.chunks(MAX_IDS) {
+            let response: Tracks = self.get("tracks", chunk)?;
+            for (id, track) in chunk.iter().zip(response.tracks) {
+                let info = track.map(|track| TrackInfo {
+                    duration_ms: track.duration_ms,
+                    album_tracks: track.album.total_tracks,
+                    artists: track
+                        .artists
+                        .into_iter()
+                        .map(|artist| (artist.id, artist.name))
+                        .collect(),
+                });
+                self.cache.tracks.insert((*id).to_string(), info);
+            }
+        }
+
+        let mut tracks = HashMap::new();
+        for (song, id) in ids {
+            if let Some(Some(info)) = self.cache.tracks.get(id) {
+                tracks.entry(song).or_insert_with(|| info.clone());
+            }
+        }
+        Ok(tracks)
+    }
+
+    /// Returns the real durations of the songs in `entries`,
+    /// to be used with [`SongEntries::with_durations`][crate::entry::SongEntries::with_durations]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn durations(
+        &mut self,
+        entries: &[SongEntry],
+    ) -> Result<HashMap<Song, TimeDelta>, SpotifyError> {
+        Ok(self
+            .tracks(entries)?
+            .into_iter()
+            .map(|(song, info)| (song, TimeDelta::milliseconds(info.duration_ms)))
+            .collect())
+    }
+
+    /// Returns the number of tracks on the albums in `entries`,
+    /// to be used with [`gather::album_completion`][crate::gather::album_completion]
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn album_track_counts(
+        &mut self,
+        entries: &[SongEntry],
+    ) -> Result<HashMap<Album, usize>, SpotifyError> {
+        Ok(self
+            .tracks(entries)?
+            .into_iter()
+            .map(|(song, info)| (Album::from(&song), info.album_tracks))
+            .collect())
+    }
+
+    /// Returns the genres of the artists in `entries`
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a request fails
+    pub fn artist_genres(
+        &mut self,
+        entries: &[SongEntry],
+    ) -> Result<HashMap<Artist, Vec<String>>, SpotifyError> {
+        // the artist of an entry is one of the artists of its track
+        let mut artist_ids: HashMap<Artist, String> = HashMap::new();
+        for (song, info) in self.tracks(entries)? {
+            let artist = Artist::from(&song);
+            if artist_ids.contains_key(&artist) {
+                continue;
+            }
+            if let Some((id, _)) = info.artists.iter().find(|(_, name)| *name == *artist.name) {
+                artist_ids.insert(artist, id.clone());
+            }
+        }
+
+        let missing = artist_ids
+            .values()
+            .filter(|id| !self.cache.artists.contains_key(*id))
+            .map(String::as_str)
+            .unique()
+            .collect_vec();
+        if !missing.is_empty() {
+            info!("Requesting {} artists from Spotify...", missing.len());
+        }
+        for chunk in missing.chunks(MAX_IDS) {
+            let response: Artists = self.get("artists", chunk)?;
+            for (id, artist) in chunk.iter().zip(response.artists) {
+                self.cache
+                    .artists
+                    .insert((*id).to_string(), artist.map(|artist| artist.genres));
+            }
+        }
+
+        Ok(artist_ids
+            .into_iter()
+            .filter_map(|(artist, id)| Some((artist, self.cache.artists.get(&id)?.clone()?)))
+            .collect())
+    }
+
+    /// Returns a valid access token, requesting a new one if needed
+    fn token(&mut self) -> Result<String, SpotifyError> {
+        if let Some((token, expires)) = &self.token {
+            if Instant::now() < *expires {
+                return Ok(token.clone());
+            }
+        }
+
+        let credentials = base64(format!("{}:{}", self.client_id, self.client_secret).as_bytes());
+        let token: Token = self
+            .agent
+            .post(TOKEN_URL)
+            .set("Authorization", &format!("Basic {credentials}"))
+            .send_form(&[("grant_type", "client_credentials")])
+            .map_err(Box::new)?
+            .into_json()?;
+
+        // a minute early so that it doesn't expire during a request
+        let expires = Instant::now() + Duration::from_secs(token.expires_in.saturating_sub(60));
+        self.token = Some((token.access_token.clone(), expires));
+        Ok(token.access_token)
+    }
+
+    /// Sends a GET request to the given API endpoint with the given IDs
+    ///
+    /// Waits and retries when rate limited
+    fn get<T: serde::de::DeserializeOwned>(
+        &mut self,
+        endpoint: &str,
+        ids: &[&str],
+    ) -> Result<T, SpotifyError> {
+        let url = format!("{API}/{endpoint}");
+        let ids = ids.join(",");
+
+        let mut retries = 0;
+        loop {
+            let token = self.token()?;
+            let result = self
+                .agent
+                .get(&url)
+                .query("ids", &ids)
+                .set("Authorization", &format!("Bearer {token}"))
+                .call();
+
+            match result {
+                Ok(response) => return Ok(response.into_json()?),
+                Err(ureq::Error::Status(429, response)) if retries < MAX_RETRIES => {
+                    let seconds = response
+                        .header("Retry-After")
+                        .and_then(|seconds| seconds.parse().ok())
+                        .unwrap_or(1);
+                    info!("Rate limited by Spotify, waiting {seconds}s...");
+                    sleep(Duration::from_secs(seconds));
+                    retries += 1;
+                }
+                Err(e) => return Err(Box::new(e).into()),
+            }
+        }
+    }
+}
+
+/// Encodes `bytes` in standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    /// The base64 alphabet
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | u32::from(byte) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::SongEntries;
+
+    #[test]
+    fn cached_tracks() {
+        let paths = vec![format!(
+            "{}/stuff/example_endsong/endsong_0.json",
+            std::env::current_dir().unwrap().display()
+        )];
+        let entries = SongEntries::new(&paths).unwrap();
+        let path =
+            std::env::temp_dir().join(format!("endsong_spotify_{}.json", std::process::id()));
+
+        let mut spotify = Spotify::new("id", "secret", &path).unwrap();
+        // every track and artist is cached -> no requests
+        for entry in entries.iter() {
+            let id = entry.id.strip_prefix("spotify:track:").unwrap().to_string();
+            let info = TrackInfo {
+                duration_ms: 60_000,
+                album_tracks: 10,
+                artists: vec![(format!("artist {}", entry.artist), entry.artist.to_string())],
+            };
+            spotify.cache.tracks.insert(id, Some(info));
+            spotify.cache.artists.insert(
+                format!("artist {}", entry.artist),
+                Some(vec![String::from("metal")]),
+            );
+        }
+        spotify.save().unwrap();
+
+        let mut spotify = Spotify::new("id", "secret", &path).unwrap();
+        let durations = spotify.durations(&entries).unwrap();
+        let song = Song::from(&entries[0]);
+        assert_eq!(durations[&song], TimeDelta::try_minutes(1).unwrap());
+        assert_eq!(
+            spotify.album_track_counts(&entries).unwrap()[&Album::from(&song)],
+            10
+        );
+        assert_eq!(
+            spotify.artist_genres(&entries).unwrap()[&Artist::from(&song)],
+            vec!["metal"]
+        );
+        assert!(spotify.token.is_none());
+
+        let entries = entries.with_spotify_durations(&mut spotify).unwrap();
+        assert_eq!(
+            entries.durations()[&song],
+            TimeDelta::try_minutes(1).unwrap()
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(super::base64(b""), "");
+        assert_eq!(super::base64(b"f"), "Zg==");
+        assert_eq!(super::base64(b"fo"), "Zm8=");
+        assert_eq!(super::base64(b"foo"), "Zm9v");
+        assert_eq!(super::base64(b"id:secret"), "aWQ6c2VjcmV0");
+    }
+}